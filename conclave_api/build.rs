@@ -1,3 +1,38 @@
+use std::process::Command;
+
+/// Run git with `args`, returning trimmed stdout if it succeeded
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=migrations");
+
+    // Builds without a checkout (e.g. Docker) can pass the commit in GIT_SHA
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    let git_sha = std::env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| git(&["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_SHA={git_sha}");
+
+    // Rebuild when a commit is made or another branch checked out
+    if let Some(head) = git(&["rev-parse", "--git-path", "HEAD"]) {
+        println!("cargo:rerun-if-changed={head}");
+    }
+    if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"])
+        && let Some(branch_ref) = git(&["rev-parse", "--git-path", &branch])
+    {
+        println!("cargo:rerun-if-changed={branch_ref}");
+    }
+
+    // When this script last ran, which is whenever the above changed
+    let build_time = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    println!("cargo:rustc-env=BUILD_TIME={build_time}");
 }
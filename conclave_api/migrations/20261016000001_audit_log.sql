@@ -0,0 +1,13 @@
+-- Append-only audit trail of every game-mutating action
+-- Rows are never updated or deleted by the application
+CREATE TABLE IF NOT EXISTS audit_log (
+    id TEXT PRIMARY KEY,
+    game_id TEXT NOT NULL,
+    actor_clerk_user_id TEXT NOT NULL, -- Clerk user who performed the action
+    action TEXT NOT NULL, -- e.g. 'life_update', 'commander_damage', 'join', 'leave', 'end_game', 'toggle_partner'
+    payload_json TEXT NOT NULL, -- JSON-encoded action details
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_game_created ON audit_log(game_id, created_at);
@@ -0,0 +1,3 @@
+-- Optional labels for the commander (and partner) each player is piloting
+ALTER TABLE players ADD COLUMN commander_name TEXT;
+ALTER TABLE players ADD COLUMN partner_name TEXT;
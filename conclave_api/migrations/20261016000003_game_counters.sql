@@ -0,0 +1,10 @@
+-- Table-wide tallies (storm count, etc.) keyed by name within a game
+CREATE TABLE IF NOT EXISTS game_counters (
+    game_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    value INTEGER NOT NULL DEFAULT 0,
+    reset_each_turn INTEGER NOT NULL DEFAULT 0, -- Reset to 0 on NextTurn when set
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (game_id, name),
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
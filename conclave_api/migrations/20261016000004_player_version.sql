@@ -0,0 +1,2 @@
+-- Monotonic per-player version, bumped on every mutation of the row
+ALTER TABLE players ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- When set, only a player themselves or the host may change that player's life
+ALTER TABLE games ADD COLUMN strict_control INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Format preset the game was created with (NULL for custom games)
+ALTER TABLE games ADD COLUMN format TEXT;
+ALTER TABLE games ADD COLUMN max_players INTEGER NOT NULL DEFAULT 8;
@@ -0,0 +1,2 @@
+-- Cancelled games are soft-deleted: kept for history/audit, hidden from lists
+ALTER TABLE games ADD COLUMN deleted_at TEXT;
@@ -0,0 +1,3 @@
+-- How a finished game ended when not by normal play, e.g. 'no_contest' when
+-- the stale game sweeper ends a forgotten game
+ALTER TABLE games ADD COLUMN outcome TEXT;
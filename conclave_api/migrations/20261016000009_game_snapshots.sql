@@ -0,0 +1,11 @@
+-- Point-in-time copies of a game's full state that a host can restore
+CREATE TABLE IF NOT EXISTS game_snapshots (
+    id TEXT PRIMARY KEY,
+    game_id TEXT NOT NULL,
+    created_by_clerk_user_id TEXT NOT NULL,
+    state_json TEXT NOT NULL, -- JSON-encoded GameState
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_snapshots_game_id ON game_snapshots(game_id);
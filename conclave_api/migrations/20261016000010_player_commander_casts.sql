@@ -0,0 +1,2 @@
+-- Times the player's commander has been cast; commander tax is 2 per cast
+ALTER TABLE players ADD COLUMN commander_casts INTEGER NOT NULL DEFAULT 0;
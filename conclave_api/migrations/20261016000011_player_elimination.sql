@@ -0,0 +1,2 @@
+-- Why the player was eliminated (life, commander_damage, poison, concede); NULL while still in
+ALTER TABLE players ADD COLUMN elimination_cause TEXT;
@@ -0,0 +1,3 @@
+-- Opening hand metadata for post-game analysis; no effect on life
+ALTER TABLE players ADD COLUMN mulligans INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE players ADD COLUMN hand_size INTEGER NOT NULL DEFAULT 7;
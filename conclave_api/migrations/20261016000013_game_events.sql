@@ -0,0 +1,9 @@
+-- Append-only log of every broadcast message, numbered per game for replay
+CREATE TABLE IF NOT EXISTS game_events (
+    game_id TEXT NOT NULL,
+    seq INTEGER NOT NULL, -- 1-based, increasing per game
+    payload_json TEXT NOT NULL, -- The JSON-encoded WebSocketMessage as broadcast
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (game_id, seq),
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+-- Elo-style skill rating per user, updated when a game ends with a winner
+CREATE TABLE IF NOT EXISTS player_ratings (
+    clerk_user_id TEXT PRIMARY KEY,
+    rating REAL NOT NULL,
+    games_played INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_player_ratings_rating ON player_ratings(rating DESC);
@@ -0,0 +1,9 @@
+-- Badges a user has earned; each badge is earned once, in the first qualifying game
+CREATE TABLE IF NOT EXISTS user_achievements (
+    clerk_user_id TEXT NOT NULL,
+    achievement_id TEXT NOT NULL,
+    game_id TEXT NOT NULL,
+    earned_at TEXT NOT NULL,
+    PRIMARY KEY (clerk_user_id, achievement_id),
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
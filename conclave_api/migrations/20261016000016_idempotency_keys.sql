@@ -0,0 +1,11 @@
+-- Client-supplied keys for game creation, so a retried request returns the original game
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    clerk_user_id TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    game_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (clerk_user_id, idempotency_key),
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
@@ -0,0 +1,3 @@
+-- Commander 2 rows are deactivated rather than deleted when a partner is
+-- disabled, so re-enabling the partner restores the damage dealt so far
+ALTER TABLE commander_damage ADD COLUMN active BOOLEAN NOT NULL DEFAULT 1;
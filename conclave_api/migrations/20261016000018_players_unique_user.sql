@@ -0,0 +1,5 @@
+-- A user holds at most one seat per game. If racing joins left duplicate
+-- seats, creating the index fails and the migration stops: the duplicates
+-- each carry their own life changes, commander damage and decks, so merge
+-- them by hand rather than losing that history.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_players_game_user_unique ON players(game_id, clerk_user_id);
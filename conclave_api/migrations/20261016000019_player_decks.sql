@@ -0,0 +1,14 @@
+-- Deck details a player attached for a game
+CREATE TABLE IF NOT EXISTS player_decks (
+    player_id TEXT PRIMARY KEY,
+    game_id TEXT NOT NULL,
+    commander_name TEXT NOT NULL,
+    partner_name TEXT,
+    color_identity TEXT, -- Some of "WUBRG" in that order, or "C" for colorless
+    decklist_url TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (player_id) REFERENCES players (id) ON DELETE CASCADE,
+    FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_player_decks_game_id ON player_decks(game_id);
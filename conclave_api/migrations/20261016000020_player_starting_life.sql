@@ -0,0 +1,3 @@
+-- Starting life per seat, so a handicapped player can start above the game's default
+ALTER TABLE players ADD COLUMN starting_life INTEGER;
+UPDATE players SET starting_life = (SELECT g.starting_life FROM games g WHERE g.id = players.game_id);
@@ -0,0 +1,3 @@
+-- Cosmetic seat color, as physical tables assign; unique within a game
+ALTER TABLE players ADD COLUMN seat_color TEXT;
+CREATE UNIQUE INDEX idx_players_game_seat_color ON players(game_id, seat_color) WHERE seat_color IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Commander damage that eliminates a player, for variants that don't use 21
+ALTER TABLE games ADD COLUMN commander_lethal INTEGER NOT NULL DEFAULT 21;
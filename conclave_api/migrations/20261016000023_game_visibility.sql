@@ -0,0 +1,4 @@
+-- Who can find and join a game: 'public' games are listed, 'unlisted' ones
+-- are joinable by id only, and 'private' ones also need the join code
+ALTER TABLE games ADD COLUMN visibility TEXT NOT NULL DEFAULT 'public';
+ALTER TABLE games ADD COLUMN join_code TEXT;
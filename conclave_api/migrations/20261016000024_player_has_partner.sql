@@ -0,0 +1,21 @@
+-- Whether the player has a partner (second commander), so clients needn't
+-- infer it from Commander 2 rows
+ALTER TABLE players ADD COLUMN has_partner BOOLEAN NOT NULL DEFAULT 0;
+
+-- Commander 2 rows are created in both directions, so a row alone doesn't say
+-- whose partner it is. A player with a partner has active rows toward every
+-- other player in the game; a partner name settles two-player games.
+UPDATE players SET has_partner = 1
+WHERE partner_name IS NOT NULL
+   OR (
+        EXISTS (SELECT 1 FROM players o WHERE o.game_id = players.game_id AND o.id != players.id)
+        AND NOT EXISTS (
+            SELECT 1 FROM players o
+            WHERE o.game_id = players.game_id AND o.id != players.id
+              AND NOT EXISTS (
+                SELECT 1 FROM commander_damage cd
+                WHERE cd.from_player_id = players.id AND cd.to_player_id = o.id
+                  AND cd.commander_number = 2 AND cd.active = 1
+              )
+        )
+   );
@@ -0,0 +1,3 @@
+-- When a game left the lobby via an explicit start; NULL for games created
+-- active, whose start is their first life change
+ALTER TABLE games ADD COLUMN started_at TEXT;
@@ -0,0 +1,3 @@
+-- The player who caused a life change (e.g. the attacker), when the client
+-- says so. Null for untracked changes and everything recorded before this.
+ALTER TABLE life_changes ADD COLUMN source_player_id TEXT;
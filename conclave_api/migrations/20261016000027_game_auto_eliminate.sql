@@ -0,0 +1,3 @@
+-- Whether reaching 0 life or lethal commander damage eliminates a player, for
+-- pods that track elimination themselves
+ALTER TABLE games ADD COLUMN auto_eliminate BOOLEAN NOT NULL DEFAULT 1;
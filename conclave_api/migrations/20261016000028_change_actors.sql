@@ -0,0 +1,4 @@
+-- The user who submitted each change, for audit and "last edited by" UIs.
+-- Null for rows written before this.
+ALTER TABLE commander_damage ADD COLUMN updated_by TEXT;
+ALTER TABLE life_changes ADD COLUMN created_by TEXT;
@@ -0,0 +1,3 @@
+-- Why a life change happened, from a fixed vocabulary (see LifeChangeReason).
+-- Null when the client didn't say, and for everything recorded before this.
+ALTER TABLE life_changes ADD COLUMN reason TEXT;
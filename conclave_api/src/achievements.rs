@@ -0,0 +1,156 @@
+use crate::{
+    database,
+    errors::Result,
+    models::{CommanderDamage, GameWithPlayers, LifeChange, UserAchievement},
+};
+use sqlx::SqlitePool;
+use tracing::info;
+use uuid::Uuid;
+
+/// Everything a rule may look at once a game is over
+pub struct FinishedGame {
+    pub game: GameWithPlayers,
+    /// Every life change in the game, oldest first
+    pub life_changes: Vec<LifeChange>,
+    pub commander_damage: Vec<CommanderDamage>,
+}
+
+/// A badge and the test that decides who earned it in a finished game.
+/// Adding a badge is just adding an entry to `RULES`.
+pub struct Rule {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Clerk user ids that earned the badge in this game
+    pub earned_by: fn(&FinishedGame) -> Vec<String>,
+}
+
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "win_at_one_life",
+        name: "By a Thread",
+        description: "Win a game at exactly 1 life",
+        earned_by: |g| winner_if(g, |winner| winner.current_life == 1),
+    },
+    Rule {
+        id: "lethal_commander_damage",
+        name: "Commander's Wrath",
+        description: "Deal lethal commander damage to a single opponent",
+        earned_by: |g| {
+            g.commander_damage
+                .iter()
+                .filter(|cd| cd.damage >= g.game.game.commander_lethal)
+                .filter_map(|cd| g.game.players.iter().find(|p| p.id == cd.from_player_id))
+                .map(|p| p.clerk_user_id.clone())
+                .collect()
+        },
+    },
+    Rule {
+        id: "win_four_player_game",
+        name: "King of the Table",
+        description: "Win a game with 4 or more players",
+        earned_by: |g| winner_if(g, |_| g.game.players.len() >= 4),
+    },
+    Rule {
+        id: "comeback_under_five",
+        name: "Comeback Kid",
+        description: "Win a game after falling below 5 life",
+        earned_by: |g| {
+            winner_if(g, |winner| {
+                g.life_changes
+                    .iter()
+                    .any(|c| c.player_id == winner.id && c.new_life_total < 5)
+            })
+        },
+    },
+];
+
+/// The winner's user id when there is a winner satisfying `test`
+fn winner_if(game: &FinishedGame, test: impl Fn(&crate::models::Player) -> bool) -> Vec<String> {
+    game.game
+        .winner
+        .iter()
+        .filter(|winner| test(winner))
+        .map(|winner| winner.clerk_user_id.clone())
+        .collect()
+}
+
+pub fn find_rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Run every rule against a finished game and store the badges earned.
+/// Returns only badges that are new; re-running for the same game is a no-op.
+pub async fn award_for_game(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<UserAchievement>> {
+    let finished = FinishedGame {
+        game: database::get_game_with_players(pool, game_id).await?,
+        life_changes: database::get_life_changes_for_game(pool, game_id).await?,
+        commander_damage: database::get_commander_damage_for_game(pool, game_id).await?,
+    };
+
+    let mut earned = Vec::new();
+    for rule in RULES {
+        let mut users = (rule.earned_by)(&finished);
+        users.sort();
+        users.dedup();
+        earned.extend(users.into_iter().map(|user| (user, rule.id)));
+    }
+
+    let awarded = database::insert_user_achievements(pool, game_id, &earned).await?;
+    for achievement in &awarded {
+        info!(
+            "User {} earned achievement {} in game {}",
+            achievement.clerk_user_id, achievement.achievement_id, game_id
+        );
+    }
+    Ok(awarded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn commander_wrath_follows_the_games_lethal_threshold() {
+        // 21 damage is lethal under a threshold of 10 but not one of 30
+        for (commander_lethal, earned) in [(10, true), (30, false)] {
+            let pool = test_support::pool().await;
+            let game = test_support::create_game(&pool, "host").await;
+            sqlx::query("UPDATE games SET commander_lethal = ? WHERE id = ?")
+                .bind(commander_lethal)
+                .bind(game.id.to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+            let host = database::get_player_by_user(&pool, game.id, "host")
+                .await
+                .unwrap();
+            let guest =
+                database::join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+                    test_support::audit("user_1", "join")
+                })
+                .await
+                .unwrap();
+            database::update_commander_damage(
+                &pool,
+                game.id,
+                host.id,
+                guest.id,
+                1,
+                21,
+                None,
+                "host",
+                test_support::audit("host", "commander_damage"),
+            )
+            .await
+            .unwrap();
+
+            let awarded = award_for_game(&pool, game.id).await.unwrap();
+            let wrath = awarded.iter().any(|a| {
+                a.achievement_id == "lethal_commander_damage" && a.clerk_user_id == "host"
+            });
+            assert_eq!(wrath, earned, "commander lethal {commander_lethal}");
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use axum::{
+    Json,
     extract::FromRequestParts,
-    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION, request::Parts},
     response::{IntoResponse, Response},
-    Json,
 };
 use serde_json::json;
 
@@ -53,6 +53,7 @@ where
         let user = clerk::validate_and_get_user(token)
             .await
             .map_err(|e| AuthError(e.to_string()))?;
+        tracing::Span::current().record("user_id", user.id.as_str());
 
         Ok(AuthenticatedUser {
             clerk_user_id: user.id.clone(),
@@ -61,20 +62,50 @@ where
     }
 }
 
-/// Optional authenticated user - doesn't fail if no token is present
+/// Header carrying the admin API key
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+/// Audit log actor for actions taken with the admin API key; never a Clerk user id
+pub const ADMIN_ACTOR: &str = "admin";
+
+/// Caller presented the admin API key configured in `ADMIN_API_KEY`.
+/// Admin endpoints are disabled entirely when the variable is unset.
 #[derive(Debug, Clone)]
-pub struct OptionalAuthenticatedUser(pub Option<AuthenticatedUser>);
+pub struct AdminKey;
 
-impl<S> FromRequestParts<S> for OptionalAuthenticatedUser
+impl<S> FromRequestParts<S> for AdminKey
 where
     S: Send + Sync,
 {
-    type Rejection = std::convert::Infallible;
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        check_admin_key(&parts.headers)?;
+        Ok(AdminKey)
+    }
+}
+
+/// Check the admin key header, for endpoints that only relax limits for admins
+/// rather than requiring the key
+pub fn check_admin_key(headers: &HeaderMap) -> Result<(), AuthError> {
+    let expected = std::env::var("ADMIN_API_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| AuthError("Admin API is not enabled".to_string()))?;
+
+    let provided = headers
+        .get(ADMIN_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AuthError("Missing admin key".to_string()))?;
 
-    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        match AuthenticatedUser::from_request_parts(parts, state).await {
-            Ok(user) => Ok(OptionalAuthenticatedUser(Some(user))),
-            Err(_) => Ok(OptionalAuthenticatedUser(None)),
-        }
+    if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        return Err(AuthError("Invalid admin key".to_string()));
     }
+
+    Ok(())
+}
+
+/// Compare secrets without leaking how many leading bytes matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
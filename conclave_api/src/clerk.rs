@@ -1,11 +1,12 @@
 use crate::errors::{ApiError, Result};
-use jsonwebtoken::{DecodingKey, Validation, decode, decode_header};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
 use once_cell::sync::OnceCell;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
 /// Clerk user information extracted from JWT or fetched from API
@@ -20,6 +21,17 @@ pub struct ClerkUser {
 }
 
 impl ClerkUser {
+    /// A user known only by ID, for when Clerk has nothing better
+    pub fn unknown(user_id: &str) -> Self {
+        Self {
+            id: user_id.to_string(),
+            username: None,
+            first_name: None,
+            last_name: None,
+            image_url: None,
+        }
+    }
+
     /// Get the display name for this user
     pub fn display_name(&self) -> String {
         // Try full name first
@@ -47,20 +59,28 @@ impl ClerkUser {
     }
 }
 
+/// A user fetched from the Clerk API, with when it was fetched
+#[derive(Debug, Clone)]
+struct CachedUser {
+    user: ClerkUser,
+    fetched_at: Instant,
+}
+
+/// Cached users older than this are refreshed in the background, unless
+/// overridden by `CLERK_USER_TTL_SECS`
+const DEFAULT_USER_TTL_SECS: u64 = 300;
+
+/// Seconds of clock skew tolerated on `exp` and `nbf`, unless overridden by
+/// `CLERK_LEEWAY_SECS`
+const DEFAULT_LEEWAY_SECS: u64 = 60;
+
 /// JWT Claims from Clerk tokens (includes custom, snake_case user fields)
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ClerkClaims {
-    /// Subject - the Clerk user ID
+    /// Subject - the Clerk user ID. Expiry and issuer are checked by the
+    /// decoder's `Validation`, not read from here.
     pub sub: String,
-    /// Expiration time
-    pub exp: usize,
-    /// Issued at
-    pub iat: usize,
-    /// Issuer
-    pub iss: Option<String>,
-    /// Authorized party (the frontend app)
-    pub azp: Option<String>,
     // Custom user fields included in the token to avoid extra API calls
     pub id: Option<String>,
     pub username: Option<String>,
@@ -80,22 +100,57 @@ struct JwksResponse {
 #[derive(Debug, Deserialize)]
 struct JwkKey {
     kid: String,
-    kty: String,
     n: String,
     e: String,
-    #[serde(rename = "use")]
-    key_use: Option<String>,
+}
+
+/// A Clerk instance whose tokens are accepted
+#[derive(Debug, Clone)]
+struct JwksIssuer {
+    jwks_url: String,
+    /// Expected `iss` claim for tokens signed with this key set
+    issuer: String,
+}
+
+impl JwksIssuer {
+    /// Clerk serves its key set at `<issuer>/.well-known/jwks.json`. Any
+    /// other URL is refused, since the issuer can't be told from it and a
+    /// guess would reject every token.
+    fn from_jwks_url(jwks_url: &str) -> Result<Self> {
+        let issuer = jwks_url
+            .strip_suffix("/.well-known/jwks.json")
+            .ok_or_else(|| {
+                ApiError::Internal(anyhow::anyhow!(
+                    "Invalid CLERK_JWKS_URL '{jwks_url}': expected <issuer>/.well-known/jwks.json"
+                ))
+            })?
+            .trim_end_matches('/')
+            .to_string();
+        Ok(Self {
+            jwks_url: jwks_url.to_string(),
+            issuer,
+        })
+    }
 }
 
 /// Clerk client for JWT validation and user info fetching
 pub struct ClerkClient {
     http_client: Client,
     secret_key: Option<String>,
-    jwks_url: Option<String>,
-    /// Cache of JWKS keys by kid
-    jwks_cache: Arc<RwLock<HashMap<String, DecodingKey>>>,
+    /// Accepted issuers, from the comma-separated `CLERK_JWKS_URL`
+    jwks_issuers: Vec<JwksIssuer>,
+    /// Shared secret for HS256-signed tokens, checked without JWKS
+    hs256_secret: Option<String>,
+    /// Cache of JWKS keys by JWKS URL, then kid
+    jwks_cache: Arc<RwLock<HashMap<String, HashMap<String, DecodingKey>>>>,
     /// Cache of user info by user ID
-    user_cache: Arc<RwLock<HashMap<String, ClerkUser>>>,
+    user_cache: Arc<RwLock<HashMap<String, CachedUser>>>,
+    /// How long a cached user is served before it is refreshed
+    user_ttl: Duration,
+    /// Clock skew tolerated when checking token expiry
+    leeway_secs: u64,
+    /// Users with a background refresh in flight, so each is fetched once
+    refreshing_users: Arc<Mutex<HashSet<String>>>,
 }
 
 // Global Clerk client instance
@@ -104,14 +159,47 @@ static CLERK_CLIENT: OnceCell<ClerkClient> = OnceCell::new();
 impl ClerkClient {
     /// Initialize the global Clerk client
     pub fn init() -> Result<()> {
-        let secret_key = std::env::var("CLERK_SECRET_KEY").ok();
-        let jwks_url = std::env::var("CLERK_JWKS_URL").ok();
-
-        match (secret_key.as_ref(), jwks_url.as_ref()) {
-            (Some(_), Some(_)) => {
+        let secret_key = std::env::var("CLERK_SECRET_KEY")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let jwks_issuers: Vec<JwksIssuer> = std::env::var("CLERK_JWKS_URL")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(JwksIssuer::from_jwks_url)
+            .collect::<Result<_>>()?;
+        let hs256_secret = std::env::var("CLERK_JWT_HS256_SECRET")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let user_ttl = Duration::from_secs(
+            std::env::var("CLERK_USER_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_USER_TTL_SECS),
+        );
+        let leeway_secs = std::env::var("CLERK_LEEWAY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_LEEWAY_SECS);
+
+        match (secret_key.is_some(), !jwks_issuers.is_empty()) {
+            (true, true) => {
                 // Strict mode enabled
+                info!(
+                    "Accepting tokens from {} Clerk issuer(s): {}",
+                    jwks_issuers.len(),
+                    jwks_issuers
+                        .iter()
+                        .map(|i| i.issuer.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            (false, false) if hs256_secret.is_some() => {
+                info!("CLERK_JWT_HS256_SECRET set - only HS256 tokens will be accepted");
             }
-            (None, None) => {
+            (false, false) => {
                 // Dev mode - skip signature validation
                 warn!(
                     "CLERK_SECRET_KEY and CLERK_JWKS_URL not set - dev mode: JWT signature validation disabled"
@@ -124,13 +212,13 @@ impl ClerkClient {
             }
         }
 
-        let client = ClerkClient {
-            http_client: Client::new(),
+        let client = ClerkClient::new(
             secret_key,
-            jwks_url,
-            jwks_cache: Arc::new(RwLock::new(HashMap::new())),
-            user_cache: Arc::new(RwLock::new(HashMap::new())),
-        };
+            jwks_issuers,
+            hs256_secret,
+            user_ttl,
+            leeway_secs,
+        );
 
         CLERK_CLIENT
             .set(client)
@@ -140,6 +228,41 @@ impl ClerkClient {
         Ok(())
     }
 
+    fn new(
+        secret_key: Option<String>,
+        jwks_issuers: Vec<JwksIssuer>,
+        hs256_secret: Option<String>,
+        user_ttl: Duration,
+        leeway_secs: u64,
+    ) -> Self {
+        ClerkClient {
+            http_client: Client::new(),
+            secret_key,
+            jwks_issuers,
+            hs256_secret,
+            jwks_cache: Arc::new(RwLock::new(HashMap::new())),
+            user_cache: Arc::new(RwLock::new(HashMap::new())),
+            user_ttl,
+            leeway_secs,
+            refreshing_users: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Install a development-mode client (nothing configured) as the global
+    /// instance, for tests; later calls are no-ops
+    #[cfg(test)]
+    pub fn init_dev_mode() {
+        CLERK_CLIENT.get_or_init(|| {
+            ClerkClient::new(
+                None,
+                Vec::new(),
+                None,
+                Duration::from_secs(DEFAULT_USER_TTL_SECS),
+                DEFAULT_LEEWAY_SECS,
+            )
+        });
+    }
+
     /// Get the global Clerk client instance
     pub fn get() -> Result<&'static ClerkClient> {
         CLERK_CLIENT
@@ -149,8 +272,25 @@ impl ClerkClient {
 
     /// Validate a JWT token and extract claims
     pub async fn validate_token(&self, token: &str) -> Result<ClerkClaims> {
+        // HS256 tokens are checked against the shared secret when one is configured
+        if let Some(secret) = &self.hs256_secret {
+            let header = decode_header(token).map_err(|e| {
+                error!("Failed to decode token header: {:?}", e);
+                ApiError::Unauthorized("Invalid token header".to_string())
+            })?;
+
+            if header.alg == Algorithm::HS256 {
+                return self.validate_with_secret(token, secret);
+            }
+            if self.jwks_issuers.is_empty() {
+                return Err(ApiError::Unauthorized(
+                    "Unsupported token algorithm".to_string(),
+                ));
+            }
+        }
+
         // Dev mode: neither environment variable set -> skip signature validation
-        if self.secret_key.is_none() && self.jwks_url.is_none() {
+        if self.secret_key.is_none() && self.jwks_issuers.is_empty() {
             let mut validation = Validation::default();
             validation.insecure_disable_signature_validation();
             validation.validate_exp = false;
@@ -168,19 +308,35 @@ impl ClerkClient {
         }
 
         // Strict mode: require both to be set, and validate via JWKS only
-        if !(self.secret_key.is_some() && self.jwks_url.is_some()) {
+        if self.secret_key.is_none() || self.jwks_issuers.is_empty() {
             return Err(ApiError::Internal(anyhow::anyhow!(
                 "Invalid Clerk configuration: both CLERK_SECRET_KEY and CLERK_JWKS_URL must be set together"
             )));
         }
-        let jwks_url = self
-            .jwks_url
-            .as_ref()
-            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("JWKS URL missing")))?;
-        self.validate_with_jwks(token, jwks_url).await
+        self.validate_with_jwks(token).await
     }
 
-    async fn validate_with_jwks(&self, token: &str, jwks_url: &str) -> Result<ClerkClaims> {
+    fn validate_with_secret(&self, token: &str, secret: &str) -> Result<ClerkClaims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = true;
+        validation.leeway = self.leeway_secs;
+
+        let token_data = decode::<ClerkClaims>(
+            token,
+            &DecodingKey::from_secret(secret.as_bytes()),
+            &validation,
+        )
+        .map_err(|e| {
+            error!("JWT validation failed: {:?}", e);
+            ApiError::Unauthorized("Invalid or expired token".to_string())
+        })?;
+
+        Ok(token_data.claims)
+    }
+
+    /// Validate against whichever configured issuer has the token's signing
+    /// key, checking `iss` matches that issuer
+    async fn validate_with_jwks(&self, token: &str) -> Result<ClerkClaims> {
         // Get the key ID from the token header
         let header = decode_header(token).map_err(|e| {
             error!("Failed to decode token header: {:?}", e);
@@ -191,62 +347,13 @@ impl ClerkClient {
             .kid
             .ok_or_else(|| ApiError::Unauthorized("Token missing key ID".to_string()))?;
 
-        // Check cache first
-        let cached_key = {
-            let cache = self.jwks_cache.read().await;
-            cache.get(&kid).cloned()
-        };
-
-        let decoding_key = match cached_key {
-            Some(key) => key,
-            None => {
-                // Fetch JWKS
-                let response: JwksResponse = self
-                    .http_client
-                    .get(jwks_url)
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        error!("Failed to fetch JWKS: {:?}", e);
-                        ApiError::Internal(anyhow::anyhow!("Failed to fetch JWKS"))
-                    })?
-                    .json()
-                    .await
-                    .map_err(|e| {
-                        error!("Failed to parse JWKS response: {:?}", e);
-                        ApiError::Internal(anyhow::anyhow!("Failed to parse JWKS"))
-                    })?;
-
-                // Find the matching key
-                let jwk =
-                    response.keys.iter().find(|k| k.kid == kid).ok_or_else(|| {
-                        ApiError::Unauthorized("Key not found in JWKS".to_string())
-                    })?;
-
-                // Create decoding key from JWK
-                let decoding_key =
-                    DecodingKey::from_rsa_components(&jwk.n, &jwk.e).map_err(|e| {
-                        error!("Failed to create decoding key: {:?}", e);
-                        ApiError::Internal(anyhow::anyhow!("Failed to create decoding key"))
-                    })?;
-
-                // Cache all keys
-                let mut cache = self.jwks_cache.write().await;
-                for key in response.keys {
-                    if let Ok(dk) = DecodingKey::from_rsa_components(&key.n, &key.e) {
-                        cache.insert(key.kid, dk);
-                    }
-                }
-
-                cache.get(&kid).cloned().ok_or_else(|| {
-                    ApiError::Unauthorized("Key not found after caching".to_string())
-                })?
-            }
-        };
+        let (issuer, decoding_key) = self.find_jwks_key(&kid).await?;
 
         // Validate token
-        let mut validation = Validation::new(jsonwebtoken::Algorithm::RS256);
+        let mut validation = Validation::new(Algorithm::RS256);
         validation.validate_exp = true;
+        validation.leeway = self.leeway_secs;
+        validation.set_issuer(&[&issuer.issuer]);
 
         let token_data = decode::<ClerkClaims>(token, &decoding_key, &validation).map_err(|e| {
             error!("JWT validation failed: {:?}", e);
@@ -256,59 +363,130 @@ impl ClerkClient {
         Ok(token_data.claims)
     }
 
-    /// Fetch user info from Clerk API
-    pub async fn get_user(&self, user_id: &str) -> Result<ClerkUser> {
+    /// Find the issuer holding a signing key, from the cache or else by
+    /// fetching each issuer's key set in turn
+    async fn find_jwks_key(&self, kid: &str) -> Result<(&JwksIssuer, DecodingKey)> {
         // Check cache first
         {
-            let cache = self.user_cache.read().await;
-            if let Some(user) = cache.get(user_id) {
-                return Ok(user.clone());
+            let cache = self.jwks_cache.read().await;
+            for issuer in &self.jwks_issuers {
+                if let Some(key) = cache.get(&issuer.jwks_url).and_then(|keys| keys.get(kid)) {
+                    return Ok((issuer, key.clone()));
+                }
             }
         }
 
-        // Fetch from Clerk API if we have a secret key
-        let secret = self.secret_key.as_ref().ok_or_else(|| {
-            // If no secret key, return a minimal user object
-            debug!("No CLERK_SECRET_KEY set, returning minimal user info");
-            ApiError::Internal(anyhow::anyhow!("No Clerk secret key configured"))
-        })?;
+        // A new kid may be a rotated key, so refresh every key set that could hold it
+        let mut fetch_failures = 0;
+        for issuer in &self.jwks_issuers {
+            let keys = match self.fetch_jwks(&issuer.jwks_url).await {
+                Ok(keys) => keys,
+                Err(e) => {
+                    warn!("Skipping JWKS {}: {:?}", issuer.jwks_url, e);
+                    fetch_failures += 1;
+                    continue;
+                }
+            };
+            // Cache all keys
+            let mut cache = self.jwks_cache.write().await;
+            cache.insert(issuer.jwks_url.clone(), keys);
+
+            if let Some(key) = cache.get(&issuer.jwks_url).and_then(|keys| keys.get(kid)) {
+                return Ok((issuer, key.clone()));
+            }
+        }
 
-        let url = format!("https://api.clerk.com/v1/users/{}", user_id);
-        let response = self
+        if fetch_failures == self.jwks_issuers.len() {
+            return Err(ApiError::Internal(anyhow::anyhow!("Failed to fetch JWKS")));
+        }
+        Err(ApiError::Unauthorized("Key not found in JWKS".to_string()))
+    }
+
+    async fn fetch_jwks(&self, jwks_url: &str) -> Result<HashMap<String, DecodingKey>> {
+        let response: JwksResponse = self
             .http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", secret))
+            .get(jwks_url)
             .send()
             .await
             .map_err(|e| {
-                error!("Failed to fetch user from Clerk: {:?}", e);
-                ApiError::Internal(anyhow::anyhow!("Failed to fetch user info"))
+                error!("Failed to fetch JWKS: {:?}", e);
+                ApiError::Internal(anyhow::anyhow!("Failed to fetch JWKS"))
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!("Failed to parse JWKS response: {:?}", e);
+                ApiError::Internal(anyhow::anyhow!("Failed to parse JWKS"))
             })?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            error!("Clerk API error: {} - {}", status, body);
-            // Return a minimal user on error
-            return Ok(ClerkUser {
-                id: user_id.to_string(),
-                username: None,
-                first_name: None,
-                last_name: None,
-                image_url: None,
-            });
+        let mut keys = HashMap::new();
+        for key in response.keys {
+            match DecodingKey::from_rsa_components(&key.n, &key.e) {
+                Ok(dk) => {
+                    keys.insert(key.kid, dk);
+                }
+                Err(e) => error!("Failed to create decoding key {}: {:?}", key.kid, e),
+            }
         }
-        let user: ClerkUser = response.json().await.map_err(|e| {
-            error!("Failed to parse Clerk user response: {:?}", e);
-            ApiError::Internal(anyhow::anyhow!("Failed to parse user info"))
+        Ok(keys)
+    }
+
+    /// Fetch user info from Clerk API. Cached users are returned immediately;
+    /// once older than the TTL they are also refreshed in the background.
+    pub async fn get_user(&self, user_id: &str) -> Result<ClerkUser> {
+        // Check cache first
+        let cached = self.user_cache.read().await.get(user_id).cloned();
+        if let Some(cached) = cached {
+            if cached.fetched_at.elapsed() >= self.user_ttl {
+                self.refresh_user_in_background(user_id).await;
+            }
+            return Ok(cached.user);
+        }
+
+        // Fetch from Clerk API if we have a secret key
+        let secret = self.secret_key.as_ref().ok_or_else(|| {
+            // If no secret key, return a minimal user object
+            debug!("No CLERK_SECRET_KEY set, returning minimal user info");
+            ApiError::Internal(anyhow::anyhow!("No Clerk secret key configured"))
         })?;
 
-        // Cache the user
+        match fetch_user(&self.http_client, secret, user_id).await? {
+            Some(user) => {
+                cache_user(&self.user_cache, user_id, &user).await;
+                Ok(user)
+            }
+            // Return a minimal user on error
+            None => Ok(ClerkUser::unknown(user_id)),
+        }
+    }
+
+    /// Re-fetch a stale cached user without holding up the caller. The stale
+    /// entry keeps being served until a fetch succeeds.
+    async fn refresh_user_in_background(&self, user_id: &str) {
+        let Some(secret) = self.secret_key.clone() else {
+            return;
+        };
+        if !self
+            .refreshing_users
+            .lock()
+            .await
+            .insert(user_id.to_string())
         {
-            let mut cache = self.user_cache.write().await;
-            cache.insert(user_id.to_string(), user.clone());
+            return;
         }
-        Ok(user)
+
+        let http_client = self.http_client.clone();
+        let user_cache = self.user_cache.clone();
+        let refreshing_users = self.refreshing_users.clone();
+        let user_id = user_id.to_string();
+        tokio::spawn(async move {
+            debug!("Refreshing cached Clerk user {}", user_id);
+            match fetch_user(&http_client, &secret, &user_id).await {
+                Ok(Some(user)) => cache_user(&user_cache, &user_id, &user).await,
+                Ok(None) | Err(_) => debug!("Keeping stale Clerk user {}", user_id),
+            }
+            refreshing_users.lock().await.remove(&user_id);
+        });
     }
 
     /// Get user info, returning a default if fetch fails
@@ -317,13 +495,7 @@ impl ClerkClient {
             Ok(user) => user,
             Err(e) => {
                 debug!("Failed to fetch user {}: {:?}", user_id, e);
-                ClerkUser {
-                    id: user_id.to_string(),
-                    username: None,
-                    first_name: None,
-                    last_name: None,
-                    image_url: None,
-                }
+                ClerkUser::unknown(user_id)
             }
         }
     }
@@ -336,18 +508,56 @@ impl ClerkClient {
     }
 }
 
+/// Fetch a user from the Clerk API. `None` means Clerk answered with an error
+/// status, e.g. for an unknown user.
+async fn fetch_user(
+    http_client: &Client,
+    secret: &str,
+    user_id: &str,
+) -> Result<Option<ClerkUser>> {
+    let url = format!("https://api.clerk.com/v1/users/{}", user_id);
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", secret))
+        .send()
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch user from Clerk: {:?}", e);
+            ApiError::Internal(anyhow::anyhow!("Failed to fetch user info"))
+        })?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("Clerk API error: {} - {}", status, body);
+        return Ok(None);
+    }
+    let user: ClerkUser = response.json().await.map_err(|e| {
+        error!("Failed to parse Clerk user response: {:?}", e);
+        ApiError::Internal(anyhow::anyhow!("Failed to parse user info"))
+    })?;
+    Ok(Some(user))
+}
+
+async fn cache_user(
+    user_cache: &RwLock<HashMap<String, CachedUser>>,
+    user_id: &str,
+    user: &ClerkUser,
+) {
+    user_cache.write().await.insert(
+        user_id.to_string(),
+        CachedUser {
+            user: user.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+}
+
 /// Extract JWT token from Authorization header
 pub fn extract_token_from_header(auth_header: &str) -> Option<&str> {
     auth_header.strip_prefix("Bearer ")
 }
 
-/// Validate a token and return the user ID
-pub async fn validate_and_get_user_id(token: &str) -> Result<String> {
-    let client = ClerkClient::get()?;
-    let claims = client.validate_token(token).await?;
-    Ok(claims.sub)
-}
-
 /// Validate a token and return full user info
 pub async fn validate_and_get_user(token: &str) -> Result<ClerkUser> {
     let client = ClerkClient::get()?;
@@ -381,3 +591,59 @@ pub async fn validate_and_get_user(token: &str) -> Result<ClerkUser> {
         image_url: None,
     })
 }
+
+/// Sign a token for `user_id` with an HS256 `secret`, expiring `expires_in`
+/// seconds from now (negative for already expired). Development mode skips
+/// signature checks, so any secret will do there.
+#[cfg(test)]
+pub fn test_token(user_id: &str, secret: &str, expires_in: i64) -> String {
+    let now = chrono::Utc::now().timestamp();
+    let claims = serde_json::json!({
+        "sub": user_id,
+        "iat": now,
+        "exp": now + expires_in,
+        "username": user_id,
+    });
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::new(Algorithm::HS256),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .expect("token encodes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issuer_comes_from_the_well_known_jwks_path() {
+        let issuer =
+            JwksIssuer::from_jwks_url("https://clerk.example.com/.well-known/jwks.json").unwrap();
+        assert_eq!(issuer.issuer, "https://clerk.example.com");
+
+        assert!(JwksIssuer::from_jwks_url("https://clerk.example.com/keys.json").is_err());
+    }
+
+    #[tokio::test]
+    async fn hs256_tokens_get_the_configured_leeway() {
+        let secret = "test-secret";
+        let client = ClerkClient::new(
+            None,
+            Vec::new(),
+            Some(secret.to_string()),
+            Duration::from_secs(DEFAULT_USER_TTL_SECS),
+            60,
+        );
+
+        let just_expired = test_token("user_1", secret, -30);
+        let claims = client.validate_token(&just_expired).await.unwrap();
+        assert_eq!(claims.sub, "user_1");
+
+        let long_expired = test_token("user_1", secret, -120);
+        assert!(matches!(
+            client.validate_token(&long_expired).await,
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
+}
@@ -0,0 +1,140 @@
+use anyhow::{Context, bail};
+
+/// Limits and tunables that deployments can adjust, loaded once at startup
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Smallest starting life a new game may use
+    pub min_starting_life: i32,
+    /// Largest starting life a new game may use
+    pub max_starting_life: i32,
+    /// Largest single life change, in either direction
+    pub max_life_change: i32,
+    /// Largest single commander damage change, in either direction
+    pub max_commander_damage_change: i32,
+    /// Elo K-factor: the most a rating can move against a single opponent
+    pub elo_k_factor: i32,
+    /// Lowest life total stored; unbounded when unset. Elimination is decided
+    /// on the unclamped total, so a floor never keeps a player alive.
+    pub life_floor: Option<i32>,
+    /// Seconds an ended or cancelled game's room stays open so clients
+    /// receive the final message
+    pub room_cleanup_delay_secs: i32,
+    /// Games a user may create per minute; 0 for no limit. Requests with the
+    /// admin key are exempt.
+    pub game_creation_per_minute: i32,
+    /// Lobby or active games a user may be in at once; creating or joining
+    /// another is refused
+    pub max_open_games_per_user: i32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            min_starting_life: 1,
+            max_starting_life: 999,
+            max_life_change: 100,
+            max_commander_damage_change: 50,
+            elo_k_factor: 32,
+            life_floor: None,
+            room_cleanup_delay_secs: 5,
+            game_creation_per_minute: 5,
+            max_open_games_per_user: 10,
+        }
+    }
+}
+
+impl Config {
+    /// Load from `MIN_STARTING_LIFE`, `MAX_STARTING_LIFE`, `MAX_LIFE_CHANGE`,
+    /// `MAX_COMMANDER_DAMAGE_CHANGE`, `ELO_K_FACTOR`, `LIFE_FLOOR`,
+    /// `ROOM_CLEANUP_DELAY_SECS`, `GAME_CREATION_PER_MINUTE` and
+    /// `MAX_OPEN_GAMES_PER_USER`, falling back to the defaults for unset (or,
+    /// for `LIFE_FLOOR`, empty) vars.
+    /// Fails on unparseable or inconsistent values rather than guessing.
+    pub fn from_env() -> anyhow::Result<Self> {
+        fn env_or(name: &str, default: i32) -> anyhow::Result<i32> {
+            match std::env::var(name) {
+                Ok(value) => value
+                    .parse()
+                    .with_context(|| format!("{name} must be an integer, got '{value}'")),
+                Err(_) => Ok(default),
+            }
+        }
+
+        fn env_opt(name: &str) -> anyhow::Result<Option<i32>> {
+            match std::env::var(name) {
+                Ok(value) if !value.trim().is_empty() => value
+                    .trim()
+                    .parse()
+                    .map(Some)
+                    .with_context(|| format!("{name} must be an integer, got '{value}'")),
+                _ => Ok(None),
+            }
+        }
+
+        let defaults = Self::default();
+        let config = Self {
+            min_starting_life: env_or("MIN_STARTING_LIFE", defaults.min_starting_life)?,
+            max_starting_life: env_or("MAX_STARTING_LIFE", defaults.max_starting_life)?,
+            max_life_change: env_or("MAX_LIFE_CHANGE", defaults.max_life_change)?,
+            max_commander_damage_change: env_or(
+                "MAX_COMMANDER_DAMAGE_CHANGE",
+                defaults.max_commander_damage_change,
+            )?,
+            elo_k_factor: env_or("ELO_K_FACTOR", defaults.elo_k_factor)?,
+            life_floor: env_opt("LIFE_FLOOR")?,
+            room_cleanup_delay_secs: env_or(
+                "ROOM_CLEANUP_DELAY_SECS",
+                defaults.room_cleanup_delay_secs,
+            )?,
+            game_creation_per_minute: env_or(
+                "GAME_CREATION_PER_MINUTE",
+                defaults.game_creation_per_minute,
+            )?,
+            max_open_games_per_user: env_or(
+                "MAX_OPEN_GAMES_PER_USER",
+                defaults.max_open_games_per_user,
+            )?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.min_starting_life < 1 {
+            bail!("MIN_STARTING_LIFE must be at least 1");
+        }
+        if self.max_starting_life < self.min_starting_life {
+            bail!("MAX_STARTING_LIFE must not be less than MIN_STARTING_LIFE");
+        }
+        if self.max_life_change < 1 {
+            bail!("MAX_LIFE_CHANGE must be at least 1");
+        }
+        if self.max_commander_damage_change < 1 {
+            bail!("MAX_COMMANDER_DAMAGE_CHANGE must be at least 1");
+        }
+        if self.elo_k_factor < 1 {
+            bail!("ELO_K_FACTOR must be at least 1");
+        }
+        if self.room_cleanup_delay_secs < 0 {
+            bail!("ROOM_CLEANUP_DELAY_SECS must not be negative");
+        }
+        if self.game_creation_per_minute < 0 {
+            bail!("GAME_CREATION_PER_MINUTE must not be negative");
+        }
+        if self.max_open_games_per_user < 1 {
+            bail!("MAX_OPEN_GAMES_PER_USER must be at least 1");
+        }
+        if let Some(floor) = self.life_floor {
+            if floor < -crate::database::MAX_TRACKED_VALUE {
+                bail!(
+                    "LIFE_FLOOR must be at least -{}",
+                    crate::database::MAX_TRACKED_VALUE
+                );
+            }
+            if floor > self.min_starting_life {
+                bail!("LIFE_FLOOR must not exceed MIN_STARTING_LIFE");
+            }
+        }
+        Ok(())
+    }
+}
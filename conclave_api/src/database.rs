@@ -1,18 +1,87 @@
-use crate::clerk::ClerkClient;
+use crate::achievements;
+use crate::clerk::{ClerkClient, ClerkUser};
 use crate::errors::{ApiError, Result};
 use crate::models::*;
-use chrono::Utc;
-use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow, SqliteSynchronous,
+};
+use sqlx::{FromRow, Row, Sqlite, SqlitePool, Transaction};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
+use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
 
+/// Open the database named by `DATABASE_URL` (default
+/// `sqlite:conclave.db?mode=rwc`) and run migrations
 pub async fn create_pool() -> Result<SqlitePool> {
     let database_url =
         std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:conclave.db?mode=rwc".to_string());
-    let pool = SqlitePool::connect(&database_url).await?;
+    connect(&database_url).await
+}
+
+/// Open a pool on `database_url` and run migrations. Each connection is set up
+/// from `SQLITE_JOURNAL_MODE` (default WAL), `SQLITE_BUSY_TIMEOUT_MS` (5000),
+/// `SQLITE_SYNCHRONOUS` (NORMAL); the pool holds up to
+/// `DATABASE_MAX_CONNECTIONS` (10) connections. Foreign keys are always on:
+/// deleting a game or player relies on `ON DELETE CASCADE` to remove its rows.
+///
+/// An in-memory database (`sqlite::memory:`) exists only as long as its
+/// connection, so it gets a single connection that is never recycled. Every
+/// such pool is a fresh, isolated database, e.g. one per test.
+pub async fn connect(database_url: &str) -> Result<SqlitePool> {
+    fn env_or<T: FromStr>(name: &str, default: T) -> Result<T> {
+        match std::env::var(name) {
+            Ok(value) => value
+                .trim()
+                .parse()
+                .map_err(|_| ApiError::Internal(anyhow::anyhow!("Invalid {name}: '{value}'"))),
+            Err(_) => Ok(default),
+        }
+    }
+
+    let in_memory = database_url.contains(":memory:") || database_url.contains("mode=memory");
+    let options = SqliteConnectOptions::from_str(database_url)?
+        .journal_mode(env_or("SQLITE_JOURNAL_MODE", SqliteJournalMode::Wal)?)
+        .busy_timeout(Duration::from_millis(env_or(
+            "SQLITE_BUSY_TIMEOUT_MS",
+            5000,
+        )?))
+        .synchronous(env_or("SQLITE_SYNCHRONOUS", SqliteSynchronous::Normal)?)
+        .foreign_keys(true);
+
+    let pool_options = if in_memory {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        SqlitePoolOptions::new().max_connections(env_or("DATABASE_MAX_CONNECTIONS", 10)?)
+    };
+    let pool = pool_options.connect_with(options).await?;
+    // A SQLite built without foreign key support ignores the pragma silently
+    let enforced: bool = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(&pool)
+        .await?;
+    if !enforced {
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "SQLite foreign keys could not be enabled"
+        )));
+    }
     run_migrations(&pool).await?;
     Ok(pool)
 }
 
+/// Start a transaction that takes the write lock up front. A deferred
+/// transaction that reads before writing fails at once with "database is
+/// locked" when another writer got in first; this one waits out the busy timeout.
+async fn begin_write(pool: &SqlitePool) -> Result<Transaction<'static, Sqlite>> {
+    Ok(pool.begin_with("BEGIN IMMEDIATE").await?)
+}
+
 async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     sqlx::migrate!("./migrations")
         .run(pool)
@@ -24,55 +93,149 @@ async fn run_migrations(pool: &SqlitePool) -> Result<()> {
 // User operations are handled by Clerk, so no local user functions needed
 
 // Game operations
+/// How long a game creation idempotency key is remembered
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// Create a game with its creator seated. With an idempotency key the creator
+/// has used in the last 24 hours, the game created then is returned instead;
+/// the flag says whether a new game was created.
 pub async fn create_game(
     pool: &SqlitePool,
-    starting_life: i32,
+    settings: NewGame,
     creator_clerk_user_id: &str,
-) -> Result<Game> {
-    let mut tx = pool.begin().await?;
+    idempotency_key: Option<&str>,
+    max_open_games: i32,
+    audit: impl FnOnce(&Game) -> NewAuditEntry,
+) -> Result<(Game, bool)> {
+    let mut tx = begin_write(pool).await?;
+
+    if let Some(key) = idempotency_key {
+        // Writing first takes the database write lock, so a concurrent retry
+        // waits for this transaction and then sees its key
+        let cutoff = Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+        sqlx::query(
+            "DELETE FROM idempotency_keys WHERE clerk_user_id = ? AND idempotency_key = ? AND created_at < ?",
+        )
+        .bind(creator_clerk_user_id)
+        .bind(key)
+        .bind(cutoff.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+
+        let existing = sqlx::query(
+            "SELECT game_id FROM idempotency_keys WHERE clerk_user_id = ? AND idempotency_key = ?",
+        )
+        .bind(creator_clerk_user_id)
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(row) = existing {
+            let game_id = uuid_column(&row, "game_id")?;
+            let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+            tx.commit().await?;
+            return Ok((game, false));
+        }
+    }
 
+    let format = settings.format;
     let game = Game {
         id: Uuid::new_v4(),
-        status: "active".to_string(),
-        starting_life,
+        status: if settings.lobby { "lobby" } else { "active" }.to_string(),
+        starting_life: settings.starting_life,
         winner_player_id: None,
         created_at: Utc::now(),
         finished_at: None,
+        strict_control: settings.strict_control,
+        format: format.map(|f| f.as_str().to_string()),
+        max_players: format.map_or(MAX_PLAYERS_PER_GAME, GameFormat::max_players) as i32,
+        deleted_at: None,
+        outcome: None,
+        commander_lethal: settings.commander_lethal,
+        visibility: settings.visibility.as_str().to_string(),
+        join_code: (settings.visibility == GameVisibility::Private).then(generate_join_code),
+        started_at: None,
+        auto_eliminate: settings.auto_eliminate,
     };
 
     sqlx::query(
-        "INSERT INTO games (id, status, starting_life, winner_player_id, created_at) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO games (id, status, starting_life, winner_player_id, created_at, strict_control, format, max_players, commander_lethal, visibility, join_code, auto_eliminate) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(game.id.to_string())
     .bind(&game.status)
     .bind(game.starting_life)
     .bind(game.winner_player_id.map(|id| id.to_string()))
     .bind(game.created_at.to_rfc3339())
+    .bind(game.strict_control)
+    .bind(&game.format)
+    .bind(game.max_players)
+    .bind(game.commander_lethal)
+    .bind(&game.visibility)
+    .bind(&game.join_code)
+    .bind(game.auto_eliminate)
     .execute(&mut *tx)
     .await?;
 
     // Add the creator as the first player atomically
-    join_game_in_tx(&mut tx, game.id, creator_clerk_user_id).await?;
+    join_game_in_tx(
+        &mut tx,
+        game.id,
+        creator_clerk_user_id,
+        JoinGameRequest {
+            join_code: game.join_code.clone(),
+            ..Default::default()
+        },
+        max_open_games,
+    )
+    .await?;
+
+    if let Some(key) = idempotency_key {
+        sqlx::query(
+            "INSERT INTO idempotency_keys (clerk_user_id, idempotency_key, game_id, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(creator_clerk_user_id)
+        .bind(key)
+        .bind(game.id.to_string())
+        .bind(game.created_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    record_audit_entry_in_tx(&mut tx, game.id, audit(&game)).await?;
     tx.commit().await?;
-    Ok(game)
+    Ok((game, true))
+}
+
+/// Short code to share for joining a private game
+fn generate_join_code() -> String {
+    Uuid::new_v4().simple().to_string()[..JOIN_CODE_LENGTH].to_uppercase()
+}
+
+/// Forget idempotency keys older than the TTL; returns how many were removed
+pub async fn purge_expired_idempotency_keys(pool: &SqlitePool) -> Result<u64> {
+    let cutoff = Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+    let result = sqlx::query("DELETE FROM idempotency_keys WHERE created_at < ?")
+        .bind(cutoff.to_rfc3339())
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
 }
 
-// Check if user is already in any active game
-async fn check_user_in_active_game_in_tx(
+// Count the active games a user is in, including games still in the lobby
+async fn count_user_open_games_in_tx(
     tx: &mut Transaction<'_, Sqlite>,
     clerk_user_id: &str,
-) -> Result<bool> {
+) -> Result<i64> {
     let result = sqlx::query(
         "SELECT COUNT(*) as count FROM players p 
          INNER JOIN games g ON p.game_id = g.id 
-         WHERE p.clerk_user_id = ? AND g.status = 'active'",
+         WHERE p.clerk_user_id = ? AND g.status IN ('lobby', 'active')",
     )
     .bind(clerk_user_id)
     .fetch_one(&mut **tx)
     .await?;
 
-    let count: i64 = result.get("count");
-    Ok(count > 0)
+    Ok(result.get("count"))
 }
 
 // Transaction-safe version of join_game
@@ -80,18 +243,21 @@ async fn join_game_in_tx(
     tx: &mut Transaction<'_, Sqlite>,
     game_id: Uuid,
     clerk_user_id: &str,
+    options: JoinGameRequest,
+    max_open_games: i32,
 ) -> Result<Player> {
-    // Verify game exists and is active
+    let seat_color = normalize_seat_color(options.seat_color)?;
+
+    // Verify game exists and is in the lobby or active
     let game = get_game_by_id_in_tx(tx, game_id).await?;
-    if game.status == "finished" {
-        return Err(ApiError::BadRequest(
-            "Cannot join finished game".to_string(),
-        ));
+    if !game.is_open() {
+        return Err(ApiError::GameFinished(game.status));
     }
 
-    // Check if user is already in any active game
-    if check_user_in_active_game_in_tx(tx, clerk_user_id).await? {
-        return Err(ApiError::UserInActiveGame);
+    if game.join_code.is_some() && !game.join_code_matches(options.join_code.as_deref()) {
+        return Err(ApiError::Forbidden(
+            "This game requires a valid join code".to_string(),
+        ));
     }
 
     // Check if user is already in game
@@ -105,45 +271,68 @@ async fn join_game_in_tx(
 
     let count: i64 = existing.get("count");
     if count > 0 {
-        return Err(ApiError::BadRequest("User already in game".to_string()));
+        return Err(ApiError::Conflict("User already in game".to_string()));
     }
 
-    // Get current player count atomically within transaction
-    let player_count_result =
-        sqlx::query("SELECT COUNT(*) as count FROM players WHERE game_id = ?")
-            .bind(game_id.to_string())
-            .fetch_one(&mut **tx)
-            .await?;
+    // Check the user has room for another game. Seated users were turned
+    // away above, so they get a conflict rather than the cap.
+    if count_user_open_games_in_tx(tx, clerk_user_id).await? >= i64::from(max_open_games) {
+        return Err(ApiError::Conflict("too many active games".to_string()));
+    }
 
-    let player_count: i64 = player_count_result.get("count");
-    if player_count >= MAX_PLAYERS_PER_GAME as i64 {
-        return Err(ApiError::BadRequest(format!(
-            "Game is full (max {MAX_PLAYERS_PER_GAME} players)"
-        )));
+    // Get current player count and highest seat atomically within transaction
+    let seats = sqlx::query(
+        "SELECT COUNT(*) as count, COALESCE(MAX(position), 0) as max_position FROM players WHERE game_id = ?",
+    )
+    .bind(game_id.to_string())
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let player_count: i64 = seats.get("count");
+    if player_count >= game.max_players as i64 {
+        return Err(ApiError::GameFull(game.max_players));
     }
 
-    // Use atomic position assignment
-    let position = (player_count + 1) as i32;
+    // Seat after the highest position so gaps left by departed players can't collide
+    let position = seats.get::<i32, _>("max_position") + 1;
+
+    if let Some(ref color) = seat_color {
+        ensure_seat_color_free_in_tx(tx, game_id, color).await?;
+    }
 
+    let starting_life = options.starting_life_override.unwrap_or(game.starting_life);
     let player = Player {
         id: Uuid::new_v4(),
         game_id,
         clerk_user_id: clerk_user_id.to_string(),
-        current_life: game.starting_life,
+        current_life: starting_life,
         position,
+        commander_name: None,
+        partner_name: None,
+        version: 0,
+        commander_casts: 0,
+        elimination_cause: None,
+        mulligans: 0,
+        hand_size: DEFAULT_HAND_SIZE,
+        starting_life,
+        seat_color,
+        has_partner: false,
     };
 
-    // Database constraint will prevent duplicate positions
+    // The unique indexes catch a concurrent join that slipped past the checks above
     sqlx::query(
-        "INSERT INTO players (id, game_id, clerk_user_id, current_life, position) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO players (id, game_id, clerk_user_id, current_life, position, starting_life, seat_color) VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(player.id.to_string())
     .bind(player.game_id.to_string())
     .bind(&player.clerk_user_id)
     .bind(player.current_life)
     .bind(player.position)
+    .bind(player.starting_life)
+    .bind(&player.seat_color)
     .execute(&mut **tx)
-    .await?;
+    .await
+    .map_err(|e| join_conflict(e, player.seat_color.as_deref()))?;
 
     // Initialize commander damage entries for this player
     initialize_commander_damage_for_player_in_tx(tx, game_id, player.id).await?;
@@ -151,47 +340,79 @@ async fn join_game_in_tx(
     Ok(player)
 }
 
-pub async fn join_game(pool: &SqlitePool, game_id: Uuid, clerk_user_id: &str) -> Result<Player> {
-    let mut tx = pool.begin().await?;
-    let player = join_game_in_tx(&mut tx, game_id, clerk_user_id).await?;
+/// Tell apart the unique indexes a player insert can trip. SQLite names the
+/// indexed columns rather than the index in its message.
+fn join_conflict(e: sqlx::Error, seat_color: Option<&str>) -> ApiError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            let message = db.message();
+            if message.contains("players.clerk_user_id") {
+                ApiError::Conflict("User already in game".to_string())
+            } else if message.contains("players.seat_color") {
+                seat_color_taken(seat_color.unwrap_or_default())
+            } else if message.contains("players.position") {
+                ApiError::Conflict("That seat was just taken, try joining again".to_string())
+            } else {
+                sqlx::Error::Database(db).into()
+            }
+        }
+        other => other.into(),
+    }
+}
+
+/// Seat a user in a game, with the starting life override and seat color in
+/// `options` when given. Refused if the user is already in `max_open_games`
+/// lobby or active games.
+pub async fn join_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    clerk_user_id: &str,
+    options: JoinGameRequest,
+    max_open_games: i32,
+    audit: impl FnOnce(&Player) -> NewAuditEntry,
+) -> Result<Player> {
+    let mut tx = begin_write(pool).await?;
+    let player = join_game_in_tx(&mut tx, game_id, clerk_user_id, options, max_open_games).await?;
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&player)).await?;
     tx.commit().await?;
     Ok(player)
 }
 
-pub async fn leave_game(pool: &SqlitePool, game_id: Uuid, clerk_user_id: &str) -> Result<()> {
-    let mut tx = pool.begin().await?;
+/// Remove the user's player from the game, returning the removed player's id.
+/// Leaving is idempotent: a user who isn't in the game gets `None`, so a
+/// retried leave doesn't fail.
+pub async fn leave_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    clerk_user_id: &str,
+    audit: impl FnOnce(Uuid) -> NewAuditEntry,
+) -> Result<Option<Uuid>> {
+    let mut tx = begin_write(pool).await?;
 
     // Verify game exists
     let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
-    if game.status == "finished" {
-        return Err(ApiError::BadRequest(
-            "Cannot leave finished game".to_string(),
-        ));
+    if !game.is_open() {
+        return Err(ApiError::BadRequest(format!(
+            "Cannot leave {} game",
+            game.status
+        )));
     }
 
-    // Find player and get their ID for commander damage cleanup
-    let player_result =
+    let Some(removed) =
         sqlx::query("SELECT id, position FROM players WHERE game_id = ? AND clerk_user_id = ?")
             .bind(game_id.to_string())
             .bind(clerk_user_id)
             .fetch_optional(&mut *tx)
-            .await?;
-
-    let player_row = player_result.ok_or(ApiError::PlayerNotFound)?;
-    let player_id = Uuid::parse_str(&player_row.get::<String, _>("id")).unwrap();
-    let removed_position: i32 = player_row.get("position");
-
-    // Clean up commander damage entries involving this player
-    sqlx::query(
-        "DELETE FROM commander_damage WHERE game_id = ? AND (from_player_id = ? OR to_player_id = ?)"
-    )
-    .bind(game_id.to_string())
-    .bind(player_id.to_string())
-    .bind(player_id.to_string())
-    .execute(&mut *tx)
-    .await?;
+            .await?
+    else {
+        // Already gone
+        return Ok(None);
+    };
+    let removed_id = uuid_column(&removed, "id")?;
+    let removed_position: i32 = removed.get("position");
 
-    // Remove the player
+    // Remove the player; their life changes, commander damage and deck go
+    // with them through ON DELETE CASCADE
     sqlx::query("DELETE FROM players WHERE game_id = ? AND clerk_user_id = ?")
         .bind(game_id.to_string())
         .bind(clerk_user_id)
@@ -199,7 +420,9 @@ pub async fn leave_game(pool: &SqlitePool, game_id: Uuid, clerk_user_id: &str) -
         .await?;
 
     // Shift positions down for players that were after the removed player
-    sqlx::query("UPDATE players SET position = position - 1 WHERE game_id = ? AND position > ?")
+    sqlx::query(
+        "UPDATE players SET position = position - 1, version = version + 1 WHERE game_id = ? AND position > ?",
+    )
         .bind(game_id.to_string())
         .bind(removed_position)
         .execute(&mut *tx)
@@ -207,96 +430,73 @@ pub async fn leave_game(pool: &SqlitePool, game_id: Uuid, clerk_user_id: &str) -
 
     // No automatic game ending - games only end via explicit EndGame request
 
+    record_audit_entry_in_tx(&mut tx, game_id, audit(removed_id)).await?;
     tx.commit().await?;
-    Ok(())
+    Ok(Some(removed_id))
 }
 
 async fn get_game_by_id_in_tx(tx: &mut Transaction<'_, Sqlite>, game_id: Uuid) -> Result<Game> {
-    let row = sqlx::query("SELECT * FROM games WHERE id = ?")
+    sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
         .bind(game_id.to_string())
         .fetch_optional(&mut **tx)
-        .await?;
-
-    match row {
-        Some(row) => Ok(Game {
-            id: Uuid::parse_str(&row.get::<String, _>("id")).unwrap(),
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        }),
-        None => Err(ApiError::GameNotFound),
-    }
+        .await?
+        .ok_or(ApiError::GameNotFound)
 }
 
 pub async fn get_game_by_id(pool: &SqlitePool, game_id: Uuid) -> Result<Game> {
-    let row = sqlx::query("SELECT * FROM games WHERE id = ?")
+    sqlx::query_as::<_, Game>("SELECT * FROM games WHERE id = ?")
         .bind(game_id.to_string())
         .fetch_optional(pool)
-        .await?;
-
-    match row {
-        Some(row) => Ok(Game {
-            id: Uuid::parse_str(&row.get::<String, _>("id")).unwrap(),
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        }),
-        None => Err(ApiError::GameNotFound),
-    }
+        .await?
+        .ok_or(ApiError::GameNotFound)
 }
 
+#[allow(dead_code)]
 pub async fn get_game_state(pool: &SqlitePool, game_id: Uuid) -> Result<GameState> {
     // Use enriched game state with user info (calls get_game_state_with_users internally)
     get_game_state_with_users(pool, game_id).await
 }
 
 pub async fn get_players_in_game(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<Player>> {
-    let rows = sqlx::query("SELECT * FROM players WHERE game_id = ? ORDER BY position")
-        .bind(game_id.to_string())
-        .fetch_all(pool)
-        .await?;
-
-    let players = rows
-        .into_iter()
-        .map(|row| Player {
-            id: Uuid::parse_str(&row.get::<String, _>("id")).unwrap(),
-            game_id: Uuid::parse_str(&row.get::<String, _>("game_id")).unwrap(),
-            clerk_user_id: row.get("clerk_user_id"),
-            current_life: row.get("current_life"),
-            position: row.get("position"),
-        })
-        .collect();
+    let players =
+        sqlx::query_as::<_, Player>("SELECT * FROM players WHERE game_id = ? ORDER BY position")
+            .bind(game_id.to_string())
+            .fetch_all(pool)
+            .await?;
 
     Ok(players)
 }
 
+pub async fn get_player(pool: &SqlitePool, game_id: Uuid, player_id: Uuid) -> Result<Player> {
+    sqlx::query_as::<_, Player>("SELECT * FROM players WHERE id = ? AND game_id = ?")
+        .bind(player_id.to_string())
+        .bind(game_id.to_string())
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::PlayerNotFound)
+}
+
+/// The player row a user holds in a game
+pub async fn get_player_by_user(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    clerk_user_id: &str,
+) -> Result<Player> {
+    sqlx::query_as::<_, Player>("SELECT * FROM players WHERE game_id = ? AND clerk_user_id = ?")
+        .bind(game_id.to_string())
+        .bind(clerk_user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::PlayerNotFound)
+}
+
 pub async fn get_user_games(pool: &SqlitePool, clerk_user_id: &str) -> Result<Vec<GameWithUsers>> {
     let rows = sqlx::query(
         r#"
         SELECT DISTINCT g.*
         FROM games g
         INNER JOIN players p ON g.id = p.game_id
-        WHERE p.clerk_user_id = ? AND g.status != 'finished'
+        WHERE p.clerk_user_id = ? AND g.status != 'finished' AND g.deleted_at IS NULL
         ORDER BY g.created_at DESC
         "#,
     )
@@ -306,43 +506,12 @@ pub async fn get_user_games(pool: &SqlitePool, clerk_user_id: &str) -> Result<Ve
 
     let mut games = Vec::new();
     for row in rows {
-        let game_id = Uuid::parse_str(&row.get::<String, _>("id")).unwrap();
-        let game = Game {
-            id: game_id,
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        };
-
-        // Get users in this game
-        let player_rows = sqlx::query(
-            "SELECT DISTINCT clerk_user_id FROM players WHERE game_id = ? ORDER BY position",
-        )
-        .bind(game_id.to_string())
-        .fetch_all(pool)
-        .await?;
-
-        let users = player_rows
-            .into_iter()
-            .map(|row| UserInfo {
-                clerk_user_id: row.get("clerk_user_id"),
-            })
-            .collect::<Vec<UserInfo>>();
-
-        games.push(GameWithUsers { game, users });
+        let game = Game::from_row(&row)?;
+        let user_ids = get_game_user_ids(pool, game.id).await?;
+        games.push((game, user_ids));
     }
 
-    Ok(games)
+    Ok(games_with_user_info(games).await)
 }
 
 pub async fn get_all_games(pool: &SqlitePool) -> Result<Vec<GameWithUsers>> {
@@ -351,7 +520,7 @@ pub async fn get_all_games(pool: &SqlitePool) -> Result<Vec<GameWithUsers>> {
         SELECT DISTINCT g.*
         FROM games g
         INNER JOIN players p ON g.id = p.game_id
-        WHERE g.status != 'finished'
+        WHERE g.status != 'finished' AND g.deleted_at IS NULL AND g.visibility = 'public'
         ORDER BY g.created_at DESC
         "#,
     )
@@ -360,100 +529,291 @@ pub async fn get_all_games(pool: &SqlitePool) -> Result<Vec<GameWithUsers>> {
 
     let mut games = Vec::new();
     for row in rows {
-        let game_id = Uuid::parse_str(&row.get::<String, _>("id")).unwrap();
-        let game = Game {
-            id: game_id,
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        };
+        let game = Game::from_row(&row)?;
+        let user_ids = get_game_user_ids(pool, game.id).await?;
+        games.push((game, user_ids));
+    }
 
-        // Get users in this game
-        let player_rows = sqlx::query(
-            "SELECT DISTINCT clerk_user_id FROM players WHERE game_id = ? ORDER BY position",
-        )
-        .bind(game_id.to_string())
-        .fetch_all(pool)
-        .await?;
+    Ok(games_with_user_info(games).await)
+}
 
-        let users = player_rows
-            .into_iter()
-            .map(|row| UserInfo {
-                clerk_user_id: row.get("clerk_user_id"),
-            })
-            .collect::<Vec<UserInfo>>();
+/// Life totals and counter values are kept within ±this, well clear of i32 overflow
+pub const MAX_TRACKED_VALUE: i32 = 100_000;
 
-        games.push(GameWithUsers { game, users });
+/// Validate an absolute life or counter value against `MAX_TRACKED_VALUE`
+fn check_tracked_value(what: &str, value: i32) -> Result<i32> {
+    if value.abs() > MAX_TRACKED_VALUE {
+        return Err(ApiError::BadRequest(format!(
+            "{what} must be between -{MAX_TRACKED_VALUE} and {MAX_TRACKED_VALUE}"
+        )));
     }
+    Ok(value)
+}
+
+/// Apply a delta to a tracked value, rejecting overflow and out-of-range results
+pub fn checked_total(what: &str, current: i32, change: i32) -> Result<i32> {
+    let total = current.checked_add(change).ok_or_else(|| {
+        ApiError::BadRequest(format!(
+            "{what} must be between -{MAX_TRACKED_VALUE} and {MAX_TRACKED_VALUE}"
+        ))
+    })?;
+    check_tracked_value(what, total)
+}
+
+/// A life total change as applied
+pub struct AppliedLifeChange {
+    pub player: Player,
+    /// Records the change actually applied, after the life floor. `None` when
+    /// the total didn't move, in which case nothing was written.
+    pub life_change: Option<LifeChange>,
+    /// The total before applying the life floor was at or below `DEATH_THRESHOLD`
+    pub lethal: bool,
+}
+
+/// Check that the player a life change is attributed to is in the same game
+async fn check_life_change_source(
+    tx: &mut sqlx::SqliteConnection,
+    game_id: Uuid,
+    source_player_id: Option<Uuid>,
+) -> Result<()> {
+    let Some(source_player_id) = source_player_id else {
+        return Ok(());
+    };
+    sqlx::query("SELECT 1 FROM players WHERE id = ? AND game_id = ?")
+        .bind(source_player_id.to_string())
+        .bind(game_id.to_string())
+        .fetch_optional(tx)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Source player is not in this game".to_string()))?;
+    Ok(())
+}
+
+/// Check a life change reason against the known set
+fn parse_life_change_reason(reason: Option<&str>) -> Result<Option<LifeChangeReason>> {
+    reason
+        .map(|name| {
+            LifeChangeReason::parse(name).ok_or_else(|| {
+                let known: Vec<&str> = LifeChangeReason::ALL.iter().map(|r| r.as_str()).collect();
+                ApiError::BadRequest(format!(
+                    "Unknown life change reason '{}' (expected one of: {})",
+                    name,
+                    known.join(", ")
+                ))
+            })
+        })
+        .transpose()
+}
 
-    Ok(games)
+/// Clamp a life total to the configured floor, if any
+fn apply_life_floor(life: i32, life_floor: Option<i32>) -> i32 {
+    life_floor.map_or(life, |floor| life.max(floor))
 }
 
+/// Apply a delta to a player's life
+#[allow(clippy::too_many_arguments)]
 pub async fn update_player_life(
     pool: &SqlitePool,
+    game_id: Uuid,
     player_id: Uuid,
     change_amount: i32,
-) -> Result<(Player, LifeChange)> {
-    let mut tx = pool.begin().await?;
+    source_player_id: Option<Uuid>,
+    reason: Option<&str>,
+    actor_clerk_user_id: &str,
+    life_floor: Option<i32>,
+    audit: impl FnOnce(&Player, &LifeChange) -> NewAuditEntry,
+) -> Result<AppliedLifeChange> {
+    let reason = parse_life_change_reason(reason)?;
+    let mut tx = begin_write(pool).await?;
+    check_life_change_source(&mut tx, game_id, source_player_id).await?;
+
+    // Compute the new total up front so it can be range checked
+    // Scoped to the game so a player id from another game is simply not found
+    let player = sqlx::query_as::<_, Player>("SELECT * FROM players WHERE id = ? AND game_id = ?")
+        .bind(player_id.to_string())
+        .bind(game_id.to_string())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::PlayerNotFound)?;
+    let current_life = player.current_life;
+    let raw_life = checked_total("Life", current_life, change_amount)?;
+    let new_life = apply_life_floor(raw_life, life_floor);
+    let lethal = raw_life <= DEATH_THRESHOLD;
 
-    // Use atomic UPDATE with calculations in SQL
-    let update_result = sqlx::query(
+    // Nothing to write when the floor absorbs the whole change, but the hit
+    // can still be lethal
+    if new_life == current_life {
+        return Ok(AppliedLifeChange {
+            player,
+            life_change: None,
+            lethal,
+        });
+    }
+
+    let updated_player = sqlx::query_as::<_, Player>(
         r#"
-        UPDATE players 
-        SET current_life = current_life + ?
-        WHERE id = ?
+        UPDATE players
+        SET current_life = ?, version = version + 1
+        WHERE id = ? AND game_id = ?
         RETURNING *
         "#,
     )
-    .bind(change_amount)
+    .bind(new_life)
     .bind(player_id.to_string())
+    .bind(game_id.to_string())
     .fetch_optional(&mut *tx)
-    .await?;
-
-    let player_row = update_result.ok_or(ApiError::PlayerNotFound)?;
-
-    let updated_player = Player {
-        id: Uuid::parse_str(&player_row.get::<String, _>("id")).unwrap(),
-        game_id: Uuid::parse_str(&player_row.get::<String, _>("game_id")).unwrap(),
-        clerk_user_id: player_row.get("clerk_user_id"),
-        current_life: player_row.get("current_life"),
-        position: player_row.get("position"),
-    };
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
 
     // Record life change atomically
     let life_change = LifeChange {
         id: Uuid::new_v4(),
         game_id: updated_player.game_id,
         player_id: updated_player.id,
-        change_amount,
+        source_player_id,
+        reason,
+        change_amount: new_life - current_life,
+        new_life_total: updated_player.current_life,
+        created_at: Utc::now(),
+        created_by: Some(actor_clerk_user_id.to_string()),
+    };
+
+    sqlx::query(
+        "INSERT INTO life_changes (id, game_id, player_id, source_player_id, reason, change_amount, new_life_total, created_at, created_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(life_change.id.to_string())
+    .bind(life_change.game_id.to_string())
+    .bind(life_change.player_id.to_string())
+    .bind(life_change.source_player_id.map(|id| id.to_string()))
+    .bind(life_change.reason.map(LifeChangeReason::as_str))
+    .bind(life_change.change_amount)
+    .bind(life_change.new_life_total)
+    .bind(life_change.created_at.to_rfc3339())
+    .bind(&life_change.created_by)
+    .execute(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&updated_player, &life_change)).await?;
+    tx.commit().await?;
+    Ok(AppliedLifeChange {
+        player: updated_player,
+        life_change: Some(life_change),
+        lethal,
+    })
+}
+
+/// Set a player's life to an absolute value. When `expected_version` is given,
+/// the update only applies if the player hasn't changed since that version;
+/// when `expected_current` is given, only if their life is currently that.
+#[allow(clippy::too_many_arguments)]
+pub async fn set_player_life(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    requested_life: i32,
+    expected_version: Option<i64>,
+    expected_current: Option<i32>,
+    source_player_id: Option<Uuid>,
+    reason: Option<&str>,
+    actor_clerk_user_id: &str,
+    life_floor: Option<i32>,
+    audit: impl FnOnce(&Player, &LifeChange) -> NewAuditEntry,
+) -> Result<AppliedLifeChange> {
+    check_tracked_value("Life", requested_life)?;
+    let reason = parse_life_change_reason(reason)?;
+    let life = apply_life_floor(requested_life, life_floor);
+
+    let mut tx = begin_write(pool).await?;
+    check_life_change_source(&mut tx, game_id, source_player_id).await?;
+
+    let player = sqlx::query_as::<_, Player>("SELECT * FROM players WHERE id = ? AND game_id = ?")
+        .bind(player_id.to_string())
+        .bind(game_id.to_string())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::PlayerNotFound)?;
+
+    let current_life = player.current_life;
+    let current_version = player.version;
+    if expected_version.is_some_and(|v| v != current_version) {
+        return Err(ApiError::BadRequest("stale".to_string()));
+    }
+    if let Some(expected) = expected_current
+        && expected != current_life
+    {
+        return Err(ApiError::Conflict(format!(
+            "Life is {current_life}, not {expected}"
+        )));
+    }
+
+    let lethal = requested_life <= DEATH_THRESHOLD;
+    if life == current_life {
+        return Ok(AppliedLifeChange {
+            player,
+            life_change: None,
+            lethal,
+        });
+    }
+
+    // Guard on the version we read so a concurrent writer can't be clobbered
+    let updated_player = sqlx::query_as::<_, Player>(
+        r#"
+        UPDATE players
+        SET current_life = ?, version = version + 1
+        WHERE id = ? AND version = ?
+        RETURNING *
+        "#,
+    )
+    .bind(life)
+    .bind(player_id.to_string())
+    .bind(current_version)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("stale".to_string()))?;
+
+    // Record the equivalent delta so history stays consistent
+    let life_change = LifeChange {
+        id: Uuid::new_v4(),
+        game_id,
+        player_id,
+        source_player_id,
+        reason,
+        change_amount: life - current_life,
         new_life_total: updated_player.current_life,
         created_at: Utc::now(),
+        created_by: Some(actor_clerk_user_id.to_string()),
     };
 
     sqlx::query(
-        "INSERT INTO life_changes (id, game_id, player_id, change_amount, new_life_total, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO life_changes (id, game_id, player_id, source_player_id, reason, change_amount, new_life_total, created_at, created_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(life_change.id.to_string())
     .bind(life_change.game_id.to_string())
     .bind(life_change.player_id.to_string())
+    .bind(life_change.source_player_id.map(|id| id.to_string()))
+    .bind(life_change.reason.map(LifeChangeReason::as_str))
     .bind(life_change.change_amount)
     .bind(life_change.new_life_total)
     .bind(life_change.created_at.to_rfc3339())
+    .bind(&life_change.created_by)
     .execute(&mut *tx)
     .await?;
 
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&updated_player, &life_change)).await?;
     tx.commit().await?;
-    Ok((updated_player, life_change))
+    Ok(AppliedLifeChange {
+        player: updated_player,
+        life_change: Some(life_change),
+        lethal,
+    })
+}
+
+pub async fn count_life_changes(pool: &SqlitePool, game_id: Uuid) -> Result<i64> {
+    let row = sqlx::query("SELECT COUNT(*) as count FROM life_changes WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .fetch_one(pool)
+        .await?;
+
+    Ok(row.get("count"))
 }
 
 pub async fn get_recent_life_changes(
@@ -461,7 +821,7 @@ pub async fn get_recent_life_changes(
     game_id: Uuid,
     limit: i32,
 ) -> Result<Vec<LifeChange>> {
-    let rows = sqlx::query(
+    let changes = sqlx::query_as::<_, LifeChange>(
         "SELECT * FROM life_changes WHERE game_id = ? ORDER BY created_at DESC LIMIT ?",
     )
     .bind(game_id.to_string())
@@ -469,38 +829,98 @@ pub async fn get_recent_life_changes(
     .fetch_all(pool)
     .await?;
 
-    let changes = rows
-        .into_iter()
-        .map(|row| LifeChange {
-            id: Uuid::parse_str(&row.get::<String, _>("id")).unwrap(),
-            game_id: Uuid::parse_str(&row.get::<String, _>("game_id")).unwrap(),
-            player_id: Uuid::parse_str(&row.get::<String, _>("player_id")).unwrap(),
-            change_amount: row.get("change_amount"),
-            new_life_total: row.get("new_life_total"),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-        })
-        .collect();
-
     Ok(changes)
 }
 
-pub async fn end_game(
+/// Every life change in a game, oldest first
+pub async fn get_life_changes_for_game(
     pool: &SqlitePool,
     game_id: Uuid,
-    winner_player_id: Option<Uuid>,
-) -> Result<Game> {
-    // Validate winner is in the game if provided
-    if let Some(winner_id) = winner_player_id {
-        let player_exists =
-            sqlx::query("SELECT COUNT(*) as count FROM players WHERE id = ? AND game_id = ?")
-                .bind(winner_id.to_string())
-                .bind(game_id.to_string())
-                .fetch_one(pool)
-                .await?
-                .get::<i64, _>("count")
-                > 0;
+) -> Result<Vec<LifeChange>> {
+    let changes = sqlx::query_as::<_, LifeChange>(
+        "SELECT * FROM life_changes WHERE game_id = ? ORDER BY created_at ASC",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(changes)
+}
+
+/// Per-player totals for a game, aggregated in SQL rather than from every change
+pub async fn get_game_summary(pool: &SqlitePool, game_id: Uuid) -> Result<GameSummary> {
+    // Also rejects unknown games
+    get_game_by_id(pool, game_id).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT p.id, p.clerk_user_id, p.position, p.current_life,
+               COALESCE(SUM(CASE WHEN lc.change_amount > 0 THEN lc.change_amount END), 0) as life_gained,
+               COALESCE(-SUM(CASE WHEN lc.change_amount < 0 THEN lc.change_amount END), 0) as life_lost,
+               MIN(p.starting_life, COALESCE(MIN(lc.new_life_total), p.starting_life)) as lowest_life,
+               COUNT(lc.id) as life_updates,
+               (SELECT COALESCE(SUM(cd.damage), 0) FROM commander_damage cd
+                WHERE cd.from_player_id = p.id AND cd.active = 1) as commander_damage_dealt,
+               (SELECT COALESCE(SUM(cd.damage), 0) FROM commander_damage cd
+                WHERE cd.to_player_id = p.id AND cd.active = 1) as commander_damage_received
+        FROM players p
+        LEFT JOIN life_changes lc ON lc.player_id = p.id
+        WHERE p.game_id = ?
+        GROUP BY p.id
+        ORDER BY p.position
+        "#,
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let players = rows
+        .iter()
+        .map(|row| {
+            Ok(PlayerGameSummary {
+                player_id: uuid_column(row, "id")?,
+                clerk_user_id: row.try_get("clerk_user_id")?,
+                position: row.try_get("position")?,
+                current_life: row.try_get("current_life")?,
+                life_gained: row.try_get("life_gained")?,
+                life_lost: row.try_get("life_lost")?,
+                lowest_life: row.try_get("lowest_life")?,
+                life_updates: row.try_get("life_updates")?,
+                commander_damage_dealt: row.try_get("commander_damage_dealt")?,
+                commander_damage_received: row.try_get("commander_damage_received")?,
+            })
+        })
+        .collect::<sqlx::Result<_>>()?;
+
+    Ok(GameSummary { game_id, players })
+}
+
+/// Finish an active game, optionally with a winner, and rate it when there is
+/// one. A game that already finished or was cancelled keeps its result.
+pub async fn end_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    winner_player_id: Option<Uuid>,
+    elo_k_factor: f64,
+    audit: NewAuditEntry,
+) -> Result<Game> {
+    let mut tx = begin_write(pool).await?;
+
+    let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    // Validate winner is in the game if provided
+    if let Some(winner_id) = winner_player_id {
+        let player_exists =
+            sqlx::query("SELECT COUNT(*) as count FROM players WHERE id = ? AND game_id = ?")
+                .bind(winner_id.to_string())
+                .bind(game_id.to_string())
+                .fetch_one(&mut *tx)
+                .await?
+                .get::<i64, _>("count")
+                > 0;
 
         if !player_exists {
             return Err(ApiError::BadRequest(
@@ -515,10 +935,358 @@ pub async fn end_game(
     .bind(Utc::now().to_rfc3339())
     .bind(winner_player_id.map(|id| id.to_string()))
     .bind(game_id.to_string())
-    .execute(pool)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(winner_id) = winner_player_id {
+        update_ratings_in_tx(&mut tx, game_id, winner_id, elo_k_factor).await?;
+    }
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+    tx.commit().await?;
+    Ok(game)
+}
+
+/// Chance that a player rated `rating` beats one rated `opponent`
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Rate a finished game as the winner beating each other participant. All
+/// adjustments are computed from the ratings before the game. Games ended by
+/// the sweeper as a no contest never get here, and solo games aren't rated.
+async fn update_ratings_in_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: Uuid,
+    winner_player_id: Uuid,
+    k_factor: f64,
+) -> Result<()> {
+    let winner_user: String = sqlx::query("SELECT clerk_user_id FROM players WHERE id = ?")
+        .bind(winner_player_id.to_string())
+        .fetch_one(&mut **tx)
+        .await?
+        .get("clerk_user_id");
+
+    let participants: Vec<String> =
+        sqlx::query("SELECT DISTINCT clerk_user_id FROM players WHERE game_id = ?")
+            .bind(game_id.to_string())
+            .fetch_all(&mut **tx)
+            .await?
+            .into_iter()
+            .map(|row| row.get("clerk_user_id"))
+            .collect();
+    if participants.len() < 2 {
+        return Ok(());
+    }
+
+    let mut ratings = HashMap::new();
+    for user in &participants {
+        let rating: f64 = sqlx::query("SELECT rating FROM player_ratings WHERE clerk_user_id = ?")
+            .bind(user)
+            .fetch_optional(&mut **tx)
+            .await?
+            .map(|row| row.get("rating"))
+            .unwrap_or(DEFAULT_RATING);
+        ratings.insert(user.clone(), rating);
+    }
+
+    let winner_rating = ratings[&winner_user];
+    let mut deltas: HashMap<&str, f64> = HashMap::new();
+    for loser in participants.iter().filter(|user| **user != winner_user) {
+        let change = k_factor * (1.0 - expected_score(winner_rating, ratings[loser]));
+        *deltas.entry(&winner_user).or_default() += change;
+        *deltas.entry(loser).or_default() -= change;
+    }
+
+    let now = Utc::now().to_rfc3339();
+    for user in &participants {
+        let rating = ratings[user] + deltas.get(user.as_str()).copied().unwrap_or(0.0);
+        sqlx::query(
+            r#"
+            INSERT INTO player_ratings (clerk_user_id, rating, games_played, updated_at)
+            VALUES (?, ?, 1, ?)
+            ON CONFLICT(clerk_user_id)
+            DO UPDATE SET rating = excluded.rating,
+                          games_played = games_played + 1,
+                          updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user)
+        .bind(rating)
+        .bind(&now)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// A game with its players and winner
+pub async fn get_game_with_players(pool: &SqlitePool, game_id: Uuid) -> Result<GameWithPlayers> {
+    let game = get_game_by_id(pool, game_id).await?;
+    let players = get_players_in_game(pool, game_id).await?;
+    let winner = game
+        .winner_player_id
+        .and_then(|id| players.iter().find(|p| p.id == id).cloned());
+
+    Ok(GameWithPlayers {
+        duration_seconds: game.duration_seconds(),
+        game,
+        players,
+        winner,
+    })
+}
+
+/// Store earned badges, ignoring ones the user already has.
+/// Returns only the newly earned badges.
+pub async fn insert_user_achievements(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    earned: &[(String, &str)],
+) -> Result<Vec<UserAchievement>> {
+    let now = Utc::now();
+    let mut awarded = Vec::new();
+
+    let mut tx = begin_write(pool).await?;
+    for (clerk_user_id, achievement_id) in earned {
+        let inserted = sqlx::query(
+            "INSERT OR IGNORE INTO user_achievements (clerk_user_id, achievement_id, game_id, earned_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(clerk_user_id)
+        .bind(achievement_id)
+        .bind(game_id.to_string())
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if inserted {
+            awarded.push(user_achievement(
+                clerk_user_id.clone(),
+                achievement_id.to_string(),
+                game_id,
+                now,
+            ));
+        }
+    }
+    tx.commit().await?;
+
+    Ok(awarded)
+}
+
+/// A user's badges, most recently earned first
+pub async fn get_user_achievements(
+    pool: &SqlitePool,
+    clerk_user_id: &str,
+) -> Result<Vec<UserAchievement>> {
+    let rows = sqlx::query(
+        "SELECT * FROM user_achievements WHERE clerk_user_id = ? ORDER BY earned_at DESC",
+    )
+    .bind(clerk_user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            Ok(user_achievement(
+                row.try_get("clerk_user_id")?,
+                row.try_get("achievement_id")?,
+                uuid_column(row, "game_id")?,
+                timestamp_column(row, "earned_at")?,
+            ))
+        })
+        .collect::<sqlx::Result<_>>()?)
+}
+
+/// Attach the display name and description of the badge's rule
+fn user_achievement(
+    clerk_user_id: String,
+    achievement_id: String,
+    game_id: Uuid,
+    earned_at: chrono::DateTime<Utc>,
+) -> UserAchievement {
+    // Badges whose rule has since been removed keep their id as a name
+    let (name, description) = match achievements::find_rule(&achievement_id) {
+        Some(rule) => (rule.name.to_string(), rule.description.to_string()),
+        None => (achievement_id.clone(), String::new()),
+    };
+
+    UserAchievement {
+        clerk_user_id,
+        achievement_id,
+        name,
+        description,
+        game_id,
+        earned_at,
+    }
+}
+
+/// A user's finished games with a winner, grouped by the color identity of
+/// the deck they played, most played first
+pub async fn get_user_color_stats(
+    pool: &SqlitePool,
+    clerk_user_id: &str,
+) -> Result<Vec<ColorIdentityStats>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT COALESCE(d.color_identity, ?) as color_identity,
+               COUNT(*) as games_played,
+               SUM(CASE WHEN g.winner_player_id = p.id THEN 1 ELSE 0 END) as games_won
+        FROM players p
+        INNER JOIN games g ON g.id = p.game_id
+        LEFT JOIN player_decks d ON d.player_id = p.id
+        WHERE p.clerk_user_id = ? AND g.status = 'finished' AND g.deleted_at IS NULL
+          AND g.winner_player_id IS NOT NULL
+        GROUP BY 1
+        ORDER BY games_played DESC, color_identity
+        "#,
+    )
+    .bind(UNKNOWN_COLOR_IDENTITY)
+    .bind(clerk_user_id)
+    .fetch_all(pool)
     .await?;
 
-    get_game_by_id(pool, game_id).await
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let games_played: i64 = row.get("games_played");
+            let games_won: i64 = row.get("games_won");
+            ColorIdentityStats {
+                color_identity: row.get("color_identity"),
+                games_played,
+                games_won,
+                win_rate: games_won as f64 / games_played as f64,
+            }
+        })
+        .collect())
+}
+
+/// A user's rating, or the default for users who haven't played a rated game
+pub async fn get_player_rating(pool: &SqlitePool, clerk_user_id: &str) -> Result<PlayerRating> {
+    let row = sqlx::query("SELECT * FROM player_ratings WHERE clerk_user_id = ?")
+        .bind(clerk_user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(match row {
+        Some(row) => PlayerRating {
+            clerk_user_id: row.get("clerk_user_id"),
+            rating: row.get("rating"),
+            games_played: row.get("games_played"),
+        },
+        None => PlayerRating {
+            clerk_user_id: clerk_user_id.to_string(),
+            rating: DEFAULT_RATING,
+            games_played: 0,
+        },
+    })
+}
+
+/// Highest rated users first, enriched with user info from Clerk
+pub async fn get_leaderboard(pool: &SqlitePool, limit: i64) -> Result<Vec<LeaderboardEntry>> {
+    let rows =
+        sqlx::query("SELECT * FROM player_ratings ORDER BY rating DESC, games_played DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+    let mut users = lookup_users(
+        rows.iter()
+            .map(|row| row.get::<String, _>("clerk_user_id"))
+            .collect(),
+    )
+    .await;
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let clerk_user_id: String = row.get("clerk_user_id");
+            let user = users
+                .remove(&clerk_user_id)
+                .unwrap_or_else(|| ClerkUser::unknown(&clerk_user_id));
+            LeaderboardEntry {
+                rank: index + 1,
+                display_name: user.display_name(),
+                image_url: user.image_url,
+                clerk_user_id,
+                rating: row.get("rating"),
+                games_played: row.get("games_played"),
+            }
+        })
+        .collect())
+}
+
+/// Filters for `list_games`; unset fields match everything
+#[derive(Debug, Default)]
+pub struct GameListFilter {
+    pub status: Option<String>,
+    /// Matches a game id or a player's clerk user id by substring
+    pub search: Option<String>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// List games of any status, including deleted ones, one page at a time
+pub async fn list_games(pool: &SqlitePool, filter: &GameListFilter) -> Result<GameListPage> {
+    let mut conditions = Vec::new();
+    if filter.status.is_some() {
+        conditions.push("g.status = ?");
+    }
+    if filter.search.is_some() {
+        conditions.push(
+            "(g.id LIKE ? OR g.id IN (SELECT game_id FROM players WHERE clerk_user_id LIKE ?))",
+        );
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let search_pattern = filter.search.as_ref().map(|s| format!("%{s}%"));
+
+    let count_sql = format!("SELECT COUNT(*) as count FROM games g {where_clause}");
+    let mut count_query = sqlx::query(&count_sql);
+    if let Some(status) = &filter.status {
+        count_query = count_query.bind(status);
+    }
+    if let Some(pattern) = &search_pattern {
+        count_query = count_query.bind(pattern).bind(pattern);
+    }
+    let total: i64 = count_query.fetch_one(pool).await?.get("count");
+
+    let page_sql = format!(
+        "SELECT g.* FROM games g {where_clause} ORDER BY g.created_at DESC LIMIT ? OFFSET ?"
+    );
+    let mut page_query = sqlx::query(&page_sql);
+    if let Some(status) = &filter.status {
+        page_query = page_query.bind(status);
+    }
+    if let Some(pattern) = &search_pattern {
+        page_query = page_query.bind(pattern).bind(pattern);
+    }
+    let rows = page_query
+        .bind(filter.limit)
+        .bind(filter.offset)
+        .fetch_all(pool)
+        .await?;
+
+    let mut games = Vec::new();
+    for row in rows {
+        let game = Game::from_row(&row)?;
+        let user_ids = get_game_user_ids(pool, game.id).await?;
+        games.push((game, user_ids));
+    }
+    let games = games_with_user_info(games).await;
+
+    Ok(GameListPage {
+        games,
+        total,
+        limit: filter.limit,
+        offset: filter.offset,
+    })
 }
 
 pub async fn get_user_game_history(
@@ -543,7 +1311,7 @@ pub async fn get_user_game_history(
             SELECT DISTINCT g.*
             FROM games g
             INNER JOIN players p ON g.id = p.game_id
-            WHERE p.clerk_user_id = ? AND g.status = 'finished'{}
+            WHERE p.clerk_user_id = ? AND g.status = 'finished' AND g.deleted_at IS NULL{}
             "#,
             winner_filter
         );
@@ -570,7 +1338,7 @@ pub async fn get_user_game_history(
             SELECT DISTINCT g.*
             FROM games g
             INNER JOIN players p ON g.id = p.game_id
-            WHERE p.clerk_user_id = ? AND g.status = 'finished'{}
+            WHERE p.clerk_user_id = ? AND g.status = 'finished' AND g.deleted_at IS NULL{}
             ORDER BY g.finished_at DESC
             "#,
             winner_filter
@@ -583,23 +1351,8 @@ pub async fn get_user_game_history(
 
     let mut games = Vec::new();
     for row in rows {
-        let game_id = Uuid::parse_str(&row.get::<String, _>("id")).unwrap();
-        let game = Game {
-            id: game_id,
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        };
+        let game = Game::from_row(&row)?;
+        let game_id = game.id;
 
         let players = get_players_in_game(pool, game_id).await?;
 
@@ -619,6 +1372,8 @@ pub async fn get_user_game_history(
         };
 
         games.push(GameWithPlayersEnriched {
+            duration_seconds: game.duration_seconds(),
+            decks: get_player_decks_for_game(pool, game_id).await?,
             game,
             players: enriched_players,
             winner: enriched_winner,
@@ -635,18 +1390,18 @@ async fn initialize_commander_damage_for_player_in_tx(
     player_id: Uuid,
 ) -> Result<()> {
     // Get all existing players in the game
-    let existing_players = sqlx::query("SELECT id FROM players WHERE game_id = ? AND id != ?")
-        .bind(game_id.to_string())
-        .bind(player_id.to_string())
-        .fetch_all(&mut **tx)
-        .await?;
+    let existing_players =
+        sqlx::query("SELECT id, has_partner FROM players WHERE game_id = ? AND id != ?")
+            .bind(game_id.to_string())
+            .bind(player_id.to_string())
+            .fetch_all(&mut **tx)
+            .await?;
 
     let now = Utc::now().to_rfc3339();
 
-    // Create commander damage entries for new player TO all existing players (Commander 1 only)
+    // Create commander damage entries for new player TO all existing players
     for existing_player_row in &existing_players {
-        let existing_player_id =
-            Uuid::parse_str(&existing_player_row.get::<String, _>("id")).unwrap();
+        let existing_player_id = uuid_column(existing_player_row, "id")?;
 
         // From new player to existing player
         sqlx::query(
@@ -677,11 +1432,20 @@ async fn initialize_commander_damage_for_player_in_tx(
         .bind(&now)
         .execute(&mut **tx)
         .await?;
+
+        // Commander 2 in both directions when the existing player has a partner
+        if existing_player_row.get::<bool, _>("has_partner") {
+            activate_partner_row_in_tx(tx, game_id, existing_player_id, player_id, &now).await?;
+            activate_partner_row_in_tx(tx, game_id, player_id, existing_player_id, &now).await?;
+        }
     }
 
     Ok(())
 }
 
+/// Set commander damage to an absolute value. When `expected_damage` is given,
+/// the update only applies if the damage is currently that.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_commander_damage(
     pool: &SqlitePool,
     game_id: Uuid,
@@ -689,37 +1453,21 @@ pub async fn update_commander_damage(
     to_player_id: Uuid,
     commander_number: i32,
     new_damage: i32,
+    expected_damage: Option<i32>,
+    actor_clerk_user_id: &str,
+    audit: NewAuditEntry,
 ) -> Result<CommanderDamage> {
-    let mut tx = pool.begin().await?;
-
-    // Validate damage amount
-    if new_damage < 0 {
-        return Err(ApiError::BadRequest(
-            "Commander damage cannot be negative".to_string(),
-        ));
-    }
-    if new_damage > 999 {
-        return Err(ApiError::BadRequest(
-            "Commander damage cannot exceed 999".to_string(),
-        ));
-    }
+    let mut tx = begin_write(pool).await?;
 
-    // Validate commander number
-    if commander_number != 1 && commander_number != 2 {
-        return Err(ApiError::BadRequest(
-            "Commander number must be 1 or 2".to_string(),
-        ));
-    }
+    validate_commander_damage(new_damage, commander_number)?;
 
     // Validate players exist and are in the game
-    let from_player_exists =
-        sqlx::query("SELECT COUNT(*) as count FROM players WHERE id = ? AND game_id = ?")
+    let from_player_has_partner: Option<bool> =
+        sqlx::query_scalar("SELECT has_partner FROM players WHERE id = ? AND game_id = ?")
             .bind(from_player_id.to_string())
             .bind(game_id.to_string())
-            .fetch_one(&mut *tx)
-            .await?
-            .get::<i64, _>("count")
-            > 0;
+            .fetch_optional(&mut *tx)
+            .await?;
 
     let to_player_exists =
         sqlx::query("SELECT COUNT(*) as count FROM players WHERE id = ? AND game_id = ?")
@@ -730,11 +1478,11 @@ pub async fn update_commander_damage(
             .get::<i64, _>("count")
             > 0;
 
-    if !from_player_exists || !to_player_exists {
+    let Some(from_player_has_partner) = from_player_has_partner.filter(|_| to_player_exists) else {
         return Err(ApiError::BadRequest(
             "One or both players not found in game".to_string(),
         ));
-    }
+    };
 
     // Prevent self-damage
     if from_player_id == to_player_id {
@@ -743,87 +1491,258 @@ pub async fn update_commander_damage(
         ));
     }
 
-    let now = Utc::now().to_rfc3339();
+    // Only a player with a partner has a second commander to deal damage.
+    // The pair's row can stay active after the source disables theirs, while
+    // the target still has one (see `toggle_partner`).
+    if commander_number == 2 {
+        if !from_player_has_partner {
+            return Err(ApiError::BadRequest("partner not enabled".to_string()));
+        }
+        let partner_row_exists = sqlx::query(
+            "SELECT COUNT(*) as count FROM commander_damage WHERE game_id = ? AND from_player_id = ? AND to_player_id = ? AND commander_number = 2 AND active = 1",
+        )
+        .bind(game_id.to_string())
+        .bind(from_player_id.to_string())
+        .bind(to_player_id.to_string())
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<i64, _>("count")
+            > 0;
 
-    // Update or insert commander damage entry
-    let result = sqlx::query(
-        r#"
-        INSERT INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-        ON CONFLICT(game_id, from_player_id, to_player_id, commander_number) 
-        DO UPDATE SET damage = ?, updated_at = ?
-        RETURNING *
-        "#
+        if !partner_row_exists {
+            return Err(ApiError::BadRequest("partner not enabled".to_string()));
+        }
+    }
+
+    if let Some(expected) = expected_damage {
+        let current_damage: i32 = sqlx::query(
+            "SELECT damage FROM commander_damage WHERE game_id = ? AND from_player_id = ? AND to_player_id = ? AND commander_number = ?",
+        )
+        .bind(game_id.to_string())
+        .bind(from_player_id.to_string())
+        .bind(to_player_id.to_string())
+        .bind(commander_number)
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|row| row.get("damage"))
+        .unwrap_or(0);
+
+        if current_damage != expected {
+            return Err(ApiError::Conflict(format!(
+                "Commander damage is {current_damage}, not {expected}"
+            )));
+        }
+    }
+
+    let commander_damage = upsert_commander_damage_in_tx(
+        &mut tx,
+        game_id,
+        from_player_id,
+        to_player_id,
+        commander_number,
+        new_damage,
+        actor_clerk_user_id,
     )
-    .bind(Uuid::new_v4().to_string())
-    .bind(game_id.to_string())
-    .bind(from_player_id.to_string())
-    .bind(to_player_id.to_string())
-    .bind(commander_number)
-    .bind(new_damage)
-    .bind(&now)
-    .bind(&now)
-    .bind(new_damage)
-    .bind(&now)
-    .fetch_one(&mut *tx)
     .await?;
 
-    let commander_damage = CommanderDamage {
-        id: Uuid::parse_str(&result.get::<String, _>("id")).unwrap(),
-        game_id: Uuid::parse_str(&result.get::<String, _>("game_id")).unwrap(),
-        from_player_id: Uuid::parse_str(&result.get::<String, _>("from_player_id")).unwrap(),
-        to_player_id: Uuid::parse_str(&result.get::<String, _>("to_player_id")).unwrap(),
-        commander_number: result.get("commander_number"),
-        damage: result.get("damage"),
-        created_at: chrono::DateTime::parse_from_rfc3339(&result.get::<String, _>("created_at"))
-            .unwrap()
-            .with_timezone(&Utc),
-        updated_at: chrono::DateTime::parse_from_rfc3339(&result.get::<String, _>("updated_at"))
-            .unwrap()
-            .with_timezone(&Utc),
-    };
-
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
     tx.commit().await?;
     Ok(commander_damage)
 }
 
-pub async fn get_commander_damage_for_game(
-    pool: &SqlitePool,
-    game_id: Uuid,
-) -> Result<Vec<CommanderDamage>> {
-    let rows = sqlx::query("SELECT * FROM commander_damage WHERE game_id = ? ORDER BY from_player_id, to_player_id, commander_number")
-        .bind(game_id.to_string())
-        .fetch_all(pool)
-        .await?;
-
-    let commander_damages = rows
-        .into_iter()
-        .map(|row| CommanderDamage {
-            id: Uuid::parse_str(&row.get::<String, _>("id")).unwrap(),
-            game_id: Uuid::parse_str(&row.get::<String, _>("game_id")).unwrap(),
-            from_player_id: Uuid::parse_str(&row.get::<String, _>("from_player_id")).unwrap(),
-            to_player_id: Uuid::parse_str(&row.get::<String, _>("to_player_id")).unwrap(),
-            commander_number: row.get("commander_number"),
-            damage: row.get("damage"),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            updated_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-        })
-        .collect();
+fn validate_commander_damage(new_damage: i32, commander_number: i32) -> Result<()> {
+    // Validate damage amount
+    if new_damage < 0 {
+        return Err(ApiError::BadRequest(
+            "Commander damage cannot be negative".to_string(),
+        ));
+    }
+    if new_damage > 999 {
+        return Err(ApiError::BadRequest(
+            "Commander damage cannot exceed 999".to_string(),
+        ));
+    }
+
+    // Validate commander number
+    if commander_number != 1 && commander_number != 2 {
+        return Err(ApiError::BadRequest(
+            "Commander number must be 1 or 2".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn upsert_commander_damage_in_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: Uuid,
+    from_player_id: Uuid,
+    to_player_id: Uuid,
+    commander_number: i32,
+    new_damage: i32,
+    actor_clerk_user_id: &str,
+) -> Result<CommanderDamage> {
+    let now = Utc::now().to_rfc3339();
+
+    // Update or insert commander damage entry
+    let damage = sqlx::query_as::<_, CommanderDamage>(
+        r#"
+        INSERT INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at, updated_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(game_id, from_player_id, to_player_id, commander_number) 
+        DO UPDATE SET damage = ?, updated_at = ?, updated_by = ?
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(game_id.to_string())
+    .bind(from_player_id.to_string())
+    .bind(to_player_id.to_string())
+    .bind(commander_number)
+    .bind(new_damage)
+    .bind(&now)
+    .bind(&now)
+    .bind(actor_clerk_user_id)
+    .bind(new_damage)
+    .bind(&now)
+    .bind(actor_clerk_user_id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(damage)
+}
+
+/// Apply several commander damage deltas in one transaction. Every entry is
+/// validated before anything is written, so one bad entry rejects the batch.
+/// Returns the resulting damage for each entry, in order; repeated pairs
+/// accumulate.
+pub async fn apply_commander_damage_batch(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    entries: &[UpdateCommanderDamageRequest],
+    actor_clerk_user_id: &str,
+    audit: impl FnOnce(&[CommanderDamage]) -> NewAuditEntry,
+) -> Result<Vec<CommanderDamage>> {
+    let mut tx = begin_write(pool).await?;
+
+    // Each player in the game, with whether they have a partner
+    let players: HashMap<Uuid, bool> =
+        sqlx::query("SELECT id, has_partner FROM players WHERE game_id = ?")
+            .bind(game_id.to_string())
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(|row| Ok((uuid_column(row, "id")?, row.try_get("has_partner")?)))
+            .collect::<sqlx::Result<_>>()?;
+
+    let mut totals: HashMap<(Uuid, Uuid, i32), i32> = sqlx::query(
+        "SELECT from_player_id, to_player_id, commander_number, damage FROM commander_damage WHERE game_id = ? AND active = 1",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(&mut *tx)
+    .await?
+    .iter()
+    .map(|row| {
+        let key = (
+            uuid_column(row, "from_player_id")?,
+            uuid_column(row, "to_player_id")?,
+            row.try_get::<i32, _>("commander_number")?,
+        );
+        Ok((key, row.try_get::<i32, _>("damage")?))
+    })
+    .collect::<sqlx::Result<_>>()?;
+
+    let mut new_damages = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let new_damage = check_batch_entry(&players, &mut totals, entry).map_err(|e| match e {
+            ApiError::BadRequest(message) => {
+                ApiError::BadRequest(format!("Entry {index}: {message}"))
+            }
+            other => other,
+        })?;
+        new_damages.push(new_damage);
+    }
+
+    let mut results = Vec::with_capacity(entries.len());
+    for (entry, new_damage) in entries.iter().zip(new_damages) {
+        results.push(
+            upsert_commander_damage_in_tx(
+                &mut tx,
+                game_id,
+                entry.from_player_id,
+                entry.to_player_id,
+                entry.commander_number,
+                new_damage,
+                actor_clerk_user_id,
+            )
+            .await?,
+        );
+    }
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&results)).await?;
+    tx.commit().await?;
+    Ok(results)
+}
+
+/// Validate one batch entry against the running totals, recording its result
+fn check_batch_entry(
+    players: &HashMap<Uuid, bool>,
+    totals: &mut HashMap<(Uuid, Uuid, i32), i32>,
+    entry: &UpdateCommanderDamageRequest,
+) -> Result<i32> {
+    let (Some(&from_player_has_partner), true) = (
+        players.get(&entry.from_player_id),
+        players.contains_key(&entry.to_player_id),
+    ) else {
+        return Err(ApiError::BadRequest(
+            "One or both players not found in game".to_string(),
+        ));
+    };
+    if entry.from_player_id == entry.to_player_id {
+        return Err(ApiError::BadRequest(
+            "Players cannot deal commander damage to themselves".to_string(),
+        ));
+    }
+
+    let key = (
+        entry.from_player_id,
+        entry.to_player_id,
+        entry.commander_number,
+    );
+    // The source needs a partner, and the pair an active row (see `toggle_partner`)
+    if entry.commander_number == 2 && !(from_player_has_partner && totals.contains_key(&key)) {
+        return Err(ApiError::BadRequest("partner not enabled".to_string()));
+    }
+    let current = totals.get(&key).copied().unwrap_or(0);
+    let new_damage = checked_total("Commander damage", current, entry.damage_amount)?;
+    validate_commander_damage(new_damage, entry.commander_number)?;
+    totals.insert(key, new_damage);
+    Ok(new_damage)
+}
+
+pub async fn get_commander_damage_for_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+) -> Result<Vec<CommanderDamage>> {
+    let commander_damages = sqlx::query_as::<_, CommanderDamage>("SELECT * FROM commander_damage WHERE game_id = ? AND active = 1 ORDER BY from_player_id, to_player_id, commander_number")
+        .bind(game_id.to_string())
+        .fetch_all(pool)
+        .await?;
 
     Ok(commander_damages)
 }
 
+/// Enable or disable a player's partner. Returns the nonzero Commander 2
+/// damage brought back by re-enabling a partner, which clients would otherwise
+/// assume to be zero.
 pub async fn toggle_partner(
     pool: &SqlitePool,
     game_id: Uuid,
     player_id: Uuid,
     enable_partner: bool,
-) -> Result<()> {
-    let mut tx = pool.begin().await?;
+    audit: NewAuditEntry,
+) -> Result<Vec<CommanderDamageChange>> {
+    let mut tx = begin_write(pool).await?;
 
     // Validate player exists in game
     let player_exists =
@@ -839,8 +1758,16 @@ pub async fn toggle_partner(
         return Err(ApiError::BadRequest("Player not found in game".to_string()));
     }
 
+    sqlx::query("UPDATE players SET has_partner = ?, version = version + 1 WHERE id = ?")
+        .bind(enable_partner)
+        .bind(player_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    let mut restored = Vec::new();
     if enable_partner {
-        // Create Commander 2 entries for this player with all other players
+        // Create Commander 2 entries for this player with all other players,
+        // reactivating rows from an earlier partner with their damage intact
         let other_players = sqlx::query("SELECT id FROM players WHERE game_id = ? AND id != ?")
             .bind(game_id.to_string())
             .bind(player_id.to_string())
@@ -850,160 +1777,3131 @@ pub async fn toggle_partner(
         let now = Utc::now().to_rfc3339();
 
         for other_player_row in other_players {
-            let other_player_id =
-                Uuid::parse_str(&other_player_row.get::<String, _>("id")).unwrap();
-
-            // From this player to other player (Commander 2)
-            sqlx::query(
-                "INSERT OR IGNORE INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(Uuid::new_v4().to_string())
-            .bind(game_id.to_string())
-            .bind(player_id.to_string())
-            .bind(other_player_id.to_string())
-            .bind(2) // Commander 2
-            .bind(0) // Initial damage
-            .bind(&now)
-            .bind(&now)
-            .execute(&mut *tx)
-            .await?;
+            let other_player_id = uuid_column(&other_player_row, "id")?;
 
-            // From other player to this player (Commander 2)
-            sqlx::query(
-                "INSERT OR IGNORE INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(Uuid::new_v4().to_string())
-            .bind(game_id.to_string())
-            .bind(other_player_id.to_string())
-            .bind(player_id.to_string())
-            .bind(2) // Commander 2
-            .bind(0) // Initial damage
-            .bind(&now)
-            .bind(&now)
-            .execute(&mut *tx)
-            .await?;
+            // Both directions, so the pair's rows stay reciprocal
+            activate_partner_row_in_tx(&mut tx, game_id, player_id, other_player_id, &now).await?;
+            activate_partner_row_in_tx(&mut tx, game_id, other_player_id, player_id, &now).await?;
         }
+
+        restored = sqlx::query(
+            "SELECT from_player_id, to_player_id, damage FROM commander_damage WHERE game_id = ? AND commander_number = 2 AND damage > 0 AND (from_player_id = ? OR to_player_id = ?)",
+        )
+        .bind(game_id.to_string())
+        .bind(player_id.to_string())
+        .bind(player_id.to_string())
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(CommanderDamageChange {
+                from_player_id: uuid_column(row, "from_player_id")?,
+                to_player_id: uuid_column(row, "to_player_id")?,
+                commander_number: 2,
+                new_damage: row.try_get("damage")?,
+                damage_amount: 0,
+            })
+        })
+        .collect::<sqlx::Result<_>>()?;
     } else {
-        // Remove all Commander 2 entries involving this player
+        // Deactivate Commander 2 entries involving this player, keeping their
+        // damage in case the partner is re-enabled. Pairs with another player
+        // who still has a partner stay active.
         sqlx::query(
-            "DELETE FROM commander_damage WHERE game_id = ? AND commander_number = 2 AND (from_player_id = ? OR to_player_id = ?)"
+            r#"
+            UPDATE commander_damage SET active = 0
+            WHERE game_id = ? AND commander_number = 2 AND (from_player_id = ? OR to_player_id = ?)
+              AND NOT EXISTS (
+                SELECT 1 FROM players o
+                WHERE o.has_partner = 1
+                  AND o.id IN (commander_damage.from_player_id, commander_damage.to_player_id)
+              )
+            "#,
         )
         .bind(game_id.to_string())
         .bind(player_id.to_string())
         .bind(player_id.to_string())
         .execute(&mut *tx)
         .await?;
+
+        // Without a partner there is no second commander to name
+        sqlx::query("UPDATE players SET partner_name = NULL WHERE id = ?")
+            .bind(player_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let repaired = repair_partner_rows_in_tx(&mut tx, game_id).await?;
+    if repaired > 0 {
+        tracing::warn!(
+            "Restored {} one-sided Commander 2 rows in game {}",
+            repaired,
+            game_id
+        );
     }
 
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
     tx.commit().await?;
+    Ok(restored)
+}
+
+/// Create or reactivate the Commander 2 row from one player to another,
+/// keeping any damage it already holds
+async fn activate_partner_row_in_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: Uuid,
+    from_player_id: Uuid,
+    to_player_id: Uuid,
+    now: &str,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at) VALUES (?, ?, ?, ?, 2, 0, ?, ?) ON CONFLICT(game_id, from_player_id, to_player_id, commander_number) DO UPDATE SET active = 1"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(game_id.to_string())
+    .bind(from_player_id.to_string())
+    .bind(to_player_id.to_string())
+    .bind(now)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?;
     Ok(())
 }
 
-pub async fn get_available_games(
-    pool: &SqlitePool,
-    clerk_user_id: &str,
-) -> Result<Vec<GameWithUsers>> {
-    let rows = sqlx::query(
+/// Active Commander 2 rows come in reciprocal pairs, and every player with a
+/// partner has them with every other player. Activate whatever rows are
+/// missing, returning how many that took; anything but 0 means an earlier
+/// write left the game inconsistent.
+async fn repair_partner_rows_in_tx(tx: &mut Transaction<'_, Sqlite>, game_id: Uuid) -> Result<u64> {
+    let unpaired = sqlx::query(
         r#"
-        SELECT g.*
-        FROM games g
-        WHERE g.status = 'active'
-        AND g.id NOT IN (
-            SELECT DISTINCT p.game_id 
-            FROM players p 
-            WHERE p.clerk_user_id = ?
-        )
-        ORDER BY g.created_at DESC
-        LIMIT 50
+        SELECT p.id AS from_player_id, o.id AS to_player_id FROM players p
+        JOIN players o ON o.game_id = p.game_id AND o.id != p.id
+        WHERE p.game_id = ? AND p.has_partner = 1
+          AND NOT EXISTS (
+            SELECT 1 FROM commander_damage cd
+            WHERE cd.game_id = p.game_id AND cd.commander_number = 2 AND cd.active = 1
+              AND cd.from_player_id = p.id AND cd.to_player_id = o.id
+          )
         "#,
     )
-    .bind(clerk_user_id)
-    .fetch_all(pool)
+    .bind(game_id.to_string())
+    .fetch_all(&mut **tx)
     .await?;
 
-    let mut games = Vec::new();
-    for row in rows {
-        let game_id = Uuid::parse_str(&row.get::<String, _>("id")).unwrap();
-        let game = Game {
-            id: game_id,
-            status: row.get("status"),
-            starting_life: row.get("starting_life"),
-            winner_player_id: row
-                .get::<Option<String>, _>("winner_player_id")
-                .and_then(|s| Uuid::parse_str(&s).ok()),
-            created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            finished_at: row.get::<Option<String>, _>("finished_at").map(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .unwrap()
-                    .with_timezone(&Utc)
-            }),
-        };
+    let now = Utc::now().to_rfc3339();
+    for row in &unpaired {
+        let from_player_id: Uuid = uuid_column(row, "from_player_id")?;
+        let to_player_id: Uuid = uuid_column(row, "to_player_id")?;
+        activate_partner_row_in_tx(tx, game_id, from_player_id, to_player_id, &now).await?;
+    }
 
-        // Get users in this game
-        let player_rows = sqlx::query(
-            "SELECT DISTINCT clerk_user_id FROM players WHERE game_id = ? ORDER BY position",
-        )
-        .bind(game_id.to_string())
-        .fetch_all(pool)
-        .await?;
+    let one_sided = sqlx::query(
+        r#"
+        SELECT cd.from_player_id, cd.to_player_id FROM commander_damage cd
+        WHERE cd.game_id = ? AND cd.commander_number = 2 AND cd.active = 1
+          AND NOT EXISTS (
+            SELECT 1 FROM commander_damage r
+            WHERE r.game_id = cd.game_id AND r.commander_number = 2 AND r.active = 1
+              AND r.from_player_id = cd.to_player_id AND r.to_player_id = cd.from_player_id
+          )
+        "#,
+    )
+    .bind(game_id.to_string())
+    .fetch_all(&mut **tx)
+    .await?;
 
-        let users = player_rows
-            .into_iter()
-            .map(|row| UserInfo {
-                clerk_user_id: row.get("clerk_user_id"),
-            })
-            .collect::<Vec<UserInfo>>();
+    for row in &one_sided {
+        let from_player_id: Uuid = uuid_column(row, "from_player_id")?;
+        let to_player_id: Uuid = uuid_column(row, "to_player_id")?;
+        activate_partner_row_in_tx(tx, game_id, to_player_id, from_player_id, &now).await?;
+    }
 
-        // Only include games that aren't full
-        if users.len() < MAX_PLAYERS_PER_GAME {
-            games.push(GameWithUsers { game, users });
-        }
+    Ok((unpaired.len() + one_sided.len()) as u64)
+}
+
+/// Trim and NFC-normalize user-entered text, so names that look the same
+/// are stored the same. Control characters are refused outright.
+fn normalize_text(field: &str, text: &str) -> Result<String> {
+    if text.chars().any(char::is_control) {
+        return Err(ApiError::BadRequest(format!(
+            "{field} cannot contain control characters"
+        )));
+    }
+    Ok(text.trim().nfc().collect())
+}
+
+/// Trim a commander name, treating blank names as unset
+fn normalize_commander_name(name: Option<String>) -> Result<Option<String>> {
+    let name = name
+        .map(|n| normalize_text("Commander names", &n))
+        .transpose()?
+        .filter(|n| !n.is_empty());
+
+    if let Some(ref n) = name
+        && n.chars().count() > MAX_COMMANDER_NAME_LENGTH
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Commander names cannot exceed {MAX_COMMANDER_NAME_LENGTH} characters"
+        )));
     }
 
-    Ok(games)
+    Ok(name)
 }
 
-/// Enrich a single player with user info from Clerk
-pub async fn enrich_player_with_user(player: Player) -> PlayerWithUser {
-    let clerk = ClerkClient::get().ok();
-    let user = if let Some(client) = clerk {
-        client.get_user_or_default(&player.clerk_user_id).await
-    } else {
-        crate::clerk::ClerkUser {
-            id: player.clerk_user_id.clone(),
-            username: None,
-            first_name: None,
-            last_name: None,
-            image_url: None,
-        }
+pub async fn set_commander_name(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    commander_name: Option<String>,
+    partner_name: Option<String>,
+    audit: impl FnOnce(&Player) -> NewAuditEntry,
+) -> Result<Player> {
+    let commander_name = normalize_commander_name(commander_name)?;
+    let partner_name = normalize_commander_name(partner_name)?;
+
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET commander_name = ?, partner_name = ?, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(&commander_name)
+    .bind(&partner_name)
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&player)).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+/// Lowercase a seat color and check it's in the palette. Blank means none.
+fn normalize_seat_color(color: Option<String>) -> Result<Option<String>> {
+    let Some(color) = color
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+    else {
+        return Ok(None);
     };
 
-    PlayerWithUser::from_player(player, user.display_name(), user.username, user.image_url)
+    if !SEAT_COLORS.contains(&color.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "Seat color must be one of: {}",
+            SEAT_COLORS.join(", ")
+        )));
+    }
+
+    Ok(Some(color))
 }
 
-/// Enrich multiple players with user info from Clerk
-pub async fn enrich_players_with_users(players: Vec<Player>) -> Vec<PlayerWithUser> {
-    let mut enriched = Vec::with_capacity(players.len());
-    for player in players {
-        enriched.push(enrich_player_with_user(player).await);
+fn seat_color_taken(color: &str) -> ApiError {
+    ApiError::BadRequest(format!("Seat color {color} is already taken"))
+}
+
+async fn ensure_seat_color_free_in_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: Uuid,
+    color: &str,
+) -> Result<()> {
+    let taken = sqlx::query("SELECT 1 FROM players WHERE game_id = ? AND seat_color = ?")
+        .bind(game_id.to_string())
+        .bind(color)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    match taken {
+        Some(_) => Err(seat_color_taken(color)),
+        None => Ok(()),
     }
-    enriched
 }
 
-/// Get game state with enriched player info
-pub async fn get_game_state_with_users(pool: &SqlitePool, game_id: Uuid) -> Result<GameState> {
-    let game = get_game_by_id(pool, game_id).await?;
-    let players = get_players_in_game(pool, game_id).await?;
-    let enriched_players = enrich_players_with_users(players).await;
-    let recent_changes = get_recent_life_changes(pool, game_id, 20).await?;
-    let commander_damage = get_commander_damage_for_game(pool, game_id).await?;
+pub async fn set_seat_color(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    color: Option<String>,
+    audit: impl FnOnce(&Player) -> NewAuditEntry,
+) -> Result<Player> {
+    let color = normalize_seat_color(color)?;
 
-    Ok(GameState {
-        game,
-        players: enriched_players,
-        recent_changes,
-        commander_damage,
-    })
+    // The partial unique index on (game_id, seat_color) rejects a color
+    // another player already holds
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET seat_color = ?, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(&color)
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            seat_color_taken(color.as_deref().unwrap_or_default())
+        }
+        other => other.into(),
+    })?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&player)).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+/// Canonicalize a color identity to WUBRG order, or "C" for colorless.
+/// Blank means no identity recorded.
+fn normalize_color_identity(colors: Option<String>) -> Result<Option<String>> {
+    let Some(colors) = colors
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+    else {
+        return Ok(None);
+    };
+    if colors == "C" {
+        return Ok(Some(colors));
+    }
+
+    let invalid = || {
+        ApiError::BadRequest(format!(
+            "Color identity must be a subset of WUBRG or C for colorless, got '{colors}'"
+        ))
+    };
+    let mut seen = [false; 5];
+    for c in colors.chars() {
+        let index = "WUBRG".find(c).ok_or_else(invalid)?;
+        if seen[index] {
+            return Err(invalid());
+        }
+        seen[index] = true;
+    }
+
+    Ok(Some(
+        "WUBRG"
+            .chars()
+            .zip(seen)
+            .filter_map(|(c, present)| present.then_some(c))
+            .collect(),
+    ))
+}
+
+fn normalize_decklist_url(url: Option<String>) -> Result<Option<String>> {
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+
+    if let Some(ref u) = url {
+        if !(u.starts_with("https://") || u.starts_with("http://")) {
+            return Err(ApiError::BadRequest(
+                "Decklist URL must start with http:// or https://".to_string(),
+            ));
+        }
+        if u.len() > MAX_DECKLIST_URL_LENGTH {
+            return Err(ApiError::BadRequest(format!(
+                "Decklist URL cannot exceed {MAX_DECKLIST_URL_LENGTH} characters"
+            )));
+        }
+    }
+
+    Ok(url)
+}
+
+/// Attach a deck to a player, replacing any earlier one. The player's
+/// commander and partner names are set from the deck.
+pub async fn set_player_deck(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    request: SetPlayerDeckRequest,
+    audit: impl FnOnce(&PlayerDeck) -> NewAuditEntry,
+) -> Result<(Player, PlayerDeck)> {
+    let commander_name = normalize_commander_name(Some(request.commander_name))?
+        .ok_or_else(|| ApiError::BadRequest("Commander name is required".to_string()))?;
+    let partner_name = normalize_commander_name(request.partner_name)?;
+    let color_identity = normalize_color_identity(request.color_identity)?;
+    let decklist_url = normalize_decklist_url(request.decklist_url)?;
+
+    let mut tx = begin_write(pool).await?;
+
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET commander_name = ?, partner_name = ?, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(&commander_name)
+    .bind(&partner_name)
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    let deck = sqlx::query_as::<_, PlayerDeck>(
+        r#"
+        INSERT INTO player_decks (player_id, game_id, commander_name, partner_name, color_identity, decklist_url, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(player_id)
+        DO UPDATE SET commander_name = excluded.commander_name,
+                      partner_name = excluded.partner_name,
+                      color_identity = excluded.color_identity,
+                      decklist_url = excluded.decklist_url,
+                      updated_at = excluded.updated_at
+        RETURNING *
+        "#,
+    )
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .bind(&commander_name)
+    .bind(&partner_name)
+    .bind(&color_identity)
+    .bind(&decklist_url)
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&deck)).await?;
+    tx.commit().await?;
+    Ok((player, deck))
+}
+
+pub async fn get_player_decks_for_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+) -> Result<Vec<PlayerDeck>> {
+    let decks = sqlx::query_as::<_, PlayerDeck>(
+        "SELECT d.* FROM player_decks d JOIN players p ON p.id = d.player_id WHERE d.game_id = ? ORDER BY p.position",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(decks)
+}
+
+pub async fn get_available_games(
+    pool: &SqlitePool,
+    clerk_user_id: &str,
+) -> Result<Vec<GameWithUsers>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT g.*
+        FROM games g
+        WHERE g.status IN ('lobby', 'active') AND g.deleted_at IS NULL AND g.visibility = 'public'
+        AND g.id NOT IN (
+            SELECT DISTINCT p.game_id 
+            FROM players p 
+            WHERE p.clerk_user_id = ?
+        )
+        -- Full by the same count joining checks, before the limit is applied
+        AND (SELECT COUNT(*) FROM players p WHERE p.game_id = g.id) < g.max_players
+        ORDER BY g.created_at DESC
+        LIMIT 50
+        "#,
+    )
+    .bind(clerk_user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut games = Vec::new();
+    for row in rows {
+        let game = Game::from_row(&row)?;
+        let user_ids = get_game_user_ids(pool, game.id).await?;
+        games.push((game, user_ids));
+    }
+
+    Ok(games_with_user_info(games).await)
+}
+
+/// The users seated in a game, in seat order
+async fn get_game_user_ids(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT DISTINCT clerk_user_id FROM players WHERE game_id = ? ORDER BY position",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(|row| row.get("clerk_user_id")).collect())
+}
+
+/// Most Clerk lookups in flight at once when enriching a listing
+const CLERK_LOOKUP_CONCURRENCY: usize = 16;
+
+/// Look up each distinct user in Clerk, a bounded number at a time. Without
+/// Clerk, users get only the fallback display name.
+async fn lookup_users(user_ids: HashSet<String>) -> HashMap<String, ClerkUser> {
+    let clerk = ClerkClient::get().ok();
+    futures::stream::iter(user_ids)
+        .map(|user_id| async move {
+            let user = match clerk {
+                Some(client) => client.get_user_or_default(&user_id).await,
+                None => ClerkUser::unknown(&user_id),
+            };
+            (user_id, user)
+        })
+        .buffer_unordered(CLERK_LOOKUP_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Attach display info from Clerk to each game's users. Every distinct user in
+/// the listing is looked up once.
+async fn games_with_user_info(games: Vec<(Game, Vec<String>)>) -> Vec<GameWithUsers> {
+    let users = lookup_users(
+        games
+            .iter()
+            .flat_map(|(_, user_ids)| user_ids.iter().cloned())
+            .collect(),
+    )
+    .await;
+
+    games
+        .into_iter()
+        .map(|(game, user_ids)| GameWithUsers {
+            game,
+            users: user_ids
+                .into_iter()
+                .map(|clerk_user_id| {
+                    let user = &users[&clerk_user_id];
+                    UserInfo {
+                        display_name: user.display_name(),
+                        image_url: user.image_url.clone(),
+                        clerk_user_id,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Enrich a single player with user info from Clerk
+pub async fn enrich_player_with_user(player: Player) -> PlayerWithUser {
+    let clerk = ClerkClient::get().ok();
+    let user = if let Some(client) = clerk {
+        client.get_user_or_default(&player.clerk_user_id).await
+    } else {
+        ClerkUser::unknown(&player.clerk_user_id)
+    };
+
+    PlayerWithUser::from_player(player, user.display_name(), user.username, user.image_url)
+}
+
+/// Enrich multiple players with user info from Clerk, fetching users concurrently
+pub async fn enrich_players_with_users(players: Vec<Player>) -> Vec<PlayerWithUser> {
+    futures::future::join_all(players.into_iter().map(enrich_player_with_user)).await
+}
+
+/// Get game state with enriched player info
+pub async fn get_game_state_with_users(pool: &SqlitePool, game_id: Uuid) -> Result<GameState> {
+    let game = get_game_by_id(pool, game_id).await?;
+    let players = get_players_in_game(pool, game_id).await?;
+    let enriched_players = enrich_players_with_users(players).await;
+    let recent_changes = get_recent_life_changes(pool, game_id, 20).await?;
+    let commander_damage = get_commander_damage_for_game(pool, game_id).await?;
+    let game_counters = get_game_counters(pool, game_id).await?;
+    let decks = get_player_decks_for_game(pool, game_id).await?;
+
+    Ok(GameState {
+        game,
+        players: enriched_players,
+        recent_changes,
+        commander_damage,
+        game_counters,
+        decks,
+    })
+}
+
+/// The game as it stood at `at`, rebuilt from history for settling disputes.
+///
+/// Life totals come from the last life change at or before `at`, or starting
+/// life. Players are those seated by then, going by their join in the audit
+/// log; players with no join entry (the creator) count from the game's
+/// creation. Commander damage rows appear only if unchanged since `at`.
+///
+/// This is an approximation. Players who left since took their life changes
+/// and commander damage with them. A reset discards earlier history, and
+/// damage changed after `at` can't be wound back. Other player fields,
+/// counters and decks are as they are now.
+pub async fn get_game_state_at(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    at: DateTime<Utc>,
+) -> Result<GameState> {
+    let mut game = get_game_by_id(pool, game_id).await?;
+    if at < game.created_at {
+        return Err(ApiError::BadRequest(format!(
+            "Game was created at {}",
+            game.created_at.to_rfc3339()
+        )));
+    }
+
+    // Only the status it had then
+    let ended_at = game.finished_at.or(game.deleted_at);
+    if ended_at.is_none_or(|ended| ended > at) {
+        game.winner_player_id = None;
+        game.outcome = None;
+        game.finished_at = None;
+        game.deleted_at = None;
+        game.status =
+            if game.status == "lobby" || game.started_at.is_some_and(|started| started > at) {
+                "lobby".to_string()
+            } else {
+                "active".to_string()
+            };
+    }
+
+    let audit = get_audit_log(pool, game_id).await?;
+    let audited_player = |action: &str| -> HashMap<Uuid, DateTime<Utc>> {
+        audit
+            .iter()
+            .filter(|entry| entry.action == action)
+            .filter_map(|entry| {
+                let player_id = entry.payload.get("playerId")?.as_str()?;
+                Some((Uuid::parse_str(player_id).ok()?, entry.created_at))
+            })
+            .collect()
+    };
+    let joined_at = audited_player("join");
+    let eliminated_at = audited_player("player_eliminated");
+
+    let changes: Vec<LifeChange> = get_life_changes_for_game(pool, game_id)
+        .await?
+        .into_iter()
+        .filter(|change| change.created_at <= at)
+        .collect();
+
+    let mut players = get_players_in_game(pool, game_id).await?;
+    players.retain(|player| joined_at.get(&player.id).is_none_or(|&joined| joined <= at));
+    for player in &mut players {
+        // Changes are oldest first, so the last one is the total at the time
+        player.current_life = changes
+            .iter()
+            .rfind(|change| change.player_id == player.id)
+            .map_or(player.starting_life, |change| change.new_life_total);
+        if eliminated_at
+            .get(&player.id)
+            .is_none_or(|&eliminated| eliminated > at)
+        {
+            player.elimination_cause = None;
+        }
+    }
+    let seated: HashSet<Uuid> = players.iter().map(|player| player.id).collect();
+
+    let mut commander_damage = get_commander_damage_for_game(pool, game_id).await?;
+    commander_damage.retain(|damage| {
+        damage.updated_at <= at
+            && seated.contains(&damage.from_player_id)
+            && seated.contains(&damage.to_player_id)
+    });
+
+    let recent_changes = changes
+        .into_iter()
+        .rev()
+        .filter(|change| seated.contains(&change.player_id))
+        .take(20)
+        .collect();
+
+    Ok(GameState {
+        game,
+        players: enrich_players_with_users(players).await,
+        recent_changes,
+        commander_damage,
+        game_counters: get_game_counters(pool, game_id).await?,
+        decks: get_player_decks_for_game(pool, game_id).await?,
+    })
+}
+
+/// Game states for several games at once, with one query per table rather
+/// than per game. Unknown ids are left out rather than failing the batch.
+pub async fn get_game_states_with_users(
+    pool: &SqlitePool,
+    game_ids: &[Uuid],
+) -> Result<HashMap<Uuid, GameState>> {
+    // Bound as one JSON array and expanded with json_each, so the SQL doesn't
+    // depend on how many ids there are
+    let ids = serde_json::to_string(game_ids).map_err(|e| ApiError::Internal(e.into()))?;
+
+    let games = sqlx::query_as::<_, Game>(
+        "SELECT * FROM games WHERE id IN (SELECT value FROM json_each(?))",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    let mut states: HashMap<Uuid, GameState> = games
+        .into_iter()
+        .map(|game| {
+            let state = GameState {
+                game,
+                players: Vec::new(),
+                recent_changes: Vec::new(),
+                commander_damage: Vec::new(),
+                game_counters: Vec::new(),
+                decks: Vec::new(),
+            };
+            (state.game.id, state)
+        })
+        .collect();
+
+    let players = sqlx::query_as::<_, Player>(
+        "SELECT * FROM players WHERE game_id IN (SELECT value FROM json_each(?)) ORDER BY game_id, position",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    for player in enrich_players_with_users(players).await {
+        if let Some(state) = states.get_mut(&player.game_id) {
+            state.players.push(player);
+        }
+    }
+
+    // The 20 most recent per game, as for a single game
+    let recent_changes = sqlx::query_as::<_, LifeChange>(
+        r#"
+        SELECT * FROM (
+            SELECT *, ROW_NUMBER() OVER (PARTITION BY game_id ORDER BY created_at DESC) AS recency
+            FROM life_changes WHERE game_id IN (SELECT value FROM json_each(?))
+        )
+        WHERE recency <= 20
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    for change in recent_changes {
+        if let Some(state) = states.get_mut(&change.game_id) {
+            state.recent_changes.push(change);
+        }
+    }
+
+    let commander_damage = sqlx::query_as::<_, CommanderDamage>(
+        "SELECT * FROM commander_damage WHERE game_id IN (SELECT value FROM json_each(?)) AND active = 1 ORDER BY from_player_id, to_player_id, commander_number",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    for damage in commander_damage {
+        if let Some(state) = states.get_mut(&damage.game_id) {
+            state.commander_damage.push(damage);
+        }
+    }
+
+    let counters = sqlx::query_as::<_, GameCounter>(
+        "SELECT * FROM game_counters WHERE game_id IN (SELECT value FROM json_each(?)) ORDER BY name",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    for counter in counters {
+        if let Some(state) = states.get_mut(&counter.game_id) {
+            state.game_counters.push(counter);
+        }
+    }
+
+    let decks = sqlx::query_as::<_, PlayerDeck>(
+        "SELECT d.* FROM player_decks d JOIN players p ON p.id = d.player_id WHERE d.game_id IN (SELECT value FROM json_each(?)) ORDER BY p.position",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    for deck in decks {
+        if let Some(state) = states.get_mut(&deck.game_id) {
+            state.decks.push(deck);
+        }
+    }
+
+    Ok(states)
+}
+
+pub async fn set_strict_control(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    strict_control: bool,
+    audit: NewAuditEntry,
+) -> Result<Game> {
+    let mut tx = begin_write(pool).await?;
+    let game =
+        sqlx::query_as::<_, Game>("UPDATE games SET strict_control = ? WHERE id = ? RETURNING *")
+            .bind(strict_control)
+            .bind(game_id.to_string())
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(ApiError::GameNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    tx.commit().await?;
+    Ok(game)
+}
+
+/// Check that `actor_clerk_user_id` may change the given player's life: under
+/// strict control only the player themselves or the host may.
+pub async fn ensure_can_control_player(
+    pool: &SqlitePool,
+    game: &Game,
+    player_id: Uuid,
+    actor_clerk_user_id: &str,
+) -> Result<()> {
+    let row = sqlx::query("SELECT clerk_user_id FROM players WHERE id = ? AND game_id = ?")
+        .bind(player_id.to_string())
+        .bind(game.id.to_string())
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::PlayerNotFound)?;
+
+    if !game.strict_control || row.get::<String, _>("clerk_user_id") == actor_clerk_user_id {
+        return Ok(());
+    }
+
+    let host = get_game_host(pool, game.id).await?;
+    if host.as_deref() == Some(actor_clerk_user_id) {
+        return Ok(());
+    }
+
+    Err(ApiError::Forbidden(
+        "Only the player or the game host can change this player's life".to_string(),
+    ))
+}
+
+/// Cancel a game by soft-deleting it. Unlike `end_game` this records no result;
+/// the row is kept for history and audit.
+pub async fn delete_game(pool: &SqlitePool, game_id: Uuid, audit: NewAuditEntry) -> Result<Game> {
+    let mut tx = begin_write(pool).await?;
+    let game = sqlx::query_as::<_, Game>(
+        "UPDATE games SET status = 'cancelled', deleted_at = ? WHERE id = ? AND status IN ('lobby', 'active') RETURNING *",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    match game {
+        Some(game) => {
+            record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+            tx.commit().await?;
+            Ok(game)
+        }
+        None => {
+            // Distinguish a missing game from one that already ended
+            get_game_by_id_in_tx(&mut tx, game_id).await?;
+            Err(ApiError::GameNotActive)
+        }
+    }
+}
+
+/// Latest activity in game `g`: creation, life changes, commander damage, or any
+/// audited action such as a join. Timestamps are RFC 3339 text, so MAX compares
+/// them in time order.
+const LAST_ACTIVITY_SQL: &str = r#"
+    MAX(
+        g.created_at,
+        COALESCE((SELECT MAX(created_at) FROM life_changes WHERE game_id = g.id), ''),
+        COALESCE((SELECT MAX(updated_at) FROM commander_damage WHERE game_id = g.id), ''),
+        COALESCE((SELECT MAX(created_at) FROM audit_log WHERE game_id = g.id), '')
+    )
+"#;
+
+/// Lobby and active games with no activity since `cutoff`
+pub async fn get_stale_game_ids(
+    pool: &SqlitePool,
+    cutoff: chrono::DateTime<Utc>,
+) -> Result<Vec<Uuid>> {
+    let query = format!(
+        "SELECT g.id FROM games g WHERE g.status IN ('lobby', 'active') AND g.deleted_at IS NULL AND {LAST_ACTIVITY_SQL} < ?"
+    );
+    let rows = sqlx::query(&query)
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| uuid_column(row, "id"))
+        .collect::<sqlx::Result<_>>()?)
+}
+
+/// End a game as a no contest if it is still open and still idle since
+/// `cutoff`. Returns whether the game was ended.
+pub async fn end_stale_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    cutoff: chrono::DateTime<Utc>,
+) -> Result<bool> {
+    let query = format!(
+        r#"
+        UPDATE games AS g
+        SET status = 'finished', finished_at = ?, outcome = ?
+        WHERE g.id = ? AND g.status IN ('lobby', 'active') AND g.deleted_at IS NULL AND {LAST_ACTIVITY_SQL} < ?
+        "#
+    );
+    let result = sqlx::query(&query)
+        .bind(Utc::now().to_rfc3339())
+        .bind(OUTCOME_NO_CONTEST)
+        .bind(game_id.to_string())
+        .bind(cutoff.to_rfc3339())
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Finish a lobby or active game with no winner, whoever its host is. Returns
+/// whether the game was open; finished and cancelled games are left as they
+/// are, with no audit entry.
+pub async fn force_end_game(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    audit: NewAuditEntry,
+) -> Result<bool> {
+    let mut tx = begin_write(pool).await?;
+    let ended = sqlx::query(
+        "UPDATE games SET status = 'finished', finished_at = ? WHERE id = ? AND status IN ('lobby', 'active')",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(game_id.to_string())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if ended {
+        record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+        tx.commit().await?;
+    }
+    Ok(ended)
+}
+
+/// Move a game out of the lobby so play can begin
+pub async fn start_game(pool: &SqlitePool, game_id: Uuid, audit: NewAuditEntry) -> Result<Game> {
+    let mut tx = begin_write(pool).await?;
+    let game = sqlx::query_as::<_, Game>(
+        "UPDATE games SET status = 'active', started_at = ? WHERE id = ? AND status = 'lobby' RETURNING *",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    match game {
+        Some(game) => {
+            record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+            tx.commit().await?;
+            Ok(game)
+        }
+        None => {
+            let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+            Err(ApiError::BadRequest(format!(
+                "Cannot start {} game",
+                game.status
+            )))
+        }
+    }
+}
+
+/// The host of a game is the player in the lowest seat: the creator, or whoever
+/// inherited the first seat after the creator left.
+pub async fn get_game_host(pool: &SqlitePool, game_id: Uuid) -> Result<Option<String>> {
+    let row = sqlx::query(
+        "SELECT clerk_user_id FROM players WHERE game_id = ? ORDER BY position LIMIT 1",
+    )
+    .bind(game_id.to_string())
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| row.get("clerk_user_id")))
+}
+
+/// Record a cast of the player's commander, raising their commander tax
+pub async fn increment_commander_casts(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    audit: impl FnOnce(&Player) -> NewAuditEntry,
+) -> Result<Player> {
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET commander_casts = commander_casts + 1, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&player)).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+pub async fn reset_commander_casts(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    audit: impl FnOnce(&Player) -> NewAuditEntry,
+) -> Result<Player> {
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET commander_casts = 0, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&player)).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+pub async fn set_player_mulligans(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    mulligans: i32,
+    audit: NewAuditEntry,
+) -> Result<Player> {
+    if !(0..=MAX_MULLIGANS).contains(&mulligans) {
+        return Err(ApiError::BadRequest(format!(
+            "Mulligans must be between 0 and {MAX_MULLIGANS}"
+        )));
+    }
+
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET mulligans = ?, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(mulligans)
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+pub async fn set_player_hand_size(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    hand_size: i32,
+    audit: NewAuditEntry,
+) -> Result<Player> {
+    if !(0..=MAX_HAND_SIZE).contains(&hand_size) {
+        return Err(ApiError::BadRequest(format!(
+            "Hand size must be between 0 and {MAX_HAND_SIZE}"
+        )));
+    }
+
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET hand_size = ?, version = version + 1 WHERE id = ? AND game_id = ? RETURNING *",
+    )
+    .bind(hand_size)
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::PlayerNotFound)?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    tx.commit().await?;
+    Ok(player)
+}
+
+/// Mark a player eliminated. Returns `None` if they were already out, so each
+/// elimination is only reported once.
+pub async fn eliminate_player(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    player_id: Uuid,
+    cause: EliminationCause,
+    audit: NewAuditEntry,
+) -> Result<Option<Player>> {
+    let mut tx = begin_write(pool).await?;
+    let player = sqlx::query_as::<_, Player>(
+        "UPDATE players SET elimination_cause = ?, version = version + 1 WHERE id = ? AND game_id = ? AND elimination_cause IS NULL RETURNING *",
+    )
+    .bind(cause.as_str())
+    .bind(player_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if player.is_some() {
+        record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+        tx.commit().await?;
+    }
+    Ok(player)
+}
+
+// Game counter operations
+fn validate_counter_name(name: &str) -> Result<String> {
+    let name = normalize_text("Counter name", name)?;
+    if name.is_empty() || name.chars().count() > MAX_COUNTER_NAME_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Counter name must be between 1 and {MAX_COUNTER_NAME_LENGTH} characters"
+        )));
+    }
+    Ok(name)
+}
+
+// Row mapping. UUIDs and timestamps are stored as TEXT (RFC 3339), so the
+// models decode those columns by hand rather than deriving FromRow.
+fn column_decode_error(
+    column: &str,
+    source: impl std::error::Error + Send + Sync + 'static,
+) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: column.to_string(),
+        source: Box::new(source),
+    }
+}
+
+fn uuid_column(row: &SqliteRow, column: &str) -> sqlx::Result<Uuid> {
+    let text: String = row.try_get(column)?;
+    Uuid::parse_str(&text).map_err(|e| column_decode_error(column, e))
+}
+
+fn optional_uuid_column(row: &SqliteRow, column: &str) -> sqlx::Result<Option<Uuid>> {
+    let text: Option<String> = row.try_get(column)?;
+    text.map(|text| Uuid::parse_str(&text).map_err(|e| column_decode_error(column, e)))
+        .transpose()
+}
+
+fn timestamp_column(row: &SqliteRow, column: &str) -> sqlx::Result<DateTime<Utc>> {
+    let text: String = row.try_get(column)?;
+    DateTime::parse_from_rfc3339(&text)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| column_decode_error(column, e))
+}
+
+fn optional_timestamp_column(row: &SqliteRow, column: &str) -> sqlx::Result<Option<DateTime<Utc>>> {
+    let text: Option<String> = row.try_get(column)?;
+    text.map(|text| {
+        DateTime::parse_from_rfc3339(&text)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|e| column_decode_error(column, e))
+    })
+    .transpose()
+}
+
+impl FromRow<'_, SqliteRow> for Player {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(Player {
+            id: uuid_column(row, "id")?,
+            game_id: uuid_column(row, "game_id")?,
+            clerk_user_id: row.try_get("clerk_user_id")?,
+            current_life: row.try_get("current_life")?,
+            position: row.try_get("position")?,
+            commander_name: row.try_get("commander_name")?,
+            partner_name: row.try_get("partner_name")?,
+            version: row.try_get("version")?,
+            commander_casts: row.try_get("commander_casts")?,
+            elimination_cause: row.try_get("elimination_cause")?,
+            mulligans: row.try_get("mulligans")?,
+            hand_size: row.try_get("hand_size")?,
+            starting_life: row.try_get("starting_life")?,
+            seat_color: row.try_get("seat_color")?,
+            has_partner: row.try_get("has_partner")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for Game {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(Game {
+            id: uuid_column(row, "id")?,
+            status: row.try_get("status")?,
+            starting_life: row.try_get("starting_life")?,
+            // A dangling winner reference reads as no winner
+            winner_player_id: row
+                .try_get::<Option<String>, _>("winner_player_id")?
+                .and_then(|s| Uuid::parse_str(&s).ok()),
+            created_at: timestamp_column(row, "created_at")?,
+            finished_at: optional_timestamp_column(row, "finished_at")?,
+            strict_control: row.try_get("strict_control")?,
+            format: row.try_get("format")?,
+            max_players: row.try_get("max_players")?,
+            deleted_at: optional_timestamp_column(row, "deleted_at")?,
+            outcome: row.try_get("outcome")?,
+            commander_lethal: row.try_get("commander_lethal")?,
+            visibility: row.try_get("visibility")?,
+            join_code: row.try_get("join_code")?,
+            started_at: optional_timestamp_column(row, "started_at")?,
+            auto_eliminate: row.try_get("auto_eliminate")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for LifeChange {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(LifeChange {
+            id: uuid_column(row, "id")?,
+            game_id: uuid_column(row, "game_id")?,
+            player_id: uuid_column(row, "player_id")?,
+            source_player_id: optional_uuid_column(row, "source_player_id")?,
+            reason: row
+                .try_get::<Option<String>, _>("reason")?
+                .and_then(|reason| LifeChangeReason::parse(&reason)),
+            change_amount: row.try_get("change_amount")?,
+            new_life_total: row.try_get("new_life_total")?,
+            created_at: timestamp_column(row, "created_at")?,
+            created_by: row.try_get("created_by")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for CommanderDamage {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(CommanderDamage {
+            id: uuid_column(row, "id")?,
+            game_id: uuid_column(row, "game_id")?,
+            from_player_id: uuid_column(row, "from_player_id")?,
+            to_player_id: uuid_column(row, "to_player_id")?,
+            commander_number: row.try_get("commander_number")?,
+            damage: row.try_get("damage")?,
+            created_at: timestamp_column(row, "created_at")?,
+            updated_at: timestamp_column(row, "updated_at")?,
+            updated_by: row.try_get("updated_by")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for GameCounter {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(GameCounter {
+            game_id: uuid_column(row, "game_id")?,
+            name: row.try_get("name")?,
+            value: row.try_get("value")?,
+            reset_each_turn: row.try_get("reset_each_turn")?,
+            updated_at: timestamp_column(row, "updated_at")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for PlayerDeck {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(PlayerDeck {
+            player_id: uuid_column(row, "player_id")?,
+            game_id: uuid_column(row, "game_id")?,
+            commander_name: row.try_get("commander_name")?,
+            partner_name: row.try_get("partner_name")?,
+            color_identity: row.try_get("color_identity")?,
+            decklist_url: row.try_get("decklist_url")?,
+            updated_at: timestamp_column(row, "updated_at")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for AuditLogEntry {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(AuditLogEntry {
+            id: uuid_column(row, "id")?,
+            game_id: uuid_column(row, "game_id")?,
+            actor_clerk_user_id: row.try_get("actor_clerk_user_id")?,
+            action: row.try_get("action")?,
+            payload: serde_json::from_str(&row.try_get::<String, _>("payload_json")?)
+                .unwrap_or(serde_json::Value::Null),
+            created_at: timestamp_column(row, "created_at")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for GameEventRecord {
+    fn from_row(row: &SqliteRow) -> sqlx::Result<Self> {
+        Ok(GameEventRecord {
+            seq: row.try_get("seq")?,
+            created_at: timestamp_column(row, "created_at")?,
+            message: serde_json::from_str(&row.try_get::<String, _>("payload_json")?)
+                .unwrap_or(serde_json::Value::Null),
+        })
+    }
+}
+
+/// Set a counter to an absolute value, creating it if needed. The reset flag is
+/// left unchanged when `reset_each_turn` is `None`.
+pub async fn set_game_counter(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    name: &str,
+    value: i32,
+    reset_each_turn: Option<bool>,
+    audit: impl FnOnce(&GameCounter) -> NewAuditEntry,
+) -> Result<GameCounter> {
+    let name = validate_counter_name(name)?;
+    check_tracked_value("Counter value", value)?;
+
+    let mut tx = begin_write(pool).await?;
+    let counter = sqlx::query_as::<_, GameCounter>(
+        r#"
+        INSERT INTO game_counters (game_id, name, value, reset_each_turn, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(game_id, name)
+        DO UPDATE SET value = excluded.value,
+                      reset_each_turn = COALESCE(?, reset_each_turn),
+                      updated_at = excluded.updated_at
+        RETURNING *
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(&name)
+    .bind(value)
+    .bind(reset_each_turn.unwrap_or(false))
+    .bind(Utc::now().to_rfc3339())
+    .bind(reset_each_turn)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&counter)).await?;
+    tx.commit().await?;
+    Ok(counter)
+}
+
+/// Add to a counter, creating it at zero first if needed
+pub async fn increment_game_counter(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    name: &str,
+    amount: i32,
+    audit: impl FnOnce(&GameCounter) -> NewAuditEntry,
+) -> Result<GameCounter> {
+    let name = validate_counter_name(name)?;
+
+    let mut tx = begin_write(pool).await?;
+
+    let current: i32 =
+        sqlx::query("SELECT value FROM game_counters WHERE game_id = ? AND name = ?")
+            .bind(game_id.to_string())
+            .bind(&name)
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|row| row.get("value"))
+            .unwrap_or(0);
+    let value = checked_total("Counter value", current, amount)?;
+
+    let counter = sqlx::query_as::<_, GameCounter>(
+        r#"
+        INSERT INTO game_counters (game_id, name, value, reset_each_turn, updated_at)
+        VALUES (?, ?, ?, 0, ?)
+        ON CONFLICT(game_id, name)
+        DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+        RETURNING *
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(&name)
+    .bind(value)
+    .bind(Utc::now().to_rfc3339())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&counter)).await?;
+    tx.commit().await?;
+    Ok(counter)
+}
+
+/// Zero every counter flagged `reset_each_turn`, returning the reset counters
+pub async fn reset_turn_counters(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    audit: impl FnOnce(&[GameCounter]) -> NewAuditEntry,
+) -> Result<Vec<GameCounter>> {
+    let mut tx = begin_write(pool).await?;
+    let counters = sqlx::query_as::<_, GameCounter>(
+        "UPDATE game_counters SET value = 0, updated_at = ? WHERE game_id = ? AND reset_each_turn = 1 RETURNING *",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(game_id.to_string())
+    .fetch_all(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&counters)).await?;
+    tx.commit().await?;
+    Ok(counters)
+}
+
+pub async fn get_game_counters(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<GameCounter>> {
+    let counters = sqlx::query_as::<_, GameCounter>(
+        "SELECT * FROM game_counters WHERE game_id = ? ORDER BY name",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(counters)
+}
+
+// Audit log operations
+/// Record an action that changes nothing else, such as an end-game vote.
+/// Mutations write their entry with `record_audit_entry_in_tx` instead.
+pub async fn record_audit_entry(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    entry: NewAuditEntry,
+) -> Result<AuditLogEntry> {
+    let mut tx = begin_write(pool).await?;
+    let entry = record_audit_entry_in_tx(&mut tx, game_id, entry).await?;
+    tx.commit().await?;
+    Ok(entry)
+}
+
+/// Write an audit row in the transaction making the change it describes, so
+/// the two commit or roll back together
+async fn record_audit_entry_in_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    game_id: Uuid,
+    entry: NewAuditEntry,
+) -> Result<AuditLogEntry> {
+    let entry = AuditLogEntry {
+        id: Uuid::new_v4(),
+        game_id,
+        actor_clerk_user_id: entry.actor_clerk_user_id,
+        action: entry.action.to_string(),
+        payload: entry.payload,
+        created_at: Utc::now(),
+    };
+
+    sqlx::query(
+        "INSERT INTO audit_log (id, game_id, actor_clerk_user_id, action, payload_json, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(entry.id.to_string())
+    .bind(entry.game_id.to_string())
+    .bind(&entry.actor_clerk_user_id)
+    .bind(&entry.action)
+    .bind(entry.payload.to_string())
+    .bind(entry.created_at.to_rfc3339())
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(entry)
+}
+
+pub async fn get_audit_log(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<AuditLogEntry>> {
+    let entries = sqlx::query_as::<_, AuditLogEntry>(
+        "SELECT * FROM audit_log WHERE game_id = ? ORDER BY created_at",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Append a broadcast message to the game's event log, returning its sequence number
+pub async fn append_game_event(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    message: &WebSocketMessage,
+) -> Result<i64> {
+    let payload = serde_json::to_string(&message.clone().without_join_code())
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    // Single statement, so concurrent appends can't claim the same seq
+    let row = sqlx::query(
+        r#"
+        INSERT INTO game_events (game_id, seq, payload_json, created_at)
+        SELECT ?, COALESCE(MAX(seq), 0) + 1, ?, ? FROM game_events WHERE game_id = ?
+        RETURNING seq
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(payload)
+    .bind(Utc::now().to_rfc3339())
+    .bind(game_id.to_string())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.get("seq"))
+}
+
+/// Highest logged sequence number for a game, or 0 if nothing has been logged
+pub async fn get_latest_game_event_seq(pool: &SqlitePool, game_id: Uuid) -> Result<i64> {
+    let row = sqlx::query("SELECT COALESCE(MAX(seq), 0) as seq FROM game_events WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .fetch_one(pool)
+        .await?;
+
+    Ok(row.get("seq"))
+}
+
+/// Logged events with a sequence number above `since_seq`, oldest first
+pub async fn get_game_events(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    since_seq: i64,
+    limit: i64,
+) -> Result<Vec<GameEventRecord>> {
+    let events = sqlx::query_as::<_, GameEventRecord>(
+        "SELECT * FROM game_events WHERE game_id = ? AND seq > ? ORDER BY seq LIMIT ?",
+    )
+    .bind(game_id.to_string())
+    .bind(since_seq)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+/// Damage dealt by each player in the game, most first: life lost by other
+/// players attributed to them plus their commander damage. Life loss with no
+/// recorded source is bucketed under a `None` player.
+pub async fn get_damage_dealt(pool: &SqlitePool, game_id: Uuid) -> Result<Vec<DamageDealt>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT source,
+               SUM(life_damage) AS life_damage,
+               SUM(commander_damage) AS commander_damage,
+               SUM(life_damage) + SUM(commander_damage) AS total
+        FROM (
+            SELECT id AS source, 0 AS life_damage, 0 AS commander_damage
+            FROM players WHERE game_id = ?
+            UNION ALL
+            SELECT source_player_id, -change_amount, 0
+            FROM life_changes
+            WHERE game_id = ? AND change_amount < 0
+              AND (source_player_id IS NULL OR source_player_id != player_id)
+            UNION ALL
+            SELECT from_player_id, 0, damage
+            FROM commander_damage
+            WHERE game_id = ? AND active = 1 AND from_player_id != to_player_id
+        )
+        GROUP BY source
+        ORDER BY total DESC, source IS NULL, source
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(game_id.to_string())
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(DamageDealt {
+                player_id: optional_uuid_column(row, "source")?,
+                life_damage: row.try_get("life_damage")?,
+                commander_damage: row.try_get("commander_damage")?,
+                total: row.try_get("total")?,
+            })
+        })
+        .collect::<sqlx::Result<_>>()
+        .map_err(Into::into)
+}
+
+/// Life changes and commander damage merged into one feed, newest first, from
+/// strictly before `before` when given. Commander damage appears once per
+/// pair, at its current total and last update.
+pub async fn get_recent_activity(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    before: Option<DateTime<Utc>>,
+    limit: i64,
+) -> Result<Vec<ActivityItem>> {
+    let before = before.map(|at| at.to_rfc3339());
+
+    let life_changes = sqlx::query_as::<_, LifeChange>(
+        "SELECT * FROM life_changes WHERE game_id = ? AND (? IS NULL OR created_at < ?) ORDER BY created_at DESC LIMIT ?",
+    )
+    .bind(game_id.to_string())
+    .bind(&before)
+    .bind(&before)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    // Only real writes record who made them; rows created at join or
+    // cleared by a reset have no actor and nothing to show
+    let commander_damage = sqlx::query_as::<_, CommanderDamage>(
+        r#"
+        SELECT * FROM commander_damage
+        WHERE game_id = ? AND active = 1 AND updated_by IS NOT NULL
+          AND (? IS NULL OR updated_at < ?)
+        ORDER BY updated_at DESC LIMIT ?
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(&before)
+    .bind(&before)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let mut activity: Vec<ActivityItem> = life_changes
+        .into_iter()
+        .map(ActivityItem::LifeChange)
+        .chain(
+            commander_damage
+                .into_iter()
+                .map(ActivityItem::CommanderDamage),
+        )
+        .collect();
+    activity.sort_by_key(|item| std::cmp::Reverse(item.at()));
+    activity.truncate(limit as usize);
+    Ok(activity)
+}
+
+/// Recent activity plus the rest of the event log merged into one feed,
+/// newest first, from strictly before `before` when given. The broadcasts for
+/// life and commander damage are left out since the activity entries already
+/// cover them.
+pub async fn get_game_timeline(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    before: Option<DateTime<Utc>>,
+    limit: i64,
+) -> Result<Vec<TimelineEntry>> {
+    let activity = get_recent_activity(pool, game_id, before, limit).await?;
+    let before = before.map(|at| at.to_rfc3339());
+
+    let events = sqlx::query_as::<_, GameEventRecord>(
+        r#"
+        SELECT * FROM game_events
+        WHERE game_id = ?
+          AND json_extract(payload_json, '$.type') NOT IN ('lifeUpdate', 'commanderDamageUpdate', 'commanderDamageBulkUpdate')
+          AND (? IS NULL OR created_at < ?)
+        ORDER BY seq DESC LIMIT ?
+        "#,
+    )
+    .bind(game_id.to_string())
+    .bind(&before)
+    .bind(&before)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let mut timeline: Vec<TimelineEntry> = activity
+        .into_iter()
+        .map(|item| match item {
+            ActivityItem::LifeChange(change) => TimelineEntry::LifeChange(change),
+            ActivityItem::CommanderDamage(damage) => TimelineEntry::CommanderDamage(damage),
+        })
+        .chain(events.into_iter().map(TimelineEntry::Event))
+        .collect();
+    timeline.sort_by_key(|entry| std::cmp::Reverse(entry.at()));
+    timeline.truncate(limit as usize);
+    Ok(timeline)
+}
+
+/// What a snapshot stores: the game state, plus the commander damage the
+/// state leaves out
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotContents {
+    #[serde(flatten)]
+    state: GameState,
+    /// Commander 2 rows a partner toggle tombstoned, kept so re-enabling the
+    /// partner after a restore brings their damage back. Absent from
+    /// snapshots taken before this was stored.
+    #[serde(default)]
+    inactive_commander_damage: Vec<CommanderDamage>,
+}
+
+pub async fn create_game_snapshot(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    created_by_clerk_user_id: &str,
+    audit: impl FnOnce(&GameSnapshot) -> NewAuditEntry,
+) -> Result<GameSnapshot> {
+    let snapshot = GameSnapshot {
+        id: Uuid::new_v4(),
+        game_id,
+        created_by_clerk_user_id: created_by_clerk_user_id.to_string(),
+        state: get_game_state_with_users(pool, game_id).await?,
+        created_at: Utc::now(),
+    };
+    let inactive_commander_damage = sqlx::query_as::<_, CommanderDamage>(
+        "SELECT * FROM commander_damage WHERE game_id = ? AND active = 0",
+    )
+    .bind(game_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let state_json = serde_json::to_string(&SnapshotContents {
+        state: snapshot.state.clone(),
+        inactive_commander_damage,
+    })
+    .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let mut tx = begin_write(pool).await?;
+    sqlx::query(
+        "INSERT INTO game_snapshots (id, game_id, created_by_clerk_user_id, state_json, created_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(snapshot.id.to_string())
+    .bind(snapshot.game_id.to_string())
+    .bind(&snapshot.created_by_clerk_user_id)
+    .bind(state_json)
+    .bind(snapshot.created_at.to_rfc3339())
+    .execute(&mut *tx)
+    .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit(&snapshot)).await?;
+    tx.commit().await?;
+    Ok(snapshot)
+}
+
+/// Put an active game back to how it started, keeping the roster and seats:
+/// everyone is back on their starting life and in the game, commander damage
+/// and counters are zeroed and the life history is cleared.
+pub async fn reset_game(pool: &SqlitePool, game_id: Uuid, audit: NewAuditEntry) -> Result<()> {
+    let mut tx = begin_write(pool).await?;
+
+    let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let now = Utc::now().to_rfc3339();
+
+    sqlx::query(
+        r#"
+        UPDATE players
+        SET current_life = starting_life, elimination_cause = NULL, commander_casts = 0,
+            mulligans = 0, hand_size = ?, version = version + 1
+        WHERE game_id = ?
+        "#,
+    )
+    .bind(DEFAULT_HAND_SIZE)
+    .bind(game_id.to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE commander_damage SET damage = 0, updated_at = ?, updated_by = NULL WHERE game_id = ?",
+    )
+        .bind(&now)
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE game_counters SET value = 0, updated_at = ? WHERE game_id = ?")
+        .bind(&now)
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM life_changes WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Rewrite an active game's players, commander damage, decks and counters to
+/// match a snapshot. Players who joined since are removed, along with their
+/// rows, and players who left are re-seated. Player versions keep increasing
+/// so stale clients are still detected.
+pub async fn restore_game_snapshot(
+    pool: &SqlitePool,
+    game_id: Uuid,
+    snapshot_id: Uuid,
+    audit: NewAuditEntry,
+) -> Result<()> {
+    let mut tx = begin_write(pool).await?;
+
+    let game = get_game_by_id_in_tx(&mut tx, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let row = sqlx::query("SELECT state_json FROM game_snapshots WHERE id = ? AND game_id = ?")
+        .bind(snapshot_id.to_string())
+        .bind(game_id.to_string())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::SnapshotNotFound)?;
+    let SnapshotContents {
+        state,
+        inactive_commander_damage,
+    } = serde_json::from_str(&row.try_get::<String, _>("state_json")?)
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    // Commander damage references players, so clear it before reshaping the roster
+    sqlx::query("DELETE FROM commander_damage WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    // Removed players' life changes and decks go with them through ON DELETE CASCADE
+    let current_ids: Vec<String> = sqlx::query("SELECT id FROM players WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|row| row.get("id"))
+        .collect();
+    for id in &current_ids {
+        if !state.players.iter().any(|p| &p.id.to_string() == id) {
+            sqlx::query("DELETE FROM players WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    // Move everyone out of the way first so reassigned seats and colors can't collide
+    sqlx::query("UPDATE players SET position = -position, seat_color = NULL WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    for player in &state.players {
+        let updated = sqlx::query(
+            r#"
+            UPDATE players
+            SET current_life = ?, position = ?, commander_name = ?, partner_name = ?,
+                commander_casts = ?, elimination_cause = ?, mulligans = ?, hand_size = ?,
+                seat_color = ?, has_partner = ?, version = version + 1
+            WHERE id = ? AND game_id = ?
+            "#,
+        )
+        .bind(player.current_life)
+        .bind(player.position)
+        .bind(&player.commander_name)
+        .bind(&player.partner_name)
+        .bind(player.commander_casts)
+        .bind(&player.elimination_cause)
+        .bind(player.mulligans)
+        .bind(player.hand_size)
+        .bind(&player.seat_color)
+        .bind(player.has_partner)
+        .bind(player.id.to_string())
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            sqlx::query(
+                "INSERT INTO players (id, game_id, clerk_user_id, current_life, position, commander_name, partner_name, commander_casts, elimination_cause, mulligans, hand_size, starting_life, seat_color, has_partner, version) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(player.id.to_string())
+            .bind(game_id.to_string())
+            .bind(&player.clerk_user_id)
+            .bind(player.current_life)
+            .bind(player.position)
+            .bind(&player.commander_name)
+            .bind(&player.partner_name)
+            .bind(player.commander_casts)
+            .bind(&player.elimination_cause)
+            .bind(player.mulligans)
+            .bind(player.hand_size)
+            // Snapshots taken before per-player starting life fall back to the game's
+            .bind(if player.starting_life > 0 {
+                player.starting_life
+            } else {
+                state.game.starting_life
+            })
+            .bind(&player.seat_color)
+            .bind(player.has_partner)
+            .bind(player.version + 1)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    let damage_rows = state
+        .commander_damage
+        .iter()
+        .map(|damage| (damage, true))
+        .chain(
+            inactive_commander_damage
+                .iter()
+                .map(|damage| (damage, false)),
+        );
+    for (damage, active) in damage_rows {
+        sqlx::query(
+            "INSERT INTO commander_damage (id, game_id, from_player_id, to_player_id, commander_number, damage, created_at, updated_at, updated_by, active) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(damage.id.to_string())
+        .bind(game_id.to_string())
+        .bind(damage.from_player_id.to_string())
+        .bind(damage.to_player_id.to_string())
+        .bind(damage.commander_number)
+        .bind(damage.damage)
+        .bind(damage.created_at.to_rfc3339())
+        .bind(damage.updated_at.to_rfc3339())
+        .bind(&damage.updated_by)
+        .bind(active)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query("DELETE FROM player_decks WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+    for deck in &state.decks {
+        sqlx::query(
+            "INSERT INTO player_decks (player_id, game_id, commander_name, partner_name, color_identity, decklist_url, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(deck.player_id.to_string())
+        .bind(game_id.to_string())
+        .bind(&deck.commander_name)
+        .bind(&deck.partner_name)
+        .bind(&deck.color_identity)
+        .bind(&deck.decklist_url)
+        .bind(deck.updated_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query("DELETE FROM game_counters WHERE game_id = ?")
+        .bind(game_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+    for counter in &state.game_counters {
+        sqlx::query(
+            "INSERT INTO game_counters (game_id, name, value, reset_each_turn, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(game_id.to_string())
+        .bind(&counter.name)
+        .bind(counter.value)
+        .bind(counter.reset_each_turn)
+        .bind(counter.updated_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    record_audit_entry_in_tx(&mut tx, game_id, audit).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn commander_names_are_trimmed_and_normalized() {
+        assert_eq!(normalize_commander_name(Some("   ".into())).unwrap(), None);
+        assert_eq!(
+            normalize_commander_name(Some("  Atraxa ".into())).unwrap(),
+            Some("Atraxa".to_string())
+        );
+        // "é" as e + combining acute composes to the single code point
+        assert_eq!(
+            normalize_commander_name(Some("E\u{301}owyn, Lady of Rohan".into())).unwrap(),
+            Some("\u{c9}owyn, Lady of Rohan".to_string())
+        );
+        assert!(matches!(
+            normalize_commander_name(Some("Atraxa\u{0}".into())),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn counter_names_reject_blank_and_control_characters() {
+        assert!(validate_counter_name(" \t ").is_err());
+        assert!(validate_counter_name("Storm\ncount").is_err());
+        assert!(validate_counter_name("").is_err());
+    }
+
+    #[test]
+    fn tracked_totals_stop_at_the_bounds() {
+        assert_eq!(
+            checked_total("Life", MAX_TRACKED_VALUE - 1, 1).unwrap(),
+            MAX_TRACKED_VALUE
+        );
+        assert_eq!(
+            checked_total("Life", -MAX_TRACKED_VALUE + 1, -1).unwrap(),
+            -MAX_TRACKED_VALUE
+        );
+        assert!(checked_total("Life", MAX_TRACKED_VALUE, 1).is_err());
+        assert!(checked_total("Life", -MAX_TRACKED_VALUE, -1).is_err());
+        assert!(checked_total("Life", i32::MAX, 1).is_err());
+        assert!(checked_total("Life", i32::MIN, -1).is_err());
+    }
+
+    #[tokio::test]
+    async fn out_of_range_values_leave_the_stored_ones_alone() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let life_audit = |_: &Player, _: &LifeChange| test_support::audit("host", "life");
+
+        set_player_life(
+            &pool,
+            game.id,
+            host.id,
+            MAX_TRACKED_VALUE,
+            None,
+            None,
+            None,
+            None,
+            "host",
+            None,
+            life_audit,
+        )
+        .await
+        .unwrap();
+        let over = update_player_life(
+            &pool, game.id, host.id, 1, None, None, "host", None, life_audit,
+        );
+        assert!(matches!(over.await, Err(ApiError::BadRequest(_))));
+        let too_high = set_player_life(
+            &pool,
+            game.id,
+            host.id,
+            MAX_TRACKED_VALUE + 1,
+            None,
+            None,
+            None,
+            None,
+            "host",
+            None,
+            life_audit,
+        );
+        assert!(matches!(too_high.await, Err(ApiError::BadRequest(_))));
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        assert_eq!(host.current_life, MAX_TRACKED_VALUE);
+
+        let counter_audit = |_: &GameCounter| test_support::audit("host", "counter");
+        set_game_counter(
+            &pool,
+            game.id,
+            "storm",
+            MAX_TRACKED_VALUE,
+            None,
+            counter_audit,
+        )
+        .await
+        .unwrap();
+        let over = increment_game_counter(&pool, game.id, "storm", 1, counter_audit);
+        assert!(matches!(over.await, Err(ApiError::BadRequest(_))));
+        let counters = get_game_counters(&pool, game.id).await.unwrap();
+        assert_eq!(counters[0].value, MAX_TRACKED_VALUE);
+
+        let damage = |amount: i32| {
+            update_commander_damage(
+                &pool,
+                game.id,
+                host.id,
+                guest.id,
+                1,
+                amount,
+                None,
+                "host",
+                test_support::audit("host", "commander_damage"),
+            )
+        };
+        damage(999).await.unwrap();
+        assert!(matches!(damage(1000).await, Err(ApiError::BadRequest(_))));
+        assert!(matches!(damage(-1).await, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn near_duplicate_counter_names_share_a_counter() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+
+        let audit = |_: &GameCounter| test_support::audit("host", "set_game_counter");
+        set_game_counter(&pool, game.id, "Cafe\u{301}", 1, None, audit)
+            .await
+            .unwrap();
+        set_game_counter(&pool, game.id, " Caf\u{e9} ", 2, None, audit)
+            .await
+            .unwrap();
+
+        let counters = get_game_counters(&pool, game.id).await.unwrap();
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters[0].name, "Caf\u{e9}");
+        assert_eq!(counters[0].value, 2);
+    }
+
+    #[tokio::test]
+    async fn leaderboard_keeps_rating_order_with_bounded_lookups() {
+        let pool = test_support::pool().await;
+        let count = CLERK_LOOKUP_CONCURRENCY * 3;
+        for i in 0..count {
+            sqlx::query(
+                "INSERT INTO player_ratings (clerk_user_id, rating, games_played, updated_at) VALUES (?, ?, 1, ?)",
+            )
+            .bind(format!("user_{i:03}"))
+            .bind(1000.0 + i as f64)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let leaderboard = get_leaderboard(&pool, count as i64).await.unwrap();
+        assert_eq!(leaderboard.len(), count);
+        for (index, entry) in leaderboard.iter().enumerate() {
+            assert_eq!(entry.rank, index + 1);
+            assert_eq!(
+                entry.clerk_user_id,
+                format!("user_{:03}", count - 1 - index)
+            );
+            assert!(!entry.display_name.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn leaving_leaves_no_orphans() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let player = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        update_player_life(
+            &pool,
+            game.id,
+            player.id,
+            -3,
+            None,
+            None,
+            "user_1",
+            None,
+            |_, _| test_support::audit("user_1", "life_update"),
+        )
+        .await
+        .unwrap();
+        set_player_deck(
+            &pool,
+            game.id,
+            player.id,
+            SetPlayerDeckRequest {
+                commander_name: "Atraxa".to_string(),
+                partner_name: None,
+                color_identity: None,
+                decklist_url: None,
+            },
+            |_| test_support::audit("user_1", "set_player_deck"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            leave_game(&pool, game.id, "user_1", |_| test_support::audit(
+                "user_1", "leave"
+            ))
+            .await
+            .unwrap(),
+            Some(player.id)
+        );
+
+        for sql in [
+            "SELECT COUNT(*) FROM life_changes WHERE player_id = ?1",
+            "SELECT COUNT(*) FROM commander_damage WHERE from_player_id = ?1 OR to_player_id = ?1",
+            "SELECT COUNT(*) FROM player_decks WHERE player_id = ?1",
+        ] {
+            let orphans: i64 = sqlx::query_scalar(sql)
+                .bind(player.id.to_string())
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(orphans, 0, "{sql}");
+        }
+    }
+
+    #[tokio::test]
+    async fn a_change_the_floor_absorbs_writes_nothing_but_can_be_lethal() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        sqlx::query("UPDATE players SET current_life = 5 WHERE id = ?")
+            .bind(host.id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let update = |change_amount: i32| {
+            update_player_life(
+                &pool,
+                game.id,
+                host.id,
+                change_amount,
+                None,
+                None,
+                "host",
+                Some(5),
+                |_, _| test_support::audit("host", "life_update"),
+            )
+        };
+
+        let absorbed = update(-3).await.unwrap();
+        assert!(absorbed.life_change.is_none());
+        assert!(!absorbed.lethal);
+        let lethal = update(-10).await.unwrap();
+        assert!(lethal.life_change.is_none());
+        assert!(lethal.lethal);
+
+        assert_eq!(lethal.player.current_life, 5);
+        assert_eq!(lethal.player.version, host.version);
+        assert!(
+            get_life_changes_for_game(&pool, game.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn only_active_games_can_be_restored() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let snapshot = create_game_snapshot(&pool, game.id, "host", |_| {
+            test_support::audit("host", "snapshot")
+        })
+        .await
+        .unwrap();
+        end_game(
+            &pool,
+            game.id,
+            None,
+            32.0,
+            test_support::audit("host", "end_game"),
+        )
+        .await
+        .unwrap();
+
+        let result = restore_game_snapshot(
+            &pool,
+            game.id,
+            snapshot.id,
+            test_support::audit("host", "restore_snapshot"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::GameNotActive)));
+    }
+
+    #[tokio::test]
+    async fn restoring_brings_back_decks_and_drops_later_players_rows() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let set_deck = |player_id: Uuid, commander_name: &str| {
+            set_player_deck(
+                &pool,
+                game.id,
+                player_id,
+                SetPlayerDeckRequest {
+                    commander_name: commander_name.to_string(),
+                    partner_name: None,
+                    color_identity: None,
+                    decklist_url: None,
+                },
+                |_| test_support::audit("host", "set_player_deck"),
+            )
+        };
+        set_deck(host.id, "Atraxa").await.unwrap();
+        let snapshot = create_game_snapshot(&pool, game.id, "host", |_| {
+            test_support::audit("host", "snapshot")
+        })
+        .await
+        .unwrap();
+
+        set_deck(host.id, "Edgar Markov").await.unwrap();
+        let late = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        set_deck(late.id, "Krenko").await.unwrap();
+        update_player_life(
+            &pool,
+            game.id,
+            late.id,
+            -2,
+            None,
+            None,
+            "user_1",
+            None,
+            |_, _| test_support::audit("user_1", "life_update"),
+        )
+        .await
+        .unwrap();
+
+        restore_game_snapshot(
+            &pool,
+            game.id,
+            snapshot.id,
+            test_support::audit("host", "restore_snapshot"),
+        )
+        .await
+        .unwrap();
+
+        let decks = get_player_decks_for_game(&pool, game.id).await.unwrap();
+        assert_eq!(decks.len(), 1);
+        assert_eq!(decks[0].player_id, host.id);
+        assert_eq!(decks[0].commander_name, "Atraxa");
+        for sql in [
+            "SELECT COUNT(*) FROM life_changes WHERE player_id = ?1",
+            "SELECT COUNT(*) FROM commander_damage WHERE from_player_id = ?1 OR to_player_id = ?1",
+            "SELECT COUNT(*) FROM player_decks WHERE player_id = ?1",
+        ] {
+            let orphans: i64 = sqlx::query_scalar(sql)
+                .bind(late.id.to_string())
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(orphans, 0, "{sql}");
+        }
+    }
+
+    #[tokio::test]
+    async fn restoring_keeps_tombstoned_partner_damage() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let toggle = |enable: bool| {
+            toggle_partner(
+                &pool,
+                game.id,
+                host.id,
+                enable,
+                test_support::audit("host", "toggle_partner"),
+            )
+        };
+        toggle(true).await.unwrap();
+        update_commander_damage(
+            &pool,
+            game.id,
+            host.id,
+            guest.id,
+            2,
+            5,
+            None,
+            "host",
+            test_support::audit("host", "commander_damage"),
+        )
+        .await
+        .unwrap();
+        toggle(false).await.unwrap();
+        let snapshot = create_game_snapshot(&pool, game.id, "host", |_| {
+            test_support::audit("host", "snapshot")
+        })
+        .await
+        .unwrap();
+
+        restore_game_snapshot(
+            &pool,
+            game.id,
+            snapshot.id,
+            test_support::audit("host", "restore_snapshot"),
+        )
+        .await
+        .unwrap();
+
+        let restored = toggle(true).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].from_player_id, host.id);
+        assert_eq!(restored[0].new_damage, 5);
+    }
+
+    #[tokio::test]
+    async fn deleting_a_game_leaves_no_orphans() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let player = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        update_player_life(
+            &pool,
+            game.id,
+            player.id,
+            -3,
+            None,
+            None,
+            "user_1",
+            None,
+            |_, _| test_support::audit("user_1", "life_update"),
+        )
+        .await
+        .unwrap();
+        set_player_deck(
+            &pool,
+            game.id,
+            player.id,
+            SetPlayerDeckRequest {
+                commander_name: "Atraxa".to_string(),
+                partner_name: None,
+                color_identity: None,
+                decklist_url: None,
+            },
+            |_| test_support::audit("user_1", "set_player_deck"),
+        )
+        .await
+        .unwrap();
+        set_game_counter(&pool, game.id, "storm", 2, None, |_| {
+            test_support::audit("host", "set_counter")
+        })
+        .await
+        .unwrap();
+        create_game_snapshot(&pool, game.id, "host", |_| {
+            test_support::audit("host", "snapshot")
+        })
+        .await
+        .unwrap();
+        append_game_event(
+            &pool,
+            game.id,
+            &WebSocketMessage::PlayerLeft {
+                game_id: game.id,
+                player_id: player.id,
+            },
+        )
+        .await
+        .unwrap();
+
+        let tables = [
+            "players",
+            "life_changes",
+            "commander_damage",
+            "player_decks",
+            "game_counters",
+            "game_snapshots",
+            "game_events",
+            "audit_log",
+        ];
+        let count = |table: &str| {
+            let pool = pool.clone();
+            let sql = format!("SELECT COUNT(*) FROM {table} WHERE game_id = ?");
+            async move {
+                sqlx::query_scalar::<_, i64>(&sql)
+                    .bind(game.id.to_string())
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+        for table in tables {
+            assert!(count(table).await > 0, "{table} starts empty");
+        }
+
+        sqlx::query("DELETE FROM games WHERE id = ?")
+            .bind(game.id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for table in tables {
+            assert_eq!(count(table).await, 0, "{table}");
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_joins_get_distinct_seats() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+
+        let users = ["user_1", "user_2", "user_3", "user_1"];
+        let joins = users.map(|user| {
+            let pool = pool.clone();
+            // Room for more open games, so the duplicate reaches the seat checks
+            tokio::spawn(async move {
+                join_game(&pool, game.id, user, Default::default(), 5, |_| {
+                    test_support::audit(user, "join")
+                })
+                .await
+            })
+        });
+        let mut joined = Vec::new();
+        let mut conflicts = 0;
+        for join in joins {
+            match join.await.unwrap() {
+                Ok(player) => joined.push(player),
+                Err(ApiError::Conflict(message)) => {
+                    assert_eq!(message, "User already in game");
+                    conflicts += 1;
+                }
+                Err(e) => panic!("unexpected join error: {e:?}"),
+            }
+        }
+        assert_eq!(joined.len(), 3);
+        assert_eq!(conflicts, 1);
+
+        let mut positions: Vec<i32> = get_players_in_game(&pool, game.id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|player| player.position)
+            .collect();
+        positions.sort();
+        assert_eq!(positions, vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn join_conflicts_name_the_index_that_was_hit() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let player = join_game(
+            &pool,
+            game.id,
+            "user_1",
+            JoinGameRequest {
+                seat_color: Some("red".to_string()),
+                ..Default::default()
+            },
+            1,
+            |_| test_support::audit("user_1", "join"),
+        )
+        .await
+        .unwrap();
+
+        let insert = |user: &'static str, position: i32, seat_color: Option<&'static str>| {
+            sqlx::query(
+                "INSERT INTO players (id, game_id, clerk_user_id, current_life, position, seat_color) VALUES (?, ?, ?, 40, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(game.id.to_string())
+            .bind(user)
+            .bind(position)
+            .bind(seat_color)
+            .execute(&pool)
+        };
+
+        let e = insert("user_1", 9, None).await.unwrap_err();
+        assert!(
+            matches!(join_conflict(e, None), ApiError::Conflict(m) if m == "User already in game")
+        );
+
+        let e = insert("user_2", player.position, None).await.unwrap_err();
+        assert!(
+            matches!(join_conflict(e, None), ApiError::Conflict(m) if m.contains("seat was just taken"))
+        );
+
+        let e = insert("user_2", 9, Some("red")).await.unwrap_err();
+        assert!(
+            matches!(join_conflict(e, Some("red")), ApiError::BadRequest(m) if m.contains("red"))
+        );
+    }
+
+    #[tokio::test]
+    async fn rows_read_back_as_written() {
+        fn json(value: &impl serde::Serialize) -> serde_json::Value {
+            serde_json::to_value(value).unwrap()
+        }
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        assert_eq!(
+            json(&get_game_by_id(&pool, game.id).await.unwrap()),
+            json(&game)
+        );
+
+        let player = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let read = get_player_by_user(&pool, game.id, "user_1").await.unwrap();
+        assert_eq!(json(&read), json(&player));
+
+        let source = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let applied = update_player_life(
+            &pool,
+            game.id,
+            player.id,
+            -4,
+            Some(source.id),
+            Some("combat"),
+            "host",
+            None,
+            |_, _| test_support::audit("host", "life_update"),
+        )
+        .await
+        .unwrap();
+        let changes = get_life_changes_for_game(&pool, game.id).await.unwrap();
+        assert_eq!(json(&changes), json(&[applied.life_change.unwrap()]));
+
+        let damage = update_commander_damage(
+            &pool,
+            game.id,
+            source.id,
+            player.id,
+            1,
+            6,
+            None,
+            "host",
+            test_support::audit("host", "commander_damage"),
+        )
+        .await
+        .unwrap();
+        let stored = get_commander_damage_for_game(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|cd| cd.id == damage.id)
+            .unwrap();
+        assert_eq!(json(&stored), json(&damage));
+    }
+
+    #[tokio::test]
+    async fn malformed_ids_are_errors_not_panics() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        sqlx::query("UPDATE players SET id = 'not-a-uuid' WHERE game_id = ?")
+            .bind(game.id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            get_players_in_game(&pool, game.id).await,
+            Err(ApiError::Database(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn malformed_timestamps_are_errors_not_panics() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        set_game_counter(&pool, game.id, "storm", 1, None, |_| {
+            test_support::audit("host", "set_counter")
+        })
+        .await
+        .unwrap();
+        for sql in [
+            "UPDATE game_counters SET updated_at = 'yesterday' WHERE game_id = ?",
+            "UPDATE audit_log SET created_at = 'yesterday' WHERE game_id = ?",
+        ] {
+            sqlx::query(sql)
+                .bind(game.id.to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            get_game_counters(&pool, game.id).await,
+            Err(ApiError::Database(_))
+        ));
+        assert!(matches!(
+            get_audit_log(&pool, game.id).await,
+            Err(ApiError::Database(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn partner_rows_stay_reciprocal_under_rapid_toggles() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        for user in ["user_1", "user_2", "user_3"] {
+            join_game(&pool, game.id, user, Default::default(), 1, |_| {
+                test_support::audit(user, "join")
+            })
+            .await
+            .unwrap();
+        }
+        let players = get_players_in_game(&pool, game.id).await.unwrap();
+
+        let toggles: Vec<_> = (0..40)
+            .map(|i| {
+                let pool = pool.clone();
+                let player_id = players[i % players.len()].id;
+                let enable = i % 3 != 0;
+                tokio::spawn(async move {
+                    toggle_partner(
+                        &pool,
+                        game.id,
+                        player_id,
+                        enable,
+                        test_support::audit("host", "toggle_partner"),
+                    )
+                    .await
+                })
+            })
+            .collect();
+        for toggle in toggles {
+            toggle.await.unwrap().unwrap();
+        }
+
+        let players = get_players_in_game(&pool, game.id).await.unwrap();
+        let active: HashSet<(Uuid, Uuid)> = get_commander_damage_for_game(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|cd| cd.commander_number == 2)
+            .map(|cd| (cd.from_player_id, cd.to_player_id))
+            .collect();
+        for from in &players {
+            for to in players.iter().filter(|p| p.id != from.id) {
+                let expected = from.has_partner || to.has_partner;
+                assert_eq!(active.contains(&(from.id, to.id)), expected);
+                assert_eq!(active.contains(&(to.id, from.id)), expected);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn commander_two_damage_needs_a_partner() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let partner_not_enabled = |result: Result<_>| matches!(result, Err(ApiError::BadRequest(m)) if m.ends_with("partner not enabled"));
+        let set = |damage: i32| {
+            update_commander_damage(
+                &pool,
+                game.id,
+                host.id,
+                guest.id,
+                2,
+                damage,
+                None,
+                "host",
+                test_support::audit("host", "commander_damage"),
+            )
+        };
+        let entries = [UpdateCommanderDamageRequest {
+            from_player_id: host.id,
+            to_player_id: guest.id,
+            commander_number: 2,
+            damage_amount: 1,
+        }];
+        let batch = || {
+            apply_commander_damage_batch(&pool, game.id, &entries, "host", |_| {
+                test_support::audit("host", "bulk_commander_damage")
+            })
+        };
+        let toggle = |enable: bool| {
+            toggle_partner(
+                &pool,
+                game.id,
+                host.id,
+                enable,
+                test_support::audit("host", "toggle_partner"),
+            )
+        };
+
+        assert!(partner_not_enabled(set(3).await.map(|_| ())));
+        assert!(partner_not_enabled(batch().await.map(|_| ())));
+
+        toggle(true).await.unwrap();
+        set(3).await.unwrap();
+        assert_eq!(batch().await.unwrap()[0].damage, 4);
+
+        toggle(false).await.unwrap();
+        assert!(partner_not_enabled(set(5).await.map(|_| ())));
+        assert!(partner_not_enabled(batch().await.map(|_| ())));
+
+        // Only the pair of rows the toggle created, nothing orphaned
+        let rows: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM commander_damage WHERE game_id = ? AND commander_number = 2",
+        )
+        .bind(game.id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(rows, 2);
+    }
+
+    #[tokio::test]
+    async fn commander_two_damage_needs_the_source_to_have_a_partner() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        for player_id in [host.id, guest.id] {
+            toggle_partner(
+                &pool,
+                game.id,
+                player_id,
+                true,
+                test_support::audit("host", "toggle_partner"),
+            )
+            .await
+            .unwrap();
+        }
+        toggle_partner(
+            &pool,
+            game.id,
+            host.id,
+            false,
+            test_support::audit("host", "toggle_partner"),
+        )
+        .await
+        .unwrap();
+
+        // The guest still has a partner, so the pair's rows stay active,
+        // but only the guest's second commander can deal damage
+        let partner_not_enabled = |result: Result<_>| matches!(result, Err(ApiError::BadRequest(m)) if m.ends_with("partner not enabled"));
+        let set = |from: Uuid, to: Uuid| {
+            update_commander_damage(
+                &pool,
+                game.id,
+                from,
+                to,
+                2,
+                3,
+                None,
+                "host",
+                test_support::audit("host", "commander_damage"),
+            )
+        };
+        let batch = |from: Uuid, to: Uuid| {
+            let entries = vec![UpdateCommanderDamageRequest {
+                from_player_id: from,
+                to_player_id: to,
+                commander_number: 2,
+                damage_amount: 1,
+            }];
+            let pool = pool.clone();
+            async move {
+                apply_commander_damage_batch(&pool, game.id, &entries, "host", |_| {
+                    test_support::audit("host", "bulk_commander_damage")
+                })
+                .await
+            }
+        };
+
+        assert!(partner_not_enabled(
+            set(host.id, guest.id).await.map(|_| ())
+        ));
+        assert!(partner_not_enabled(
+            batch(host.id, guest.id).await.map(|_| ())
+        ));
+        set(guest.id, host.id).await.unwrap();
+        assert_eq!(batch(guest.id, host.id).await.unwrap()[0].damage, 4);
+    }
+
+    #[tokio::test]
+    async fn toggling_repairs_one_sided_partner_rows() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let toggle = |player_id: Uuid, enable: bool| {
+            toggle_partner(
+                &pool,
+                game.id,
+                player_id,
+                enable,
+                test_support::audit("host", "toggle_partner"),
+            )
+        };
+        toggle(guest.id, true).await.unwrap();
+
+        // Break the pair behind the API's back
+        sqlx::query(
+            "UPDATE commander_damage SET active = 0 WHERE game_id = ? AND commander_number = 2 AND from_player_id = ?",
+        )
+        .bind(game.id.to_string())
+        .bind(guest.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Any toggle in the game puts the missing side back
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        toggle(host.id, false).await.unwrap();
+        let partner_rows = get_commander_damage_for_game(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|cd| cd.commander_number == 2)
+            .count();
+        assert_eq!(partner_rows, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_life_updates_on_a_file_database_all_land() {
+        let path = std::env::temp_dir().join(format!("conclave-test-{}.db", Uuid::new_v4()));
+        let pool = connect(&format!("sqlite://{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        let game = test_support::create_game(&pool, "host").await;
+        let player = get_player_by_user(&pool, game.id, "host").await.unwrap();
+
+        let updates: Vec<_> = (0..30)
+            .map(|_| {
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    update_player_life(
+                        &pool,
+                        game.id,
+                        player.id,
+                        -1,
+                        None,
+                        None,
+                        "host",
+                        None,
+                        |_, _| test_support::audit("host", "life_update"),
+                    )
+                    .await
+                })
+            })
+            .collect();
+        for update in updates {
+            update.await.unwrap().unwrap();
+        }
+
+        let player = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        assert_eq!(player.current_life, 10);
+        assert_eq!(
+            get_life_changes_for_game(&pool, game.id)
+                .await
+                .unwrap()
+                .len(),
+            30
+        );
+
+        pool.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[tokio::test]
+    async fn joining_after_a_seat_gap_takes_the_next_seat() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let join = |user: &'static str| {
+            join_game(&pool, game.id, user, Default::default(), 1, move |_| {
+                test_support::audit(user, "join")
+            })
+        };
+        let middle = join("user_1").await.unwrap();
+        join("user_2").await.unwrap();
+
+        // A row removed without shifting the seats behind it leaves a gap
+        sqlx::query("DELETE FROM players WHERE id = ?")
+            .bind(middle.id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let fourth = join("user_3").await.unwrap();
+        assert_eq!(fourth.position, 4);
+    }
+
+    #[tokio::test]
+    async fn leaving_twice_shifts_seats_once() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let join = |user: &'static str| {
+            join_game(&pool, game.id, user, Default::default(), 1, move |_| {
+                test_support::audit(user, "join")
+            })
+        };
+        let leaver = join("user_1").await.unwrap();
+        let behind = join("user_2").await.unwrap();
+        assert_eq!(behind.position, 3);
+
+        let leave = || {
+            leave_game(&pool, game.id, "user_1", |_| {
+                test_support::audit("user_1", "leave")
+            })
+        };
+        assert_eq!(leave().await.unwrap(), Some(leaver.id));
+        assert_eq!(leave().await.unwrap(), None);
+
+        let positions: Vec<(String, i32)> = get_players_in_game(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|player| (player.clerk_user_id, player.position))
+            .collect();
+        assert_eq!(
+            positions,
+            [("host".to_string(), 1), ("user_2".to_string(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn life_updates_are_scoped_to_the_game() {
+        let pool = test_support::pool().await;
+        let game_a = test_support::create_game(&pool, "host_a").await;
+        let game_b = test_support::create_game(&pool, "host_b").await;
+        let player_a = get_player_by_user(&pool, game_a.id, "host_a")
+            .await
+            .unwrap();
+
+        let result = update_player_life(
+            &pool,
+            game_b.id,
+            player_a.id,
+            -5,
+            None,
+            None,
+            "host_b",
+            None,
+            |_, _| test_support::audit("host_b", "life_update"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PlayerNotFound)));
+
+        let unchanged = get_player_by_user(&pool, game_a.id, "host_a")
+            .await
+            .unwrap();
+        assert_eq!(unchanged.current_life, player_a.current_life);
+    }
+
+    #[tokio::test]
+    async fn joins_stop_at_the_open_game_cap() {
+        const CAP: i32 = 3;
+        let pool = test_support::pool().await;
+        let join = |game_id: Uuid| {
+            join_game(&pool, game_id, "user_1", Default::default(), CAP, |_| {
+                test_support::audit("user_1", "join")
+            })
+        };
+
+        let mut joined = Vec::new();
+        for host in ["host_1", "host_2", "host_3"] {
+            let game = test_support::create_game(&pool, host).await;
+            join(game.id).await.unwrap();
+            joined.push(game.id);
+        }
+
+        let one_too_many = test_support::create_game(&pool, "host_4").await;
+        assert!(matches!(
+            join(one_too_many.id).await,
+            Err(ApiError::Conflict(msg)) if msg == "too many active games"
+        ));
+        // Joining a game they're already in is a conflict, cap or not
+        assert!(matches!(join(joined[0]).await, Err(ApiError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn audit_rows_commit_and_roll_back_with_their_change() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let join = |user: &'static str, color: &str| {
+            let request = JoinGameRequest {
+                seat_color: Some(color.to_string()),
+                ..Default::default()
+            };
+            join_game(&pool, game.id, user, request, 1, move |_| {
+                test_support::audit(user, "join")
+            })
+        };
+        join("user_1", "red").await.unwrap();
+        let player = join("user_2", "blue").await.unwrap();
+
+        let taken = set_seat_color(&pool, game.id, player.id, Some("red".into()), |_| {
+            test_support::audit("user_2", "set_seat_color")
+        })
+        .await;
+        assert!(matches!(taken, Err(ApiError::BadRequest(_))));
+
+        let actions: Vec<String> = get_audit_log(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(actions, ["create_game", "join", "join"]);
+    }
+
+    #[tokio::test]
+    async fn ending_a_finished_game_keeps_its_result() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let end = |winner: Uuid| {
+            end_game(
+                &pool,
+                game.id,
+                Some(winner),
+                32.0,
+                test_support::audit("host", "end_game"),
+            )
+        };
+
+        let finished = end(host.id).await.unwrap();
+        assert!(matches!(end(guest.id).await, Err(ApiError::GameNotActive)));
+
+        let game = get_game_by_id(&pool, game.id).await.unwrap();
+        assert_eq!(game.winner_player_id, Some(host.id));
+        assert_eq!(game.finished_at, finished.finished_at);
+    }
+
+    #[tokio::test]
+    async fn reset_commander_damage_stays_out_of_the_activity_feed() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let host = get_player_by_user(&pool, game.id, "host").await.unwrap();
+        let guest = join_game(&pool, game.id, "user_1", Default::default(), 1, |_| {
+            test_support::audit("user_1", "join")
+        })
+        .await
+        .unwrap();
+        let damage = |amount: i32| {
+            update_commander_damage(
+                &pool,
+                game.id,
+                host.id,
+                guest.id,
+                1,
+                amount,
+                None,
+                "host",
+                test_support::audit("host", "commander_damage"),
+            )
+        };
+
+        damage(4).await.unwrap();
+        reset_game(&pool, game.id, test_support::audit("host", "reset"))
+            .await
+            .unwrap();
+        let activity = get_recent_activity(&pool, game.id, None, 50).await.unwrap();
+        assert!(activity.is_empty());
+
+        // A write after the reset shows up again
+        damage(2).await.unwrap();
+        let activity = get_recent_activity(&pool, game.id, None, 50).await.unwrap();
+        assert_eq!(activity.len(), 1);
+        assert!(matches!(
+            &activity[0],
+            ActivityItem::CommanderDamage(entry) if entry.damage == 2
+        ));
+    }
+
+    #[tokio::test]
+    async fn force_ending_an_ended_game_leaves_no_audit_entry() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let force_end = || {
+            force_end_game(
+                &pool,
+                game.id,
+                test_support::audit("admin", "admin_end_game"),
+            )
+        };
+
+        assert!(force_end().await.unwrap());
+        assert!(!force_end().await.unwrap());
+
+        let actions: Vec<String> = get_audit_log(&pool, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(actions, ["create_game", "admin_end_game"]);
+    }
 }
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
 use serde_json::json;
@@ -17,27 +17,88 @@ pub enum ApiError {
     #[error("Player not found")]
     PlayerNotFound,
 
+    #[error("Snapshot not found")]
+    SnapshotNotFound,
+
     #[error("Game is not active")]
     GameNotActive,
 
-    #[error("User is already in an active game")]
-    UserInActiveGame,
+    /// Joining a game whose seats are all taken
+    #[error("Game is full (max {0} players)")]
+    GameFull(i32),
+
+    /// Joining a game that has finished or been cancelled
+    #[error("Cannot join {0} game")]
+    GameFinished(String),
 
     #[error("Invalid request: {0}")]
     BadRequest(String),
 
+    /// The request is well-formed but clashes with the current state
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("WebSocket error: {0}")]
     WebSocket(String),
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// Too many requests; the client may retry after this many seconds
+    #[error("Too many requests; try again in {0} seconds")]
+    RateLimited(u64),
+
     #[error("Internal server error")]
     Internal(#[from] anyhow::Error),
 }
 
+impl ApiError {
+    /// Stable machine-readable code, for clients that branch on the failure kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::Database(_) => "database_error",
+            ApiError::GameNotFound => "game_not_found",
+            ApiError::PlayerNotFound => "player_not_found",
+            ApiError::SnapshotNotFound => "snapshot_not_found",
+            ApiError::GameNotActive => "game_not_active",
+            ApiError::GameFull(_) => "game_full",
+            ApiError::GameFinished(_) => "game_finished",
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::WebSocket(_) => "websocket_error",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::RateLimited(_) => "rate_limited",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+
+    /// Message safe to show a client; server-side failure details are withheld
+    pub fn client_message(&self) -> String {
+        match self {
+            ApiError::Database(_) => "Database error occurred".to_string(),
+            ApiError::Internal(_) => "Internal server error".to_string(),
+            ApiError::BadRequest(msg)
+            | ApiError::Conflict(msg)
+            | ApiError::WebSocket(msg)
+            | ApiError::Unauthorized(msg)
+            | ApiError::Forbidden(msg) => msg.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let retry_after = match self {
+            ApiError::RateLimited(secs) => Some(secs),
+            _ => None,
+        };
+        let client_message = self.client_message();
+        let code = self.code();
         let (status, error_message) = match self {
             ApiError::Database(ref e) => {
                 tracing::error!("Database error: {:?}", e);
@@ -45,11 +106,16 @@ impl IntoResponse for ApiError {
             }
             ApiError::GameNotFound => (StatusCode::NOT_FOUND, "Game not found"),
             ApiError::PlayerNotFound => (StatusCode::NOT_FOUND, "Player not found"),
+            ApiError::SnapshotNotFound => (StatusCode::NOT_FOUND, "Snapshot not found"),
             ApiError::GameNotActive => (StatusCode::BAD_REQUEST, "Game is not active"),
-            ApiError::UserInActiveGame => (StatusCode::CONFLICT, "User is already in an active game"),
+            ApiError::GameFull(_) => (StatusCode::CONFLICT, client_message.as_str()),
+            ApiError::GameFinished(_) => (StatusCode::GONE, client_message.as_str()),
             ApiError::BadRequest(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            ApiError::Conflict(ref msg) => (StatusCode::CONFLICT, msg.as_str()),
             ApiError::WebSocket(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
             ApiError::Unauthorized(ref msg) => (StatusCode::UNAUTHORIZED, msg.as_str()),
+            ApiError::Forbidden(ref msg) => (StatusCode::FORBIDDEN, msg.as_str()),
+            ApiError::RateLimited(_) => (StatusCode::TOO_MANY_REQUESTS, client_message.as_str()),
             ApiError::Internal(ref e) => {
                 tracing::error!("Internal error: {:?}", e);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
@@ -58,10 +124,17 @@ impl IntoResponse for ApiError {
 
         let body = Json(json!({
             "error": error_message,
+            "code": code,
             "status": status.as_u16()
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::{
-    auth::AuthenticatedUser,
+    achievements,
+    auth::{self, ADMIN_ACTOR, AdminKey, AuthenticatedUser},
     database,
     errors::{ApiError, Result},
     models::*,
@@ -8,12 +9,14 @@ use crate::{
 };
 use axum::{
     Json,
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
 };
 use serde::Deserialize;
 use sqlx::Row;
-use tracing::{debug, info};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -23,35 +26,228 @@ pub struct HistoryQueryParams {
     pub include_no_winner: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CommanderDamageQueryParams {
+    /// Only return damage received by this player
+    pub to_player_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventsLogQueryParams {
+    /// Only return events after this sequence number (default 0, i.e. all)
+    pub since_seq: Option<i64>,
+    /// Page size (default and max 500)
+    pub limit: Option<i64>,
+    /// Lets someone who isn't a player read a private game's events
+    pub join_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PollQueryParams {
+    /// Return events after this sequence number (default 0, i.e. all)
+    pub since_seq: Option<i64>,
+    /// Lets someone who isn't a player follow a private game
+    pub join_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineQueryParams {
+    /// Only entries strictly before this time, for paging back (default: newest)
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Page size (default 100, max 500)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StateAtQueryParams {
+    /// The moment to rebuild the state for (RFC 3339)
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaveQueryParams {
+    /// "quit" (default) to leave the table, or "eliminate" to concede and
+    /// stay on the board in an active game
+    pub mode: Option<LeaveMode>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQueryParams {
+    /// Number of items (default 20, max 100)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQueryParams {
+    /// Number of entries (default 50, max 200)
+    pub limit: Option<i64>,
+    /// Entries to skip, for paging (default 0)
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLifeQueryParams {
+    /// Pick the player by user instead of `playerId` in the body
+    pub clerk_user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminGamesQueryParams {
+    /// "lobby", "active", "finished" or "cancelled"; all statuses when omitted
+    pub status: Option<String>,
+    /// Page size (default 50, max 200)
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Substring of a game id or a player's clerk user id
+    pub search: Option<String>,
+}
+
 // User operations are handled by Clerk, so no local user endpoints needed
 
 // Game endpoints
+/// Header letting clients retry game creation without creating duplicates
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+/// Longest a poll waits for an event before returning empty
+const LONG_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(25);
+
 pub async fn create_game(
     State(state): State<AppState>,
     auth: AuthenticatedUser,
+    headers: HeaderMap,
     Json(request): Json<CreateGameRequest>,
 ) -> Result<Json<Game>> {
+    let idempotency_key = match headers.get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => {
+            let key = value
+                .to_str()
+                .map_err(|_| ApiError::BadRequest("Invalid Idempotency-Key header".to_string()))?;
+            if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+                return Err(ApiError::BadRequest(format!(
+                    "Idempotency-Key must be 1 to {MAX_IDEMPOTENCY_KEY_LENGTH} characters"
+                )));
+            }
+            Some(key)
+        }
+        None => None,
+    };
+
+    let per_minute = state.config.game_creation_per_minute as u32;
+    if per_minute > 0
+        && auth::check_admin_key(&headers).is_err()
+        && let Err(wait) = state
+            .game_creation_limiter
+            .try_acquire(&auth.clerk_user_id, per_minute)
+    {
+        warn!(
+            "User {} is creating games too quickly; retry in {:?}",
+            auth.clerk_user_id, wait
+        );
+        return Err(ApiError::RateLimited(wait.as_secs_f64().ceil() as u64));
+    }
+
+    let format = match request.format.as_deref() {
+        Some(name) => Some(GameFormat::parse(name).ok_or_else(|| {
+            let known: Vec<&str> = GameFormat::ALL.iter().map(|f| f.as_str()).collect();
+            ApiError::BadRequest(format!(
+                "Unknown format '{}' (expected one of: {})",
+                name,
+                known.join(", ")
+            ))
+        })?),
+        None => None,
+    };
+
+    // An explicit starting life overrides the format preset
+    let starting_life = request
+        .starting_life
+        .or(format.map(GameFormat::starting_life))
+        .unwrap_or(DEFAULT_STARTING_LIFE);
+
     info!(
-        "Creating game for user {} ({}), starting life: {}",
+        "Creating game for user {} ({}), format: {:?}, starting life: {}",
         auth.clerk_user_id,
         auth.user.display_name(),
-        request.starting_life.unwrap_or(DEFAULT_STARTING_LIFE)
+        format,
+        starting_life
     );
 
-    let starting_life = request.starting_life.unwrap_or(DEFAULT_STARTING_LIFE);
+    let limits = &state.config;
+    if !(limits.min_starting_life..=limits.max_starting_life).contains(&starting_life) {
+        return Err(ApiError::BadRequest(format!(
+            "Starting life must be between {} and {}",
+            limits.min_starting_life, limits.max_starting_life
+        )));
+    }
 
-    if !(1..=999).contains(&starting_life) {
-        return Err(ApiError::BadRequest(
-            "Starting life must be between 1 and 999".to_string(),
-        ));
+    let strict_control = request.strict_control.unwrap_or(true);
+
+    let commander_lethal = request.commander_lethal.unwrap_or(COMMANDER_DAMAGE_LETHAL);
+    if !(1..=MAX_COMMANDER_LETHAL).contains(&commander_lethal) {
+        return Err(ApiError::BadRequest(format!(
+            "Commander lethal must be between 1 and {MAX_COMMANDER_LETHAL}"
+        )));
     }
 
-    let game = database::create_game(&state.db, starting_life, &auth.clerk_user_id).await?;
+    let visibility = match request.visibility.as_deref() {
+        Some(name) => GameVisibility::parse(name).ok_or_else(|| {
+            let known: Vec<&str> = GameVisibility::ALL.iter().map(|v| v.as_str()).collect();
+            ApiError::BadRequest(format!(
+                "Unknown visibility '{}' (expected one of: {})",
+                name,
+                known.join(", ")
+            ))
+        })?,
+        None => GameVisibility::Public,
+    };
+
+    let settings = NewGame {
+        starting_life,
+        format,
+        strict_control,
+        commander_lethal,
+        visibility,
+        lobby: request.lobby.unwrap_or(true),
+        auto_eliminate: request.auto_eliminate.unwrap_or(true),
+    };
+    let (game, created) = database::create_game(
+        &state.db,
+        settings,
+        &auth.clerk_user_id,
+        idempotency_key,
+        state.config.max_open_games_per_user,
+        |game| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "create_game",
+                serde_json::json!({
+                    "startingLife": starting_life,
+                    "format": game.format,
+                    "strictControl": strict_control,
+                    "commanderLethal": commander_lethal,
+                    "visibility": game.visibility,
+                    "lobby": game.status == "lobby",
+                    "autoEliminate": game.auto_eliminate,
+                }),
+            )
+        },
+    )
+    .await?;
+
+    // A retry of a request we already handled gets the original game back
+    if !created {
+        info!(
+            "Idempotency key reused by user {}, returning game {}",
+            auth.clerk_user_id, game.id
+        );
+        return Ok(Json(game));
+    }
 
     // Initialize WebSocket room for the new game
     state.get_or_create_game_room(game.id);
 
-    info!("Game created and started: {}", game.id);
+    info!("Game created ({}): {}", game.status, game.id);
     Ok(Json(game))
 }
 
@@ -59,6 +255,7 @@ pub async fn join_game(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
     auth: AuthenticatedUser,
+    body: Bytes,
 ) -> Result<Json<PlayerWithUser>> {
     info!(
         "User {} ({}) joining game {}",
@@ -67,51 +264,138 @@ pub async fn join_game(
         game_id
     );
 
-    let player = database::join_game(&state.db, game_id, &auth.clerk_user_id).await?;
+    // Clients without an override may send no body at all
+    let request: JoinGameRequest = if body.is_empty() {
+        JoinGameRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid request body: {e}")))?
+    };
 
-    // Broadcast player joined event to WebSocket clients
-    websocket::broadcast_player_joined(&state, game_id, player.clone()).await;
+    let limits = &state.config;
+    if let Some(starting_life) = request.starting_life_override
+        && !(limits.min_starting_life..=limits.max_starting_life).contains(&starting_life)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Starting life must be between {} and {}",
+            limits.min_starting_life, limits.max_starting_life
+        )));
+    }
 
-    // Return enriched player
-    let enriched_player = PlayerWithUser::from_player(
-        player.clone(),
-        auth.user.display_name(),
-        auth.user.username,
-        auth.user.image_url,
-    );
+    let player = database::join_game(
+        &state.db,
+        game_id,
+        &auth.clerk_user_id,
+        request,
+        state.config.max_open_games_per_user,
+        |player| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "join",
+                serde_json::json!({
+                    "playerId": player.id,
+                    "position": player.position,
+                    "startingLife": player.starting_life,
+                    "seatColor": player.seat_color,
+                }),
+            )
+        },
+    )
+    .await?;
 
     info!(
         "User {} successfully joined game {} as player {}",
         auth.clerk_user_id, game_id, player.position
     );
+
+    // Broadcast and return the same enriched player
+    let enriched_player =
+        websocket::broadcast_player_joined(&state, game_id, player, &auth.user).await;
     Ok(Json(enriched_player))
 }
 
 pub async fn leave_game(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
+    Query(params): Query<LeaveQueryParams>,
     auth: AuthenticatedUser,
 ) -> Result<StatusCode> {
-    info!("User {} leaving game {}", auth.clerk_user_id, game_id);
+    if params.mode == Some(LeaveMode::Eliminate) {
+        info!("User {} conceding game {}", auth.clerk_user_id, game_id);
+        let player = database::get_player_by_user(&state.db, game_id, &auth.clerk_user_id).await?;
+        websocket::concede_player(&state, game_id, player.id, &auth.clerk_user_id).await?;
+        return Ok(StatusCode::OK);
+    }
 
-    // Get player info before removing them (needed for broadcast)
-    let players = database::get_players_in_game(&state.db, game_id).await?;
-    let player = players
-        .iter()
-        .find(|p| p.clerk_user_id == auth.clerk_user_id)
-        .ok_or(ApiError::PlayerNotFound)?;
-    let player_id = player.id;
+    info!("User {} leaving game {}", auth.clerk_user_id, game_id);
+    quit_game(&state, game_id, &auth.clerk_user_id).await?;
+    Ok(StatusCode::OK)
+}
 
-    database::leave_game(&state.db, game_id, &auth.clerk_user_id).await?;
+/// Remove a user from a game and announce it. Returns `None` if they weren't
+/// in it: leaving twice is fine, e.g. a retry after a dropped response.
+async fn quit_game(state: &AppState, game_id: Uuid, clerk_user_id: &str) -> Result<Option<Uuid>> {
+    let Some(player_id) = database::leave_game(&state.db, game_id, clerk_user_id, |player_id| {
+        NewAuditEntry::new(
+            clerk_user_id,
+            "leave",
+            serde_json::json!({ "playerId": player_id }),
+        )
+    })
+    .await?
+    else {
+        info!("User {} already not in game {}", clerk_user_id, game_id);
+        return Ok(None);
+    };
 
     // Broadcast player left event to WebSocket clients
-    websocket::broadcast_player_left(&state, game_id, player_id).await;
+    websocket::broadcast_player_left(state, game_id, player_id, clerk_user_id).await;
 
+    info!("User {} successfully left game {}", clerk_user_id, game_id);
+    Ok(Some(player_id))
+}
+
+/// Quit several of the user's games at once, to free up open game slots.
+/// Requested games the user isn't in, or that are already over, are skipped.
+/// A game that fails to leave is reported and the rest are still attempted.
+pub async fn leave_games(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<LeaveGamesRequest>,
+) -> Result<Json<LeaveGamesResponse>> {
     info!(
-        "User {} successfully left game {}",
-        auth.clerk_user_id, game_id
+        "User {} leaving {} game(s)",
+        auth.clerk_user_id,
+        request.game_ids.len()
     );
-    Ok(StatusCode::OK)
+
+    let open_games: HashSet<Uuid> = database::get_user_games(&state.db, &auth.clerk_user_id)
+        .await?
+        .into_iter()
+        .filter(|g| g.game.is_open())
+        .map(|g| g.game.id)
+        .collect();
+
+    let mut left = Vec::new();
+    let mut failed = Vec::new();
+    for game_id in request.game_ids {
+        if !open_games.contains(&game_id) || left.contains(&game_id) || failed.contains(&game_id) {
+            continue;
+        }
+        match quit_game(&state, game_id, &auth.clerk_user_id).await {
+            Ok(Some(_)) => left.push(game_id),
+            Ok(None) => {}
+            Err(e) => {
+                error!(
+                    "Failed to leave game {} for user {}: {:?}",
+                    game_id, auth.clerk_user_id, e
+                );
+                failed.push(game_id);
+            }
+        }
+    }
+
+    Ok(Json(LeaveGamesResponse { left, failed }))
 }
 
 pub async fn get_game(
@@ -120,7 +404,7 @@ pub async fn get_game(
 ) -> Result<Json<Game>> {
     debug!("GET /api/v1/games/{} - Getting game details", game_id);
     let game = database::get_game_by_id(&state.db, game_id).await?;
-    Ok(Json(game))
+    Ok(Json(game.without_join_code()))
 }
 
 pub async fn get_game_state(
@@ -129,10 +413,95 @@ pub async fn get_game_state(
 ) -> Result<Json<GameState>> {
     debug!("GET /api/v1/games/{}/state - Getting game state", game_id);
     // Use enriched game state with user display info
-    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    let game_state = state.game_state(game_id).await?;
+    let mut game_state = GameState::clone(&game_state);
+    game_state.game = game_state.game.without_join_code();
+    Ok(Json(game_state))
+}
+
+/// The game state as it was at a past moment; see `database::get_game_state_at`
+/// for what can't be rebuilt
+pub async fn get_game_state_at(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<StateAtQueryParams>,
+) -> Result<Json<GameState>> {
+    debug!(
+        "GET /api/v1/games/{}/state-at - at {}",
+        game_id, params.timestamp
+    );
+    let mut game_state = database::get_game_state_at(&state.db, game_id, params.timestamp).await?;
+    game_state.game = game_state.game.without_join_code();
     Ok(Json(game_state))
 }
 
+pub async fn get_game_states(
+    State(state): State<AppState>,
+    Json(request): Json<GameStatesRequest>,
+) -> Result<Json<HashMap<Uuid, GameState>>> {
+    debug!(
+        "POST /api/v1/games/states - {} games",
+        request.game_ids.len()
+    );
+
+    let mut game_ids = request.game_ids;
+    game_ids.sort();
+    game_ids.dedup();
+    if game_ids.is_empty() || game_ids.len() > MAX_BATCH_GAME_STATES {
+        return Err(ApiError::BadRequest(format!(
+            "Between 1 and {MAX_BATCH_GAME_STATES} game ids are required"
+        )));
+    }
+
+    let states = state.game_states_for(&game_ids).await?;
+    Ok(Json(
+        states
+            .into_iter()
+            .map(|(game_id, game_state)| {
+                let mut game_state = GameState::clone(&game_state);
+                game_state.game = game_state.game.without_join_code();
+                (game_id, game_state)
+            })
+            .collect(),
+    ))
+}
+
+pub async fn get_players(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+) -> Result<Json<Vec<PlayerWithUser>>> {
+    debug!("GET /api/v1/games/{}/players - Getting players", game_id);
+
+    // Verify game exists so unknown games 404 instead of returning an empty roster
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let players = database::get_players_in_game(&state.db, game_id).await?;
+    let enriched_players = database::enrich_players_with_users(players).await;
+    Ok(Json(enriched_players))
+}
+
+pub async fn get_player(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<PlayerDetails>> {
+    debug!(
+        "GET /api/v1/games/{}/players/{} - Getting player",
+        game_id, player_id
+    );
+
+    let player = database::get_player(&state.db, game_id, player_id).await?;
+    let commander_damage_received = database::get_commander_damage_for_game(&state.db, game_id)
+        .await?
+        .into_iter()
+        .filter(|cd| cd.to_player_id == player_id)
+        .collect();
+
+    Ok(Json(PlayerDetails {
+        player: database::enrich_player_with_user(player).await,
+        commander_damage_received,
+    }))
+}
+
 pub async fn get_user_games(
     State(state): State<AppState>,
     auth: AuthenticatedUser,
@@ -163,21 +532,117 @@ pub async fn get_all_games(State(state): State<AppState>) -> Result<Json<Vec<Gam
     Ok(Json(games))
 }
 
+pub async fn admin_list_games(
+    State(state): State<AppState>,
+    Query(params): Query<AdminGamesQueryParams>,
+    _admin: AdminKey,
+) -> Result<Json<GameListPage>> {
+    debug!("GET /api/v1/admin/games - {:?}", params);
+
+    if let Some(status) = &params.status
+        && !["lobby", "active", "finished", "cancelled"].contains(&status.as_str())
+    {
+        return Err(ApiError::BadRequest(format!("Unknown status '{status}'")));
+    }
+
+    let filter = database::GameListFilter {
+        status: params.status,
+        search: params.search.filter(|s| !s.trim().is_empty()),
+        limit: params.limit.unwrap_or(50).clamp(1, 200),
+        offset: params.offset.unwrap_or(0).max(0),
+    };
+
+    let page = database::list_games(&state.db, &filter).await?;
+    Ok(Json(page))
+}
+
+pub async fn admin_metrics(
+    State(state): State<AppState>,
+    _admin: AdminKey,
+) -> Result<Json<BroadcastMetrics>> {
+    debug!("GET /api/v1/admin/metrics");
+    Ok(Json(state.broadcast_metrics()))
+}
+
+/// Break-glass end for any game: no host check, no winner, and the room is
+/// closed even if the game had already ended
+pub async fn admin_end_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _admin: AdminKey,
+) -> Result<Json<Game>> {
+    info!("Admin force-ending game {}", game_id);
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    let ended = database::force_end_game(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(
+            ADMIN_ACTOR,
+            "admin_end_game",
+            serde_json::json!({ "previousStatus": game.status }),
+        ),
+    )
+    .await?;
+
+    if ended {
+        let receivers = state
+            .broadcast_to_game(
+                game_id,
+                WebSocketMessage::GameEnded {
+                    game_id,
+                    winner: None,
+                    outcome: None,
+                },
+            )
+            .await;
+        state.schedule_room_cleanup(game_id, receivers);
+    } else {
+        // The game had already ended, so its final message went out earlier
+        state.cleanup_game_room(game_id);
+    }
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    Ok(Json(game))
+}
+
 pub async fn update_life(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
+    Query(params): Query<UpdateLifeQueryParams>,
+    auth: AuthenticatedUser,
     Json(request): Json<UpdateLifeRequest>,
 ) -> Result<Json<Player>> {
+    let player_id = match (request.player_id, params.clerk_user_id) {
+        (Some(player_id), None) => player_id,
+        (None, Some(clerk_user_id)) => {
+            database::get_player_by_user(&state.db, game_id, &clerk_user_id)
+                .await?
+                .id
+        }
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Provide either playerId or clerkUserId".to_string(),
+            ));
+        }
+    };
+
     info!(
         "Updating life for player {} in game {} by {}",
-        request.player_id, game_id, request.change_amount
+        player_id, game_id, request.change_amount
     );
 
-    if request.change_amount.abs() > 100 {
+    if request.change_amount == 0 {
         return Err(ApiError::BadRequest(
-            "Life change too large (max ±100)".to_string(),
+            "Life change must be non-zero".to_string(),
         ));
     }
+    let max_change = state.config.max_life_change;
+    if request.change_amount.abs() > max_change {
+        return Err(ApiError::BadRequest(format!(
+            "Life change too large (max ±{max_change})"
+        )));
+    }
 
     // Verify game is active
     let game = database::get_game_by_id(&state.db, game_id).await?;
@@ -185,29 +650,156 @@ pub async fn update_life(
         return Err(ApiError::GameNotActive);
     }
 
-    // Update player life
-    let (updated_player, _life_change) =
-        database::update_player_life(&state.db, request.player_id, request.change_amount).await?;
+    database::ensure_can_control_player(&state.db, &game, player_id, &auth.clerk_user_id).await?;
 
-    // Broadcast life update via WebSocket
-    let message = WebSocketMessage::LifeUpdate {
+    // Update player life
+    let applied = database::update_player_life(
+        &state.db,
         game_id,
-        player_id: request.player_id,
-        new_life: updated_player.current_life,
-        change_amount: request.change_amount,
-    };
-    state.broadcast_to_game(game_id, message);
+        player_id,
+        request.change_amount,
+        request.source_player_id,
+        request.reason.as_deref(),
+        &auth.clerk_user_id,
+        state.config.life_floor,
+        |player, life_change| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "life_update",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "changeAmount": request.change_amount,
+                    "newLife": player.current_life,
+                    "sourcePlayerId": request.source_player_id,
+                    "reason": life_change.reason,
+                }),
+            )
+        },
+    )
+    .await?;
+    let updated_player = applied.player;
+
+    // Nothing to announce when the life floor absorbed the whole change
+    if let Some(life_change) = applied.life_change {
+        websocket::broadcast_game_started_if_first_change(&state, game_id).await?;
+
+        // Broadcast life update via WebSocket
+        let message = WebSocketMessage::LifeUpdate {
+            game_id,
+            player_id,
+            new_life: updated_player.current_life,
+            change_amount: life_change.change_amount,
+            version: updated_player.version,
+            source_player_id: life_change.source_player_id,
+            reason: life_change.reason,
+            actor_clerk_user_id: Some(auth.clerk_user_id.clone()),
+        };
+        state.broadcast_to_game(game_id, message).await;
+    }
+
+    if applied.lethal {
+        websocket::eliminate_player(
+            &state,
+            game_id,
+            player_id,
+            EliminationCause::Life,
+            &auth.clerk_user_id,
+        )
+        .await?;
+    }
 
     info!(
         "Life updated for player {} in game {}: new life = {}",
-        request.player_id, game_id, updated_player.current_life
+        player_id, game_id, updated_player.current_life
+    );
+    Ok(Json(updated_player))
+}
+
+pub async fn set_life(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SetLifeRequest>,
+) -> Result<Json<Player>> {
+    info!(
+        "Setting life for player {} in game {} to {} (expected version {:?})",
+        request.player_id, game_id, request.life, request.expected_version
     );
+
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    database::ensure_can_control_player(&state.db, &game, request.player_id, &auth.clerk_user_id)
+        .await?;
+
+    let applied = database::set_player_life(
+        &state.db,
+        game_id,
+        request.player_id,
+        request.life,
+        request.expected_version,
+        request.expected_current,
+        request.source_player_id,
+        request.reason.as_deref(),
+        &auth.clerk_user_id,
+        state.config.life_floor,
+        |player, life_change| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "set_life",
+                serde_json::json!({
+                    "playerId": request.player_id,
+                    "life": request.life,
+                    "changeAmount": life_change.change_amount,
+                    "version": player.version,
+                    "sourcePlayerId": request.source_player_id,
+                    "reason": life_change.reason,
+                }),
+            )
+        },
+    )
+    .await?;
+    let updated_player = applied.player;
+
+    // Nothing to announce when the total didn't move
+    if let Some(life_change) = applied.life_change {
+        websocket::broadcast_game_started_if_first_change(&state, game_id).await?;
+
+        // Broadcast life update via WebSocket
+        let message = WebSocketMessage::LifeUpdate {
+            game_id,
+            player_id: request.player_id,
+            new_life: updated_player.current_life,
+            change_amount: life_change.change_amount,
+            version: updated_player.version,
+            source_player_id: life_change.source_player_id,
+            reason: life_change.reason,
+            actor_clerk_user_id: Some(auth.clerk_user_id.clone()),
+        };
+        state.broadcast_to_game(game_id, message).await;
+    }
+
+    if applied.lethal {
+        websocket::eliminate_player(
+            &state,
+            game_id,
+            request.player_id,
+            EliminationCause::Life,
+            &auth.clerk_user_id,
+        )
+        .await?;
+    }
+
     Ok(Json(updated_player))
 }
 
 pub async fn end_game(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
     Json(req): Json<EndGameRequest>,
 ) -> Result<Json<Game>> {
     info!(
@@ -215,7 +807,26 @@ pub async fn end_game(
         game_id, req.winner_player_id
     );
 
-    let game = database::end_game(&state.db, game_id, req.winner_player_id).await?;
+    let game = database::end_game(
+        &state.db,
+        game_id,
+        req.winner_player_id,
+        state.config.elo_k_factor.into(),
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "end_game",
+            serde_json::json!({ "winnerPlayerId": req.winner_player_id }),
+        ),
+    )
+    .await?;
+    // The game has already ended; missing badges shouldn't turn that into an error
+    if let Err(e) = achievements::award_for_game(&state.db, game_id).await {
+        error!("Failed to award achievements for game {}: {:?}", game_id, e);
+    }
+    // Only games with a winner move ratings
+    if req.winner_player_id.is_some() {
+        state.leaderboard.mark_stale();
+    }
 
     // Get the winner player if specified
     let enriched_winner = if let Some(winner_id) = req.winner_player_id {
@@ -223,7 +834,7 @@ pub async fn end_game(
         players
             .into_iter()
             .find(|p| p.id == winner_id)
-            .map(|w| database::enrich_player_with_user(w))
+            .map(database::enrich_player_with_user)
     } else {
         None
     };
@@ -239,19 +850,86 @@ pub async fn end_game(
     let message = WebSocketMessage::GameEnded {
         game_id,
         winner: enriched_winner,
+        outcome: None,
     };
-    state.broadcast_to_game(game_id, message);
+    let receivers = state.broadcast_to_game(game_id, message).await;
 
     // Clean up WebSocket room
-    tokio::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-        state.cleanup_game_room(game_id);
-    });
+    state.schedule_room_cleanup(game_id, receivers);
 
     info!("Game ended: {}", game.id);
     Ok(Json(game))
 }
 
+pub async fn delete_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Game>> {
+    info!("User {} cancelling game {}", auth.clerk_user_id, game_id);
+
+    // Verify game exists before checking host
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can delete the game".to_string(),
+        ));
+    }
+
+    let game = database::delete_game(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(&auth.clerk_user_id, "delete_game", serde_json::json!({})),
+    )
+    .await?;
+
+    let receivers = state
+        .broadcast_to_game(game_id, WebSocketMessage::GameCancelled { game_id })
+        .await;
+
+    // Clean up WebSocket room
+    state.schedule_room_cleanup(game_id, receivers);
+
+    info!("Game cancelled: {}", game.id);
+    Ok(Json(game))
+}
+
+/// Host-only: take a game out of the lobby and announce that play has begun
+pub async fn start_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Game>> {
+    info!("User {} starting game {}", auth.clerk_user_id, game_id);
+
+    // Verify game exists before checking host
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can start the game".to_string(),
+        ));
+    }
+
+    let game = database::start_game(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(&auth.clerk_user_id, "start_game", serde_json::json!({})),
+    )
+    .await?;
+
+    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    state
+        .broadcast_to_game(game_id, WebSocketMessage::GameStarted { game_state })
+        .await;
+
+    info!("Game started: {}", game.id);
+    Ok(Json(game))
+}
+
 pub async fn get_user_history(
     State(state): State<AppState>,
     Query(params): Query<HistoryQueryParams>,
@@ -271,20 +949,70 @@ pub async fn get_user_history(
     Ok(Json(history))
 }
 
-pub async fn get_user_history_with_pod(
+pub async fn get_my_rating(
     State(state): State<AppState>,
-    Path(pod_filter): Path<String>, // comma-separated clerk_user_ids
-    Query(params): Query<HistoryQueryParams>,
     auth: AuthenticatedUser,
-) -> Result<Json<GameHistory>> {
+) -> Result<Json<PlayerRating>> {
     debug!(
-        "GET /api/v1/users/me/history/pod/{} - Getting pod game history for user {} (include_no_winner: {})",
-        pod_filter, auth.clerk_user_id, params.include_no_winner
+        "GET /api/v1/users/me/rating - Getting rating for user {}",
+        auth.clerk_user_id
     );
+    let rating = database::get_player_rating(&state.db, &auth.clerk_user_id).await?;
+    Ok(Json(rating))
+}
 
-    // Parse the comma-separated user IDs
-    let pod_user_ids: Vec<String> = pod_filter
-        .split(',')
+pub async fn get_my_achievements(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<UserAchievement>>> {
+    debug!(
+        "GET /api/v1/users/me/achievements - Getting achievements for user {}",
+        auth.clerk_user_id
+    );
+    let achievements = database::get_user_achievements(&state.db, &auth.clerk_user_id).await?;
+    Ok(Json(achievements))
+}
+
+pub async fn get_my_color_stats(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<ColorIdentityStats>>> {
+    debug!(
+        "GET /api/v1/users/me/stats/colors - Getting color stats for user {}",
+        auth.clerk_user_id
+    );
+    let stats = database::get_user_color_stats(&state.db, &auth.clerk_user_id).await?;
+    Ok(Json(stats))
+}
+
+pub async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(params): Query<LeaderboardQueryParams>,
+) -> Result<Json<Vec<LeaderboardEntry>>> {
+    debug!("GET /api/v1/leaderboard - {:?}", params);
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 200) as usize;
+    let offset = params.offset.unwrap_or(0).max(0) as usize;
+    let entries = state.leaderboard().await?;
+    Ok(Json(
+        entries.iter().skip(offset).take(limit).cloned().collect(),
+    ))
+}
+
+pub async fn get_user_history_with_pod(
+    State(state): State<AppState>,
+    Path(pod_filter): Path<String>, // comma-separated clerk_user_ids
+    Query(params): Query<HistoryQueryParams>,
+    auth: AuthenticatedUser,
+) -> Result<Json<GameHistory>> {
+    debug!(
+        "GET /api/v1/users/me/history/pod/{} - Getting pod game history for user {} (include_no_winner: {})",
+        pod_filter, auth.clerk_user_id, params.include_no_winner
+    );
+
+    // Parse the comma-separated user IDs
+    let pod_user_ids: Vec<String> = pod_filter
+        .split(',')
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .collect();
@@ -323,6 +1051,51 @@ pub async fn get_recent_life_changes(
     Ok(Json(changes))
 }
 
+pub async fn get_game_summary(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+) -> Result<Json<GameSummary>> {
+    debug!(
+        "GET /api/v1/games/{}/summary - Getting game summary",
+        game_id
+    );
+    let summary = database::get_game_summary(&state.db, game_id).await?;
+    Ok(Json(summary))
+}
+
+pub async fn get_commander_damage(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<CommanderDamageQueryParams>,
+) -> Result<Json<Vec<CommanderDamage>>> {
+    debug!(
+        "GET /api/v1/games/{}/commander-damage - Getting commander damage (to_player_id: {:?})",
+        game_id, params.to_player_id
+    );
+    let mut damage = database::get_commander_damage_for_game(&state.db, game_id).await?;
+    if let Some(to_player_id) = params.to_player_id {
+        damage.retain(|cd| cd.to_player_id == to_player_id);
+    }
+    Ok(Json(damage))
+}
+
+pub async fn get_commander_damage_matrix(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+) -> Result<Json<CommanderDamageMatrix>> {
+    debug!(
+        "GET /api/v1/games/{}/commander-damage/matrix - Getting commander damage matrix",
+        game_id
+    );
+
+    // Verify game exists so unknown games 404 instead of returning an empty matrix
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let players = database::get_players_in_game(&state.db, game_id).await?;
+    let damage = database::get_commander_damage_for_game(&state.db, game_id).await?;
+    Ok(Json(CommanderDamageMatrix::new(game_id, &players, &damage)))
+}
+
 pub async fn health_check() -> Result<Json<serde_json::Value>> {
     debug!("GET /health - Health check endpoint called");
     Ok(Json(serde_json::json!({
@@ -331,8 +1104,15 @@ pub async fn health_check() -> Result<Json<serde_json::Value>> {
     })))
 }
 
+/// The running build, for confirming what is deployed
+pub async fn get_version() -> Json<BuildInfo> {
+    Json(BuildInfo::CURRENT)
+}
+
 pub async fn get_stats(State(state): State<AppState>) -> Result<Json<serde_json::Value>> {
     debug!("GET /api/v1/stats - Getting API statistics");
+    let (active_rooms, connected_users) = state.room_counts();
+
     let active_games_count =
         sqlx::query("SELECT COUNT(*) as count FROM games WHERE status = 'active'")
             .fetch_one(&state.db)
@@ -340,8 +1120,18 @@ pub async fn get_stats(State(state): State<AppState>) -> Result<Json<serde_json:
 
     let count: i64 = active_games_count.get("count");
 
+    let average_duration: Option<f64> = sqlx::query(
+        "SELECT AVG((julianday(finished_at) - julianday(created_at)) * 86400) as seconds FROM games WHERE status = 'finished' AND finished_at IS NOT NULL",
+    )
+    .fetch_one(&state.db)
+    .await?
+    .get("seconds");
+
     Ok(Json(serde_json::json!({
         "activeGames": count,
+        "averageGameDurationSeconds": average_duration.map(|s| s.round() as i64),
+        "activeRooms": active_rooms,
+        "connectedUsers": connected_users,
         "service": "conclave-api"
     })))
 }
@@ -350,6 +1140,7 @@ pub async fn get_stats(State(state): State<AppState>) -> Result<Json<serde_json:
 pub async fn update_commander_damage(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
     Json(request): Json<UpdateCommanderDamageRequest>,
 ) -> Result<Json<CommanderDamage>> {
     info!(
@@ -362,11 +1153,17 @@ pub async fn update_commander_damage(
     );
 
     // Validate damage amount change
-    if request.damage_amount.abs() > 50 {
+    if request.damage_amount == 0 {
         return Err(ApiError::BadRequest(
-            "Commander damage change too large (max ±50)".to_string(),
+            "Commander damage change must be non-zero".to_string(),
         ));
     }
+    let max_change = state.config.max_commander_damage_change;
+    if request.damage_amount.abs() > max_change {
+        return Err(ApiError::BadRequest(format!(
+            "Commander damage change too large (max ±{max_change})"
+        )));
+    }
 
     // Verify game is active
     let game = database::get_game_by_id(&state.db, game_id).await?;
@@ -386,7 +1183,8 @@ pub async fn update_commander_damage(
         .map(|cd| cd.damage)
         .unwrap_or(0);
 
-    let new_damage = current_damage + request.damage_amount;
+    let new_damage =
+        database::checked_total("Commander damage", current_damage, request.damage_amount)?;
 
     // Update commander damage
     let updated_damage = database::update_commander_damage(
@@ -396,6 +1194,19 @@ pub async fn update_commander_damage(
         request.to_player_id,
         request.commander_number,
         new_damage,
+        None,
+        &auth.clerk_user_id,
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "commander_damage",
+            serde_json::json!({
+                "fromPlayerId": request.from_player_id,
+                "toPlayerId": request.to_player_id,
+                "commanderNumber": request.commander_number,
+                "damageAmount": request.damage_amount,
+                "newDamage": new_damage,
+            }),
+        ),
     )
     .await?;
 
@@ -407,8 +1218,20 @@ pub async fn update_commander_damage(
         commander_number: request.commander_number,
         new_damage,
         damage_amount: request.damage_amount,
+        actor_clerk_user_id: Some(auth.clerk_user_id.clone()),
     };
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+
+    if new_damage >= game.commander_lethal {
+        websocket::eliminate_player(
+            &state,
+            game_id,
+            request.to_player_id,
+            EliminationCause::CommanderDamage,
+            &auth.clerk_user_id,
+        )
+        .await?;
+    }
 
     info!(
         "Commander damage updated in game {} from player {} to player {} (commander {}): new damage = {}",
@@ -417,9 +1240,99 @@ pub async fn update_commander_damage(
     Ok(Json(updated_damage))
 }
 
+pub async fn bulk_update_commander_damage(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+    Json(request): Json<BulkCommanderDamageRequest>,
+) -> Result<Json<Vec<CommanderDamage>>> {
+    info!(
+        "Applying {} commander damage changes in game {}",
+        request.entries.len(),
+        game_id
+    );
+
+    if request.entries.is_empty() || request.entries.len() > MAX_BULK_COMMANDER_DAMAGE_ENTRIES {
+        return Err(ApiError::BadRequest(format!(
+            "Between 1 and {MAX_BULK_COMMANDER_DAMAGE_ENTRIES} entries are required"
+        )));
+    }
+
+    // Validate damage amount changes
+    if request.entries.iter().any(|entry| entry.damage_amount == 0) {
+        return Err(ApiError::BadRequest(
+            "Commander damage change must be non-zero".to_string(),
+        ));
+    }
+
+    let max_change = state.config.max_commander_damage_change;
+    if request
+        .entries
+        .iter()
+        .any(|entry| entry.damage_amount.abs() > max_change)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Commander damage change too large (max ±{max_change})"
+        )));
+    }
+
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let updated = database::apply_commander_damage_batch(
+        &state.db,
+        game_id,
+        &request.entries,
+        &auth.clerk_user_id,
+        |updated| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "bulk_commander_damage",
+                serde_json::json!({
+                    "changes": CommanderDamageChange::from_batch(&request.entries, updated),
+                }),
+            )
+        },
+    )
+    .await?;
+    let changes = CommanderDamageChange::from_batch(&request.entries, &updated);
+
+    // Broadcast all changes as one message
+    state
+        .broadcast_to_game(
+            game_id,
+            WebSocketMessage::CommanderDamageBulkUpdate {
+                game_id,
+                changes,
+                actor_clerk_user_id: Some(auth.clerk_user_id.clone()),
+            },
+        )
+        .await;
+
+    for damage in updated
+        .iter()
+        .filter(|damage| damage.damage >= game.commander_lethal)
+    {
+        websocket::eliminate_player(
+            &state,
+            game_id,
+            damage.to_player_id,
+            EliminationCause::CommanderDamage,
+            &auth.clerk_user_id,
+        )
+        .await?;
+    }
+
+    Ok(Json(updated))
+}
+
 pub async fn toggle_partner(
     State(state): State<AppState>,
     Path((game_id, player_id)): Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
     Json(request): Json<TogglePartnerRequest>,
 ) -> Result<StatusCode> {
     info!(
@@ -427,9 +1340,9 @@ pub async fn toggle_partner(
         player_id, game_id, request.enable_partner
     );
 
-    // Verify game is active
+    // Verify game is active, or still in the lobby
     let game = database::get_game_by_id(&state.db, game_id).await?;
-    if game.status != "active" {
+    if !game.is_open() {
         return Err(ApiError::GameNotActive);
     }
 
@@ -441,7 +1354,18 @@ pub async fn toggle_partner(
     }
 
     // Toggle partner status
-    database::toggle_partner(&state.db, game_id, player_id, request.enable_partner).await?;
+    let restored = database::toggle_partner(
+        &state.db,
+        game_id,
+        player_id,
+        request.enable_partner,
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "toggle_partner",
+            serde_json::json!({ "playerId": player_id, "enablePartner": request.enable_partner }),
+        ),
+    )
+    .await?;
 
     // Broadcast partner toggle event via WebSocket
     let message = WebSocketMessage::PartnerToggled {
@@ -449,7 +1373,21 @@ pub async fn toggle_partner(
         player_id,
         has_partner: request.enable_partner,
     };
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+
+    // Re-enabling a partner brings back damage its commander had already dealt
+    if !restored.is_empty() {
+        state
+            .broadcast_to_game(
+                game_id,
+                WebSocketMessage::CommanderDamageBulkUpdate {
+                    game_id,
+                    changes: restored,
+                    actor_clerk_user_id: Some(auth.clerk_user_id.clone()),
+                },
+            )
+            .await;
+    }
 
     info!(
         "Partner {} for player {} in game {}",
@@ -463,3 +1401,447 @@ pub async fn toggle_partner(
     );
     Ok(StatusCode::OK)
 }
+
+pub async fn set_commander_name(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SetCommanderNameRequest>,
+) -> Result<Json<Player>> {
+    info!(
+        "Setting commander name for player {} in game {}: {:?} / {:?}",
+        player_id, game_id, request.commander_name, request.partner_name
+    );
+
+    // Verify game is active, or still in the lobby
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if !game.is_open() {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let player = database::set_commander_name(
+        &state.db,
+        game_id,
+        player_id,
+        request.commander_name,
+        request.partner_name,
+        |player| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "set_commander_name",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "commanderName": player.commander_name,
+                    "partnerName": player.partner_name,
+                }),
+            )
+        },
+    )
+    .await?;
+
+    // Broadcast commander name update via WebSocket
+    let message = WebSocketMessage::CommanderNameUpdated {
+        game_id,
+        player_id,
+        commander_name: player.commander_name.clone(),
+        partner_name: player.partner_name.clone(),
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+
+    Ok(Json(player))
+}
+
+pub async fn set_seat_color(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SetSeatColorRequest>,
+) -> Result<Json<Player>> {
+    info!(
+        "Setting seat color for player {} in game {}: {:?}",
+        player_id, game_id, request.color
+    );
+
+    // Verify game is active, or still in the lobby
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if !game.is_open() {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let player = database::set_seat_color(&state.db, game_id, player_id, request.color, |player| {
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "set_seat_color",
+            serde_json::json!({
+                "playerId": player_id,
+                "seatColor": player.seat_color,
+            }),
+        )
+    })
+    .await?;
+
+    let message = WebSocketMessage::SeatColorUpdated {
+        game_id,
+        player_id,
+        color: player.seat_color.clone(),
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+
+    Ok(Json(player))
+}
+
+pub async fn set_player_deck(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SetPlayerDeckRequest>,
+) -> Result<Json<PlayerDeck>> {
+    info!(
+        "Setting deck for player {} in game {}: {:?}",
+        player_id, game_id, request
+    );
+
+    // Verify game is active, or still in the lobby
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if !game.is_open() {
+        return Err(ApiError::GameNotActive);
+    }
+
+    database::ensure_can_control_player(&state.db, &game, player_id, &auth.clerk_user_id).await?;
+
+    let (player, deck) =
+        database::set_player_deck(&state.db, game_id, player_id, request, |deck| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "set_player_deck",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "commanderName": deck.commander_name,
+                    "partnerName": deck.partner_name,
+                    "colorIdentity": deck.color_identity,
+                    "decklistUrl": deck.decklist_url,
+                }),
+            )
+        })
+        .await?;
+
+    let message = WebSocketMessage::PlayerDeckUpdated {
+        game_id,
+        player_id,
+        deck: deck.clone(),
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+
+    Ok(Json(deck))
+}
+
+pub async fn set_strict_control(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SetStrictControlRequest>,
+) -> Result<Json<Game>> {
+    info!(
+        "Setting strict control for game {} to {}",
+        game_id, request.strict_control
+    );
+
+    // Verify game exists before checking host
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can change strict control".to_string(),
+        ));
+    }
+
+    let game = database::set_strict_control(
+        &state.db,
+        game_id,
+        request.strict_control,
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "set_strict_control",
+            serde_json::json!({ "strictControl": request.strict_control }),
+        ),
+    )
+    .await?;
+    // Nothing is broadcast for this change, so drop the cached state here
+    state.game_states.invalidate(game_id);
+
+    Ok(Json(game))
+}
+
+pub async fn get_audit_log(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<AuditLogEntry>>> {
+    debug!("GET /api/v1/games/{}/audit - Getting audit log", game_id);
+
+    // Verify game exists before checking host
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can view the audit log".to_string(),
+        ));
+    }
+
+    let entries = database::get_audit_log(&state.db, game_id).await?;
+    Ok(Json(entries))
+}
+
+/// A game's event history is for its players, or anyone holding the join code
+async fn ensure_can_read_events(
+    state: &AppState,
+    game: &Game,
+    clerk_user_id: &str,
+    join_code: Option<&str>,
+) -> Result<()> {
+    if game.join_code_matches(join_code) {
+        return Ok(());
+    }
+    match database::get_player_by_user(&state.db, game.id, clerk_user_id).await {
+        Ok(_) => Ok(()),
+        Err(ApiError::PlayerNotFound) => Err(ApiError::Forbidden(
+            "Only players in this game can read its events".to_string(),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn get_events_log(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<EventsLogQueryParams>,
+    auth: AuthenticatedUser,
+) -> Result<Json<Vec<GameEventRecord>>> {
+    debug!(
+        "GET /api/v1/games/{}/events-log - since {:?}",
+        game_id, params.since_seq
+    );
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    ensure_can_read_events(
+        &state,
+        &game,
+        &auth.clerk_user_id,
+        params.join_code.as_deref(),
+    )
+    .await?;
+
+    let events = database::get_game_events(
+        &state.db,
+        game_id,
+        params.since_seq.unwrap_or(0),
+        params.limit.unwrap_or(500).clamp(1, 500),
+    )
+    .await?;
+    Ok(Json(events))
+}
+
+/// Long-poll fallback for clients that can't hold a WebSocket: returns any
+/// logged events after `since_seq` at once, else waits up to 25 seconds for
+/// the next one
+pub async fn poll_events(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<PollQueryParams>,
+    auth: AuthenticatedUser,
+) -> Result<Json<PollResponse>> {
+    let since_seq = params.since_seq.unwrap_or(0);
+    debug!("GET /api/v1/games/{}/poll - since {}", game_id, since_seq);
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    ensure_can_read_events(
+        &state,
+        &game,
+        &auth.clerk_user_id,
+        params.join_code.as_deref(),
+    )
+    .await?;
+
+    // Subscribe before reading the log so an event logged in between is
+    // either in the read or delivered to the receiver
+    let mut receiver = game.is_open().then(|| state.get_game_receiver(game_id));
+
+    let mut events = database::get_game_events(&state.db, game_id, since_seq, 500).await?;
+    if events.is_empty()
+        && let Some(receiver) = receiver.as_mut()
+    {
+        let wait = async {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.seq.is_some_and(|seq| seq > since_seq) => break,
+                    Ok(_) => continue,
+                    // Missed messages or a closed room: the log has the answer
+                    Err(_) => break,
+                }
+            }
+        };
+        if tokio::time::timeout(LONG_POLL_TIMEOUT, wait).await.is_ok() {
+            events = database::get_game_events(&state.db, game_id, since_seq, 500).await?;
+        }
+    }
+
+    let cursor = events.last().map_or(since_seq, |event| event.seq);
+    Ok(Json(PollResponse { events, cursor }))
+}
+
+pub async fn get_timeline(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<TimelineQueryParams>,
+) -> Result<Json<Vec<TimelineEntry>>> {
+    debug!(
+        "GET /api/v1/games/{}/timeline - before {:?}",
+        game_id, params.before
+    );
+
+    // Verify game exists
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let timeline = database::get_game_timeline(
+        &state.db,
+        game_id,
+        params.before,
+        params.limit.unwrap_or(100).clamp(1, 500),
+    )
+    .await?;
+    Ok(Json(timeline))
+}
+
+pub async fn get_activity(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<ActivityQueryParams>,
+) -> Result<Json<Vec<ActivityItem>>> {
+    debug!("GET /api/v1/games/{}/activity", game_id);
+
+    // Verify game exists
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let activity = database::get_recent_activity(
+        &state.db,
+        game_id,
+        None,
+        params.limit.unwrap_or(20).clamp(1, 100),
+    )
+    .await?;
+    Ok(Json(activity))
+}
+
+pub async fn get_damage_dealt(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+) -> Result<Json<Vec<DamageDealt>>> {
+    debug!("GET /api/v1/games/{}/damage-dealt", game_id);
+
+    // Verify game exists
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let damage = database::get_damage_dealt(&state.db, game_id).await?;
+    Ok(Json(damage))
+}
+
+pub async fn reset_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<Json<GameState>> {
+    info!("User {} resetting game {}", auth.clerk_user_id, game_id);
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can reset the game".to_string(),
+        ));
+    }
+
+    database::reset_game(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(&auth.clerk_user_id, "reset", serde_json::json!({})),
+    )
+    .await?;
+    state.game_states.invalidate(game_id);
+
+    // Everyone's local state is now wrong; resync all clients
+    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    state.broadcast_resync(game_id, game_state.clone()).await;
+
+    Ok(Json(game_state))
+}
+
+pub async fn create_snapshot(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<Json<GameSnapshot>> {
+    info!("User {} snapshotting game {}", auth.clerk_user_id, game_id);
+
+    let snapshot =
+        database::create_game_snapshot(&state.db, game_id, &auth.clerk_user_id, |snapshot| {
+            NewAuditEntry::new(
+                &auth.clerk_user_id,
+                "create_snapshot",
+                serde_json::json!({ "snapshotId": snapshot.id }),
+            )
+        })
+        .await?;
+
+    Ok(Json(snapshot))
+}
+
+pub async fn restore_snapshot(
+    State(state): State<AppState>,
+    Path((game_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+) -> Result<Json<GameState>> {
+    info!(
+        "User {} restoring snapshot {} for game {}",
+        auth.clerk_user_id, snapshot_id, game_id
+    );
+
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(auth.clerk_user_id.as_str()) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can restore a snapshot".to_string(),
+        ));
+    }
+
+    database::restore_game_snapshot(
+        &state.db,
+        game_id,
+        snapshot_id,
+        NewAuditEntry::new(
+            &auth.clerk_user_id,
+            "restore_snapshot",
+            serde_json::json!({ "snapshotId": snapshot_id }),
+        ),
+    )
+    .await?;
+    state.game_states.invalidate(game_id);
+
+    // Everyone's local state is now wrong; resync all clients
+    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    state.broadcast_resync(game_id, game_state.clone()).await;
+
+    Ok(Json(game_state))
+}
@@ -0,0 +1,31 @@
+use crate::state::AppState;
+use std::time::Duration;
+use tracing::{debug, error};
+
+/// Load the refresh interval from `LEADERBOARD_REFRESH_MINUTES` (default 5)
+pub fn refresh_interval_from_env() -> Duration {
+    let minutes = std::env::var("LEADERBOARD_REFRESH_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&minutes: &u64| minutes > 0)
+        .unwrap_or(5);
+    Duration::from_secs(minutes * 60)
+}
+
+/// Spawn the leaderboard refresh loop; it runs for the lifetime of the server.
+/// The cache is rebuilt on every tick and whenever it's marked stale.
+pub fn spawn(state: AppState, interval: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                _ = state.leaderboard.stale() => {},
+            }
+            match state.refresh_leaderboard().await {
+                Ok(entries) => debug!("Refreshed leaderboard with {} users", entries.len()),
+                Err(e) => error!("Leaderboard refresh failed: {:?}", e),
+            }
+        }
+    });
+}
@@ -1,16 +1,24 @@
+mod achievements;
 mod auth;
 mod clerk;
+mod config;
 mod database;
 mod errors;
 mod handlers;
+mod leaderboard;
 mod models;
 mod state;
+mod state_hash;
+mod sweeper;
+#[cfg(test)]
+mod test_support;
 mod websocket;
 
 use axum::{
     Router,
+    extract::Request,
     http::{
-        Method,
+        HeaderName, Method,
         header::{AUTHORIZATION, CONTENT_TYPE},
     },
     routing::{get, post, put},
@@ -19,49 +27,86 @@ use state::AppState;
 use std::net::SocketAddr;
 use tower::ServiceBuilder;
 use tower_http::{
+    compression::CompressionLayer,
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
-use tracing::info;
+use tracing::{Span, field, info, info_span, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    // Load environment variables from .env if present (before reading any env vars)
-    let _ = dotenvy::dotenv();
-
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                "conclave_api=debug,tower_http=debug,axum::rejection=trace".into()
-            }),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+/// Span for each HTTP request, carrying a fresh request id and the game id
+/// from the path; `user_id` is filled in once the caller is authenticated
+fn request_span(request: &Request) -> Span {
+    let game_id = request
+        .uri()
+        .path()
+        .split('/')
+        .skip_while(|segment| *segment != "games")
+        .nth(1)
+        .and_then(|segment| segment.parse::<Uuid>().ok());
+    let span = info_span!(
+        "request",
+        request_id = %Uuid::new_v4(),
+        method = %request.method(),
+        // Path only: the WebSocket query string carries the token
+        path = %request.uri().path(),
+        game_id = field::Empty,
+        user_id = field::Empty,
+    );
+    if let Some(game_id) = game_id {
+        span.record("game_id", field::display(game_id));
+    }
+    span
+}
 
-    info!("🎯 Starting Conclave API Server...");
+/// Resolve on Ctrl+C or SIGTERM, after asking open WebSockets to close
+async fn shutdown_signal(state: AppState) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
 
-    // Initialize Clerk client for JWT validation
-    clerk::ClerkClient::init()?;
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    // Initialize database
-    let db_pool = database::create_pool().await?;
-    info!("✅ Database connected and migrations completed");
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 
-    // Create application state
-    let app_state = AppState::new(db_pool);
+    info!("Shutting down, closing WebSocket connections");
+    state.begin_shutdown();
+}
 
+/// The full application: REST API under /api/v1, the WebSocket endpoint and
+/// middleware. Kept apart from `main` so the real stack can be built around
+/// any state, e.g. one backed by an in-memory database.
+fn build_router(app_state: AppState) -> Router {
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([AUTHORIZATION, CONTENT_TYPE])
+        .allow_headers([
+            AUTHORIZATION,
+            CONTENT_TYPE,
+            HeaderName::from_static(auth::ADMIN_KEY_HEADER),
+            HeaderName::from_static(handlers::IDEMPOTENCY_KEY_HEADER),
+        ])
         .allow_origin(Any);
 
     // Build the API v1 router
     let api_v1_router = Router::new()
         // Health and monitoring endpoints
         .route("/health", get(handlers::health_check))
+        .route("/version", get(handlers::get_version))
         .route("/stats", get(handlers::get_stats))
         // User endpoints (authenticated via JWT - uses /users/me/ pattern)
         .route("/users/me/history", get(handlers::get_user_history))
@@ -70,6 +115,11 @@ async fn main() -> anyhow::Result<()> {
             get(handlers::get_user_history_with_pod),
         )
         .route("/users/me/games", get(handlers::get_user_games))
+        .route("/users/me/games/leave", post(handlers::leave_games))
+        .route("/users/me/rating", get(handlers::get_my_rating))
+        .route("/users/me/achievements", get(handlers::get_my_achievements))
+        .route("/users/me/stats/colors", get(handlers::get_my_color_stats))
+        .route("/leaderboard", get(handlers::get_leaderboard))
         .route(
             "/users/me/available-games",
             get(handlers::get_available_games),
@@ -77,38 +127,171 @@ async fn main() -> anyhow::Result<()> {
         // Game endpoints
         .route("/games", post(handlers::create_game))
         .route("/games", get(handlers::get_all_games))
-        .route("/games/{game_id}", get(handlers::get_game))
+        .route(
+            "/games/{game_id}",
+            get(handlers::get_game).delete(handlers::delete_game),
+        )
         .route("/games/{game_id}/state", get(handlers::get_game_state))
+        .route(
+            "/games/{game_id}/state-at",
+            get(handlers::get_game_state_at),
+        )
+        .route("/games/states", post(handlers::get_game_states))
+        .route("/games/{game_id}/players", get(handlers::get_players))
+        .route(
+            "/games/{game_id}/players/{player_id}",
+            get(handlers::get_player),
+        )
         .route("/games/{game_id}/join", post(handlers::join_game))
         .route("/games/{game_id}/leave", post(handlers::leave_game))
         .route("/games/{game_id}/update-life", put(handlers::update_life))
+        .route("/games/{game_id}/set-life", put(handlers::set_life))
+        .route("/games/{game_id}/start", post(handlers::start_game))
         .route("/games/{game_id}/end", put(handlers::end_game))
         .route(
             "/games/{game_id}/life-changes",
             get(handlers::get_recent_life_changes),
         )
+        .route("/games/{game_id}/summary", get(handlers::get_game_summary))
+        .route("/games/{game_id}/audit", get(handlers::get_audit_log))
+        .route("/games/{game_id}/events-log", get(handlers::get_events_log))
+        .route("/games/{game_id}/timeline", get(handlers::get_timeline))
+        .route("/games/{game_id}/poll", get(handlers::poll_events))
+        .route("/games/{game_id}/activity", get(handlers::get_activity))
+        .route(
+            "/games/{game_id}/damage-dealt",
+            get(handlers::get_damage_dealt),
+        )
+        .route(
+            "/games/{game_id}/strict-control",
+            put(handlers::set_strict_control),
+        )
+        .route("/games/{game_id}/reset", post(handlers::reset_game))
+        .route(
+            "/games/{game_id}/snapshots",
+            post(handlers::create_snapshot),
+        )
+        .route(
+            "/games/{game_id}/snapshots/{snapshot_id}/restore",
+            post(handlers::restore_snapshot),
+        )
         // Commander Damage endpoints
         .route(
             "/games/{game_id}/commander-damage",
             put(handlers::update_commander_damage),
         )
+        .route(
+            "/games/{game_id}/commander-damage",
+            get(handlers::get_commander_damage),
+        )
+        .route(
+            "/games/{game_id}/commander-damage/matrix",
+            get(handlers::get_commander_damage_matrix),
+        )
+        .route(
+            "/games/{game_id}/bulk-commander-damage",
+            put(handlers::bulk_update_commander_damage),
+        )
         .route(
             "/games/{game_id}/players/{player_id}/partner",
             post(handlers::toggle_partner),
-        );
+        )
+        .route(
+            "/games/{game_id}/players/{player_id}/commander",
+            put(handlers::set_commander_name),
+        )
+        .route(
+            "/games/{game_id}/players/{player_id}/seat-color",
+            put(handlers::set_seat_color),
+        )
+        .route(
+            "/games/{game_id}/players/{player_id}/deck",
+            post(handlers::set_player_deck),
+        )
+        // Admin endpoints (guarded by the X-Admin-Key header)
+        .route("/admin/games", get(handlers::admin_list_games))
+        .route("/admin/games/{game_id}/end", post(handlers::admin_end_game))
+        .route("/admin/metrics", get(handlers::admin_metrics));
 
     // Build the main router with nested API routes
-    let app = Router::new()
+    Router::new()
         .nest("/api/v1", api_v1_router)
         // WebSocket endpoint at root level for easier access
         .route("/ws", get(websocket::websocket_handler))
         // Add middleware
         .layer(
             ServiceBuilder::new()
-                .layer(TraceLayer::new_for_http())
+                .layer(TraceLayer::new_for_http().make_span_with(request_span))
+                // Inside tracing so traces see the final response; bodiless
+                // WebSocket upgrade responses pass through uncompressed
+                .layer(CompressionLayer::new())
                 .layer(cors),
         )
-        .with_state(app_state);
+        .with_state(app_state)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // Load environment variables from .env if present (before reading any env vars)
+    let _ = dotenvy::dotenv();
+
+    // Initialize tracing; LOG_FORMAT=json emits one JSON object per line for
+    // log aggregators, with span fields (request id, game, user) as keys
+    let log_format = std::env::var("LOG_FORMAT").ok();
+    let json_logs = log_format
+        .as_deref()
+        .is_some_and(|v| v.eq_ignore_ascii_case("json"));
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                "conclave_api=debug,tower_http=debug,axum::rejection=trace".into()
+            }),
+        )
+        .with(json_logs.then(|| tracing_subscriber::fmt::layer().json()))
+        .with((!json_logs).then(tracing_subscriber::fmt::layer))
+        .init();
+    if let Some(format) =
+        log_format.filter(|v| !v.eq_ignore_ascii_case("json") && !v.eq_ignore_ascii_case("text"))
+    {
+        warn!("Unknown LOG_FORMAT {:?}, falling back to text logs", format);
+    }
+
+    let build = models::BuildInfo::CURRENT;
+    info!(
+        "🎯 Starting Conclave API Server v{} ({}, built {})...",
+        build.version, build.git_sha, build.build_time
+    );
+
+    // Load validation limits; bad values should stop startup, not surface per request
+    let config = config::Config::from_env()?;
+
+    // Initialize Clerk client for JWT validation
+    clerk::ClerkClient::init()?;
+
+    // Initialize database
+    let db_pool = database::create_pool().await?;
+    info!("✅ Database connected and migrations completed");
+
+    // Create application state
+    let app_state = AppState::new(
+        db_pool,
+        state::ConnectionLimits::from_env(),
+        state::channel_capacity_from_env(),
+        config,
+    );
+
+    // End games nobody has touched in a while
+    sweeper::spawn(app_state.clone(), sweeper::SweeperConfig::from_env()?);
+
+    // Keep the leaderboard warm so requests don't pay for building it
+    leaderboard::spawn(app_state.clone(), leaderboard::refresh_interval_from_env());
+
+    // Let clients check for drift, when enabled
+    if let Some(interval) = state_hash::interval_from_env() {
+        state_hash::spawn(app_state.clone(), interval);
+    }
+
+    let app = build_router(app_state.clone());
 
     // Start server
     let port = std::env::var("PORT")
@@ -122,7 +305,474 @@ async fn main() -> anyhow::Result<()> {
     info!("📡 API endpoints available at http://{}/api/v1/", addr);
     info!("📡 WebSocket endpoint available at ws://{}/ws", addr);
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(app_state))
+        .await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, test_support};
+    use axum::{
+        body::Body,
+        http::{
+            Request, StatusCode,
+            header::{ACCEPT_ENCODING, CONTENT_ENCODING, RETRY_AFTER},
+        },
+    };
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    /// A JSON request authenticated as `user`
+    fn request(method: Method, uri: &str, user: &str, body: Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", test_support::token(user)),
+            )
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    /// Send a request through the router, returning the status and JSON body
+    async fn call(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        user: &str,
+        body: Value,
+    ) -> (StatusCode, Value) {
+        let request = request(method, uri, user, body);
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn events_log_is_for_players_and_join_code_holders() {
+        let router = build_router(test_support::app_state().await);
+
+        let (_, game) = call(
+            &router,
+            Method::POST,
+            "/api/v1/games",
+            "host",
+            json!({ "visibility": "private" }),
+        )
+        .await;
+        let game_id = game["id"].as_str().unwrap().to_string();
+        let join_code = game["joinCode"].as_str().unwrap().to_string();
+
+        // Starting from the lobby logs a gameStarted carrying the game state
+        let (status, body) = call(
+            &router,
+            Method::POST,
+            &format!("/api/v1/games/{game_id}/start"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        let log_uri = format!("/api/v1/games/{game_id}/events-log");
+        let (status, _) = call(&router, Method::GET, &log_uri, "stranger", Value::Null).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let with_code = format!("{log_uri}?join_code={join_code}");
+        let (status, _) = call(&router, Method::GET, &with_code, "stranger", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, events) = call(&router, Method::GET, &log_uri, "host", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        let started = events
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|event| event["type"] == "gameStarted")
+            .expect("gameStarted is logged");
+        assert!(started["game"]["joinCode"].is_null());
+        assert!(!events.to_string().contains(&join_code));
+
+        // Polling is gated the same way; with events waiting it returns at once
+        let poll_uri = format!("/api/v1/games/{game_id}/poll");
+        let (status, _) = call(&router, Method::GET, &poll_uri, "stranger", Value::Null).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let with_code = format!("{poll_uri}?join_code={join_code}");
+        let (status, polled) =
+            call(&router, Method::GET, &with_code, "stranger", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!polled["events"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_logs_a_full_state() {
+        let router = build_router(test_support::app_state().await);
+
+        let (_, game) = call(&router, Method::POST, "/api/v1/games", "host", json!({})).await;
+        let game_id = game["id"].as_str().unwrap().to_string();
+        call(
+            &router,
+            Method::POST,
+            &format!("/api/v1/games/{game_id}/start"),
+            "host",
+            Value::Null,
+        )
+        .await;
+
+        let (status, body) = call(
+            &router,
+            Method::POST,
+            &format!("/api/v1/games/{game_id}/reset"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        let (_, events) = call(
+            &router,
+            Method::GET,
+            &format!("/api/v1/games/{game_id}/events-log"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        let last = events
+            .as_array()
+            .unwrap()
+            .last()
+            .expect("an event is logged");
+        assert_eq!(last["type"], "fullState");
+        assert!(last["seq"].is_i64());
+        assert!(last["game"]["joinCode"].is_null());
+    }
+
+    #[tokio::test]
+    async fn create_join_update_life_and_end() {
+        let router = build_router(test_support::app_state().await);
+
+        let (status, game) = call(
+            &router,
+            Method::POST,
+            "/api/v1/games",
+            "host",
+            json!({ "lobby": false }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{game}");
+        let game_id = game["id"].as_str().unwrap().to_string();
+
+        let (status, guest) = call(
+            &router,
+            Method::POST,
+            &format!("/api/v1/games/{game_id}/join"),
+            "guest",
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{guest}");
+        let guest_id = guest["id"].as_str().unwrap().to_string();
+
+        let (status, body) = call(
+            &router,
+            Method::PUT,
+            &format!("/api/v1/games/{game_id}/update-life"),
+            "guest",
+            json!({ "playerId": guest_id, "changeAmount": -5 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        let (status, state) = call(
+            &router,
+            Method::GET,
+            &format!("/api/v1/games/{game_id}/state"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let guest_life = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["id"] == guest_id.as_str())
+            .map(|p| p["currentLife"].clone());
+        assert_eq!(guest_life, Some(json!(15)));
+
+        let (status, body) = call(
+            &router,
+            Method::PUT,
+            &format!("/api/v1/games/{game_id}/end"),
+            "host",
+            json!({ "winnerPlayerId": guest_id }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        let (_, game) = call(
+            &router,
+            Method::GET,
+            &format!("/api/v1/games/{game_id}"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(game["status"], "finished");
+        assert_eq!(game["winnerPlayerId"], guest_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn rapid_game_creation_is_throttled() {
+        let config = Config {
+            game_creation_per_minute: 2,
+            max_open_games_per_user: 10,
+            ..Config::default()
+        };
+        let router = build_router(test_support::app_state_with_config(config).await);
+
+        for _ in 0..2 {
+            let (status, body) =
+                call(&router, Method::POST, "/api/v1/games", "host", json!({})).await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+        }
+
+        let request = request(Method::POST, "/api/v1/games", "host", json!({}));
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "30");
+
+        // Someone else can still create a game
+        let (status, _) = call(&router, Method::POST, "/api/v1/games", "guest", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn game_state_is_compressed_when_accepted() {
+        let router = build_router(test_support::app_state().await);
+        let (_, game) = call(
+            &router,
+            Method::POST,
+            "/api/v1/games",
+            "host",
+            json!({ "lobby": false }),
+        )
+        .await;
+        let game_id = game["id"].as_str().unwrap().to_string();
+        for user in ["user_1", "user_2", "user_3"] {
+            let uri = format!("/api/v1/games/{game_id}/join");
+            let (status, body) = call(&router, Method::POST, &uri, user, json!({})).await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+        }
+        let state_uri = format!("/api/v1/games/{game_id}/state");
+
+        let mut gzip = request(Method::GET, &state_uri, "host", Value::Null);
+        gzip.headers_mut()
+            .insert(ACCEPT_ENCODING, "gzip".parse().unwrap());
+        let response = router.clone().oneshot(gzip).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        let compressed = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let plain = request(Method::GET, &state_uri, "host", Value::Null);
+        let response = router.clone().oneshot(plain).await.unwrap();
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        let uncompressed = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(compressed.len() < uncompressed.len());
+    }
+
+    #[tokio::test]
+    async fn ending_succeeds_when_achievements_fail() {
+        let state = test_support::app_state().await;
+        sqlx::query("DROP TABLE user_achievements")
+            .execute(&state.db)
+            .await
+            .unwrap();
+        let router = build_router(state);
+
+        let (_, game) = call(
+            &router,
+            Method::POST,
+            "/api/v1/games",
+            "host",
+            json!({ "lobby": false }),
+        )
+        .await;
+        let game_id = game["id"].as_str().unwrap().to_string();
+        let (_, state) = call(
+            &router,
+            Method::GET,
+            &format!("/api/v1/games/{game_id}/state"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        let host_id = state["players"][0]["id"].as_str().unwrap().to_string();
+
+        // Winning at exactly 1 life earns a badge, which can't be stored
+        let (status, body) = call(
+            &router,
+            Method::PUT,
+            &format!("/api/v1/games/{game_id}/set-life"),
+            "host",
+            json!({ "playerId": host_id, "life": 1 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        let (status, body) = call(
+            &router,
+            Method::PUT,
+            &format!("/api/v1/games/{game_id}/end"),
+            "host",
+            json!({ "winnerPlayerId": host_id }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["status"], "finished");
+    }
+
+    #[tokio::test]
+    async fn zero_deltas_change_and_broadcast_nothing() {
+        let state = test_support::app_state().await;
+        let router = build_router(state.clone());
+
+        let (_, game) = call(
+            &router,
+            Method::POST,
+            "/api/v1/games",
+            "host",
+            json!({ "lobby": false }),
+        )
+        .await;
+        let game_id = game["id"].as_str().unwrap().to_string();
+        let (_, guest) = call(
+            &router,
+            Method::POST,
+            &format!("/api/v1/games/{game_id}/join"),
+            "guest",
+            json!({}),
+        )
+        .await;
+        let guest_id = guest["id"].as_str().unwrap().to_string();
+        let (_, game_state) = call(
+            &router,
+            Method::GET,
+            &format!("/api/v1/games/{game_id}/state"),
+            "host",
+            Value::Null,
+        )
+        .await;
+        let host_id = game_state["players"][0]["id"].as_str().unwrap().to_string();
+
+        let count = |table: &'static str| {
+            let db = state.db.clone();
+            let game_id = game_id.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>(&format!(
+                    "SELECT COUNT(*) FROM {table} WHERE game_id = ?"
+                ))
+                .bind(game_id)
+                .fetch_one(&db)
+                .await
+                .unwrap()
+            }
+        };
+        let life_rows = count("life_changes").await;
+        let damage_rows = count("commander_damage").await;
+        let mut receiver = state.get_game_receiver(game_id.parse().unwrap());
+
+        let damage = json!({
+            "fromPlayerId": host_id,
+            "toPlayerId": guest_id,
+            "commanderNumber": 1,
+            "damageAmount": 0,
+        });
+        let zero_deltas = [
+            (
+                "update-life",
+                json!({ "playerId": guest_id, "changeAmount": 0 }),
+            ),
+            ("commander-damage", damage.clone()),
+            ("bulk-commander-damage", json!({ "entries": [damage] })),
+        ];
+        for (path, body) in zero_deltas {
+            let uri = format!("/api/v1/games/{game_id}/{path}");
+            let (status, body) = call(&router, Method::PUT, &uri, "host", body).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{path}: {body}");
+        }
+
+        assert_eq!(count("life_changes").await, life_rows);
+        assert_eq!(count("commander_damage").await, damage_rows);
+        assert!(receiver.try_recv().is_err());
+
+        // A real change does both
+        let uri = format!("/api/v1/games/{game_id}/update-life");
+        let body = json!({ "playerId": guest_id, "changeAmount": -1 });
+        let (status, _) = call(&router, Method::PUT, &uri, "host", body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(count("life_changes").await, life_rows + 1);
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    /// Collects everything the JSON layer writes
+    #[derive(Clone, Default)]
+    struct CaptureWriter(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_logs_carry_request_span_fields_as_keys() {
+        let writer = CaptureWriter::default();
+        let make_writer = writer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(move || make_writer.clone()),
+        );
+
+        let game_id = Uuid::new_v4();
+        let request = Request::builder()
+            .uri(format!("/api/v1/games/{game_id}/state"))
+            .body(Body::empty())
+            .unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            request_span(&request).in_scope(|| info!("inside the request"));
+        });
+
+        let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        let span = &line["span"];
+        assert_eq!(span["name"], "request");
+        assert!(span["request_id"].as_str().unwrap().parse::<Uuid>().is_ok());
+        assert_eq!(span["game_id"], game_id.to_string());
+    }
+}
@@ -1,20 +1,136 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
-#[serde(rename_all = "camelCase")]
+/// Serializes with a `settings` object built from the rule fields; see
+/// `impl Serialize for Game`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self", rename_all = "camelCase")]
 pub struct Game {
     pub id: Uuid,
-    pub status: String, // "active", "finished"
+    pub status: String, // "lobby", "active", "finished", "cancelled"
     pub starting_life: i32,
     pub winner_player_id: Option<Uuid>, // Optional winner reference
     pub created_at: DateTime<Utc>,
     pub finished_at: Option<DateTime<Utc>>,
+    pub strict_control: bool, // Only the player or the host may change a player's life
+    pub format: Option<String>, // "commander", "standard", "brawl", "two_headed_giant"
+    pub max_players: i32,
+    pub deleted_at: Option<DateTime<Utc>>, // Set when the host cancels the game
+    pub outcome: Option<String>,           // "no_contest" when ended for inactivity
+    #[serde(default = "default_commander_lethal")] // Absent from older snapshots
+    pub commander_lethal: i32, // Damage from a single commander that eliminates a player
+    #[serde(default = "default_visibility")]
+    pub visibility: String, // "public", "unlisted" or "private"; see GameVisibility
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join_code: Option<String>, // Required to join private games; redacted for outsiders
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>, // Set when the host starts a lobby game
+    #[serde(default = "default_auto_eliminate")] // Absent from older snapshots
+    pub auto_eliminate: bool, // Eliminate players at 0 life or lethal commander damage
+}
+
+/// A game's rules in one place, so clients needn't probe individual fields.
+/// Always built from the `Game` by `Game::settings`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSettings {
+    pub starting_life: i32,
+    pub max_players: i32,
+    pub commander_lethal: i32,
+    pub format: Option<String>,
+    pub visibility: String,
+    pub strict_control: bool,
+    pub auto_eliminate: bool,
+}
+
+// The rule fields stay at the top level for older clients, with `settings`
+// derived from them so the two can't disagree
+impl Serialize for Game {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct WithSettings<'a> {
+            #[serde(flatten, with = "Game")]
+            game: &'a Game,
+            settings: GameSettings,
+        }
+        WithSettings {
+            game: self,
+            settings: self.settings(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A serialized `settings` is ignored; it is rebuilt from the rule fields
+        Game::deserialize(deserializer)
+    }
+}
+
+fn default_commander_lethal() -> i32 {
+    COMMANDER_DAMAGE_LETHAL
+}
+
+fn default_auto_eliminate() -> bool {
+    true
+}
+
+fn default_hand_size() -> i32 {
+    DEFAULT_HAND_SIZE
+}
+
+fn default_visibility() -> String {
+    GameVisibility::Public.as_str().to_string()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+impl Game {
+    pub fn settings(&self) -> GameSettings {
+        GameSettings {
+            starting_life: self.starting_life,
+            max_players: self.max_players,
+            commander_lethal: self.commander_lethal,
+            format: self.format.clone(),
+            visibility: self.visibility.clone(),
+            strict_control: self.strict_control,
+            auto_eliminate: self.auto_eliminate,
+        }
+    }
+
+    /// Whether the game is still gathering players or being played
+    pub fn is_open(&self) -> bool {
+        self.status == "lobby" || self.status == "active"
+    }
+
+    /// The game as shown to someone who may not be in it
+    pub fn without_join_code(mut self) -> Self {
+        self.join_code = None;
+        self
+    }
+
+    /// Whether `given` is this game's join code; codes are case-insensitive
+    /// and games without one match nothing
+    pub fn join_code_matches(&self, given: Option<&str>) -> bool {
+        match (&self.join_code, given) {
+            (Some(expected), Some(given)) => given.trim().eq_ignore_ascii_case(expected),
+            _ => false,
+        }
+    }
+
+    /// Seconds from creation until the game finished or was cancelled, or
+    /// until now while it's still going
+    pub fn duration_seconds(&self) -> i64 {
+        let end = self
+            .finished_at
+            .or(self.deleted_at)
+            .unwrap_or_else(Utc::now);
+        (end - self.created_at).num_seconds().max(0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Player {
     pub id: Uuid,
@@ -22,6 +138,21 @@ pub struct Player {
     pub clerk_user_id: String, // Clerk user ID
     pub current_life: i32,
     pub position: i32, // Player position in game (1-8 for MTG)
+    pub commander_name: Option<String>,
+    pub partner_name: Option<String>,
+    pub version: i64, // Bumped on every mutation, for compare-and-swap updates
+    #[serde(default)] // Absent from events and snapshots logged before it existed
+    pub commander_casts: i32, // Commander tax is 2 × casts
+    pub elimination_cause: Option<String>, // Set once the player is out; see EliminationCause
+    #[serde(default)] // Absent from events and snapshots logged before it existed
+    pub mulligans: i32,
+    #[serde(default = "default_hand_size")]
+    pub hand_size: i32, // Opening hand size after mulligans
+    #[serde(default)] // Absent from events and snapshots logged before it existed
+    pub starting_life: i32, // The game's, unless overridden when joining
+    pub seat_color: Option<String>, // One of SEAT_COLORS, unique within the game
+    #[serde(default)]
+    pub has_partner: bool, // Has a second commander; see toggle_partner
 }
 
 /// Player with enriched user display info from Clerk
@@ -33,6 +164,21 @@ pub struct PlayerWithUser {
     pub clerk_user_id: String,
     pub current_life: i32,
     pub position: i32,
+    pub commander_name: Option<String>,
+    pub partner_name: Option<String>,
+    pub version: i64,
+    #[serde(default)]
+    pub commander_casts: i32,
+    pub elimination_cause: Option<String>,
+    #[serde(default)]
+    pub mulligans: i32,
+    #[serde(default = "default_hand_size")]
+    pub hand_size: i32,
+    #[serde(default)]
+    pub starting_life: i32,
+    pub seat_color: Option<String>,
+    #[serde(default)]
+    pub has_partner: bool,
     // User display info
     pub display_name: String,
     pub username: Option<String>,
@@ -52,6 +198,16 @@ impl PlayerWithUser {
             clerk_user_id: player.clerk_user_id,
             current_life: player.current_life,
             position: player.position,
+            commander_name: player.commander_name,
+            partner_name: player.partner_name,
+            version: player.version,
+            commander_casts: player.commander_casts,
+            elimination_cause: player.elimination_cause,
+            mulligans: player.mulligans,
+            hand_size: player.hand_size,
+            starting_life: player.starting_life,
+            seat_color: player.seat_color,
+            has_partner: player.has_partner,
             display_name,
             username,
             image_url,
@@ -59,18 +215,32 @@ impl PlayerWithUser {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+/// A single player's board: enriched player info plus per-player game details
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerDetails {
+    #[serde(flatten)]
+    pub player: PlayerWithUser,
+    pub commander_damage_received: Vec<CommanderDamage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LifeChange {
     pub id: Uuid,
     pub game_id: Uuid,
     pub player_id: Uuid,
+    /// The player who caused the change, when known
+    pub source_player_id: Option<Uuid>,
+    /// Why the change happened, when the client said
+    pub reason: Option<LifeChangeReason>,
     pub change_amount: i32,
     pub new_life_total: i32,
     pub created_at: DateTime<Utc>,
+    pub created_by: Option<String>, // Clerk user who made the change
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommanderDamage {
     pub id: Uuid,
@@ -81,6 +251,275 @@ pub struct CommanderDamage {
     pub damage: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub updated_by: Option<String>, // Clerk user who last changed the damage
+}
+
+/// Named table-wide tally such as a storm count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameCounter {
+    pub game_id: Uuid,
+    pub name: String,
+    pub value: i32,
+    pub reset_each_turn: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Recap of one player's life and commander damage over a game
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerGameSummary {
+    pub player_id: Uuid,
+    pub clerk_user_id: String,
+    pub position: i32,
+    pub current_life: i32,
+    pub life_gained: i64,
+    pub life_lost: i64,    // As a positive number
+    pub lowest_life: i32,  // Includes the starting life
+    pub life_updates: i64, // Number of recorded life changes
+    pub commander_damage_dealt: i64,
+    pub commander_damage_received: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSummary {
+    pub game_id: Uuid,
+    pub players: Vec<PlayerGameSummary>,
+}
+
+/// Commander damage between every pair of players, pivoted for grid UIs
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommanderDamageMatrix {
+    pub game_id: Uuid,
+    pub player_order: Vec<Uuid>, // By position, for laying out rows and columns
+    pub damage: BTreeMap<Uuid, BTreeMap<Uuid, CommanderDamagePair>>, // From player → to player
+}
+
+/// Damage one player's commanders have dealt another
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommanderDamagePair {
+    pub commander1: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commander2: Option<i32>, // Only while partner damage is being tracked
+}
+
+impl CommanderDamageMatrix {
+    /// Pivot the active commander damage entries; `players` must be in
+    /// position order
+    pub fn new(game_id: Uuid, players: &[Player], entries: &[CommanderDamage]) -> Self {
+        let mut damage: BTreeMap<Uuid, BTreeMap<Uuid, CommanderDamagePair>> = BTreeMap::new();
+        for entry in entries {
+            let pair = damage
+                .entry(entry.from_player_id)
+                .or_default()
+                .entry(entry.to_player_id)
+                .or_default();
+            match entry.commander_number {
+                1 => pair.commander1 = entry.damage,
+                _ => pair.commander2 = Some(entry.damage),
+            }
+        }
+
+        Self {
+            game_id,
+            player_order: players.iter().map(|p| p.id).collect(),
+            damage,
+        }
+    }
+}
+
+/// Deck a player attached to their seat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerDeck {
+    pub player_id: Uuid,
+    pub game_id: Uuid,
+    pub commander_name: String,
+    pub partner_name: Option<String>,
+    pub color_identity: Option<String>, // Subset of "WUBRG" in that order, or "C" for colorless
+    pub decklist_url: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Immutable record of a game-mutating action, used for dispute resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub actor_clerk_user_id: String,
+    pub action: String, // e.g. "life_update", "commander_damage", "join", "end_game"
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An audit log entry not yet written. Mutations take one as `audit` and write
+/// it in the transaction that makes the change, or take a closure building it
+/// from the change's result when the entry needs that.
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub actor_clerk_user_id: String,
+    pub action: &'static str,
+    pub payload: serde_json::Value,
+}
+
+impl NewAuditEntry {
+    pub fn new(
+        actor_clerk_user_id: &str,
+        action: &'static str,
+        payload: serde_json::Value,
+    ) -> Self {
+        Self {
+            actor_clerk_user_id: actor_clerk_user_id.to_string(),
+            action,
+            payload,
+        }
+    }
+}
+
+/// A user's skill rating across all rated games
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerRating {
+    pub clerk_user_id: String,
+    pub rating: f64,
+    pub games_played: i32,
+}
+
+/// A badge a user has earned; see `achievements::RULES`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAchievement {
+    pub clerk_user_id: String,
+    pub achievement_id: String,
+    pub name: String,
+    pub description: String,
+    pub game_id: Uuid, // Game in which it was first earned
+    pub earned_at: DateTime<Utc>,
+}
+
+/// A user's record with one color identity
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorIdentityStats {
+    pub color_identity: String, // WUBRG order, "C" for colorless, or "unknown"
+    pub games_played: i64,
+    pub games_won: i64,
+    pub win_rate: f64, // games_won / games_played, 0.0 to 1.0
+}
+
+/// A leaderboard row: rating plus display info from Clerk
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    pub rank: usize, // 1-based position by rating
+    pub clerk_user_id: String,
+    pub rating: f64,
+    pub games_played: i32,
+    pub display_name: String,
+    pub image_url: Option<String>,
+}
+
+/// Damage one player has dealt the others in a game
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageDealt {
+    pub player_id: Option<Uuid>, // None buckets life loss with no recorded source ("unknown")
+    pub life_damage: i64,        // Life lost by other players to this source
+    pub commander_damage: i64,   // Current commander damage totals against other players
+    pub total: i64,
+}
+
+/// Version of the running server, fixed at compile time
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str, // "unknown" when built outside a git checkout
+    pub build_time: &'static str, // RFC 3339, when build.rs last ran
+}
+
+impl BuildInfo {
+    pub const CURRENT: BuildInfo = BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("GIT_SHA"),
+        build_time: env!("BUILD_TIME"),
+    };
+}
+
+/// A broadcast message as stored in the game's event log
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameEventRecord {
+    pub seq: i64,
+    pub created_at: DateTime<Utc>,
+    /// The message as it was broadcast, including its `type`
+    #[serde(flatten)]
+    pub message: serde_json::Value,
+}
+
+/// Events returned by a long poll, and the `sinceSeq` to poll with next
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollResponse {
+    pub events: Vec<GameEventRecord>,
+    pub cursor: i64,
+}
+
+/// One life or commander damage change in a game's recent activity
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ActivityItem {
+    LifeChange(LifeChange),
+    /// A commander pair's current total, as of its last update
+    CommanderDamage(CommanderDamage),
+}
+
+impl ActivityItem {
+    /// When the change happened, for ordering the feed
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            ActivityItem::LifeChange(change) => change.created_at,
+            ActivityItem::CommanderDamage(damage) => damage.updated_at,
+        }
+    }
+}
+
+/// One item in a game's timeline
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TimelineEntry {
+    LifeChange(LifeChange),
+    /// A commander pair's current total, as of its last update
+    CommanderDamage(CommanderDamage),
+    /// Any other logged broadcast (joins, leaves, eliminations, ...), under its own `type`
+    #[serde(untagged)]
+    Event(GameEventRecord),
+}
+
+impl TimelineEntry {
+    /// When the entry happened, for ordering the timeline
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            TimelineEntry::LifeChange(change) => change.created_at,
+            TimelineEntry::CommanderDamage(damage) => damage.updated_at,
+            TimelineEntry::Event(event) => event.created_at,
+        }
+    }
+}
+
+/// Saved copy of a game's full state, restorable by the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSnapshot {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub created_by_clerk_user_id: String,
+    pub state: GameState,
+    pub created_at: DateTime<Utc>,
 }
 
 // Request/Response DTOs
@@ -88,20 +527,76 @@ pub struct CommanderDamage {
 #[serde(rename_all = "camelCase")]
 pub struct CreateGameRequest {
     pub starting_life: Option<i32>, // Default to 20 if not provided
-                                    // clerk_user_id is now extracted from JWT token
+    // clerk_user_id is now extracted from JWT token
+    pub strict_control: Option<bool>, // Default to true if not provided
+    pub format: Option<String>,       // Preset supplying starting life and max players
+    pub commander_lethal: Option<i32>, // Default to 21 if not provided
+    pub visibility: Option<String>,   // Default to "public" if not provided
+    pub lobby: Option<bool>,          // Start in the lobby until the host starts it; default true
+    pub auto_eliminate: Option<bool>, // Default to true if not provided
+}
+
+/// Settings for a new game, resolved from a `CreateGameRequest`
+#[derive(Debug, Clone, Copy)]
+pub struct NewGame {
+    pub starting_life: i32,
+    pub format: Option<GameFormat>,
+    pub strict_control: bool,
+    pub commander_lethal: i32,
+    pub visibility: GameVisibility,
+    pub lobby: bool,
+    pub auto_eliminate: bool,
+}
+
+// clerk_user_id comes from JWT; the body is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinGameRequest {
+    /// Handicap: start this player on a different life total than the game's
+    pub starting_life_override: Option<i32>,
+    /// Claim a seat color from SEAT_COLORS
+    pub seat_color: Option<String>,
+    /// Needed to join a private game
+    pub join_code: Option<String>,
+}
+
+/// Games to quit at once, e.g. abandoned ones holding the user's open game slots
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaveGamesRequest {
+    pub game_ids: Vec<Uuid>,
 }
 
-// JoinGameRequest is no longer needed - clerk_user_id comes from JWT
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaveGamesResponse {
+    pub left: Vec<Uuid>,   // Requested games the user was in and has now left
+    pub failed: Vec<Uuid>, // Requested games that couldn't be left; retry these
+}
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateLifeRequest {
-    pub player_id: Uuid,
+    /// Omitted when the player is picked by `clerkUserId` in the query
+    pub player_id: Option<Uuid>,
     pub change_amount: i32,
+    pub source_player_id: Option<Uuid>, // The player who caused the change, e.g. the attacker
+    pub reason: Option<String>,         // One of LifeChangeReason, checked on write
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SetLifeRequest {
+    pub player_id: Uuid,
+    pub life: i32,
+    pub expected_version: Option<i64>, // Reject as stale if the player has changed since
+    pub expected_current: Option<i32>, // Reject as a conflict unless life is currently this
+    pub source_player_id: Option<Uuid>, // The player who caused the change, e.g. the attacker
+    pub reason: Option<String>,        // One of LifeChangeReason, checked on write
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateCommanderDamageRequest {
     pub from_player_id: Uuid,
     pub to_player_id: Uuid,
@@ -109,6 +604,51 @@ pub struct UpdateCommanderDamageRequest {
     pub damage_amount: i32, // Amount to add (can be negative)
 }
 
+/// Game ids whose states to fetch together, e.g. for a dashboard
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStatesRequest {
+    pub game_ids: Vec<Uuid>,
+}
+
+/// Several commander damage changes applied together, all or nothing
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCommanderDamageRequest {
+    pub entries: Vec<UpdateCommanderDamageRequest>,
+}
+
+/// One applied change within a bulk commander damage update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommanderDamageChange {
+    pub from_player_id: Uuid,
+    pub to_player_id: Uuid,
+    pub commander_number: i32,
+    pub new_damage: i32,
+    pub damage_amount: i32,
+}
+
+impl CommanderDamageChange {
+    /// Pair each requested batch entry with the damage it resulted in
+    pub fn from_batch(
+        entries: &[UpdateCommanderDamageRequest],
+        updated: &[CommanderDamage],
+    ) -> Vec<Self> {
+        entries
+            .iter()
+            .zip(updated)
+            .map(|(entry, damage)| CommanderDamageChange {
+                from_player_id: entry.from_player_id,
+                to_player_id: entry.to_player_id,
+                commander_number: entry.commander_number,
+                new_damage: damage.damage,
+                damage_amount: entry.damage_amount,
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TogglePartnerRequest {
@@ -116,6 +656,34 @@ pub struct TogglePartnerRequest {
     pub enable_partner: bool,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCommanderNameRequest {
+    pub commander_name: Option<String>,
+    pub partner_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSeatColorRequest {
+    pub color: Option<String>, // null releases the color
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPlayerDeckRequest {
+    pub commander_name: String,
+    pub partner_name: Option<String>,
+    pub color_identity: Option<String>, // Any order or case, e.g. "gwu"; "C" for colorless
+    pub decklist_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetStrictControlRequest {
+    pub strict_control: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EndGameRequest {
@@ -127,6 +695,8 @@ pub struct EndGameRequest {
 #[serde(rename_all = "camelCase")]
 pub struct UserInfo {
     pub clerk_user_id: String,
+    pub display_name: String,
+    pub image_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -136,6 +706,45 @@ pub struct GameState {
     pub players: Vec<PlayerWithUser>,
     pub recent_changes: Vec<LifeChange>,
     pub commander_damage: Vec<CommanderDamage>,
+    pub game_counters: Vec<GameCounter>,
+    #[serde(default)] // Absent from snapshots taken before decks existed
+    pub decks: Vec<PlayerDeck>,
+}
+
+impl GameState {
+    /// Fingerprint of the state clients track most closely, for spotting
+    /// desync: FNV-1a (64-bit, lowercase hex) over each player's
+    /// `id:currentLife:version` sorted by id and joined with `,`, then `|`,
+    /// then each commander damage row's
+    /// `fromPlayerId:toPlayerId:commanderNumber:damage` sorted the same way.
+    /// Clients compute the same hash from their local state.
+    pub fn state_hash(&self) -> String {
+        let mut players: Vec<String> = self
+            .players
+            .iter()
+            .map(|p| format!("{}:{}:{}", p.id, p.current_life, p.version))
+            .collect();
+        players.sort();
+        let mut damage: Vec<String> = self
+            .commander_damage
+            .iter()
+            .map(|cd| {
+                format!(
+                    "{}:{}:{}:{}",
+                    cd.from_player_id, cd.to_player_id, cd.commander_number, cd.damage
+                )
+            })
+            .collect();
+        damage.sort();
+
+        let canonical = format!("{}|{}", players.join(","), damage.join(","));
+        let hash = canonical
+            .bytes()
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            });
+        format!("{hash:016x}")
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -150,6 +759,7 @@ pub struct GameWithPlayers {
     pub game: Game,
     pub players: Vec<Player>,
     pub winner: Option<Player>,
+    pub duration_seconds: i64, // See `Game::duration_seconds`
 }
 
 /// Game with enriched player info (includes display names, avatars, etc.)
@@ -159,6 +769,8 @@ pub struct GameWithPlayersEnriched {
     pub game: Game,
     pub players: Vec<PlayerWithUser>,
     pub winner: Option<PlayerWithUser>,
+    pub decks: Vec<PlayerDeck>,
+    pub duration_seconds: i64, // See `Game::duration_seconds`
 }
 
 #[derive(Debug, Serialize)]
@@ -168,11 +780,38 @@ pub struct GameWithUsers {
     pub users: Vec<UserInfo>, // User info from players
 }
 
-// Result type for game ending operations
-#[derive(Debug, Clone, Serialize)]
+/// One page of games plus the total matching the filter
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GameEndResult {
-    pub winner: Option<Player>,
+pub struct GameListPage {
+    pub games: Vec<GameWithUsers>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// WebSocket delivery health for one game room
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomMetrics {
+    pub game_id: Uuid,
+    pub connections: usize,
+    /// Most connections the room has held at once
+    pub peak_connections: usize,
+    /// Times a slow receiver fell behind and was resynced
+    pub lag_events: u64,
+    /// Broadcast messages those receivers missed
+    pub skipped_messages: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastMetrics {
+    pub channel_capacity: usize,
+    pub rooms: Vec<RoomMetrics>,
+    /// Since startup, including rooms since closed
+    pub total_lag_events: u64,
+    pub total_skipped_messages: u64,
 }
 
 // WebSocket Message Types
@@ -188,6 +827,11 @@ pub enum WebSocketMessage {
         player_id: Uuid,
         new_life: i32,
         change_amount: i32,
+        version: i64,
+        source_player_id: Option<Uuid>,
+        reason: Option<LifeChangeReason>,
+        /// Absent from events logged before it was recorded
+        actor_clerk_user_id: Option<String>,
     },
     PlayerJoined {
         game_id: Uuid,
@@ -197,13 +841,30 @@ pub enum WebSocketMessage {
         game_id: Uuid,
         player_id: Uuid,
     },
+    /// The game just got underway (its first life change)
     GameStarted {
         #[serde(flatten)]
         game_state: GameState,
     },
+    /// Full snapshot for initial connection, explicit requests and resyncs
+    FullState {
+        #[serde(flatten)]
+        game_state: GameState,
+    },
     GameEnded {
         game_id: Uuid,
         winner: Option<PlayerWithUser>,
+        outcome: Option<String>,
+    },
+    GameCancelled {
+        game_id: Uuid,
+    },
+    /// Sent once when a player is knocked out, whatever the cause
+    PlayerEliminated {
+        game_id: Uuid,
+        player_id: Uuid,
+        cause: EliminationCause,
+        version: i64,
     },
     CommanderDamageUpdate {
         game_id: Uuid,
@@ -212,17 +873,213 @@ pub enum WebSocketMessage {
         commander_number: i32,
         new_damage: i32,
         damage_amount: i32,
+        /// Absent from events logged before it was recorded
+        actor_clerk_user_id: Option<String>,
+    },
+    /// Every change from one bulk request, applied atomically
+    CommanderDamageBulkUpdate {
+        game_id: Uuid,
+        changes: Vec<CommanderDamageChange>,
+        actor_clerk_user_id: Option<String>,
     },
     PartnerToggled {
         game_id: Uuid,
         player_id: Uuid,
         has_partner: bool,
     },
+    CommanderNameUpdated {
+        game_id: Uuid,
+        player_id: Uuid,
+        commander_name: Option<String>,
+        partner_name: Option<String>,
+        version: i64,
+    },
+    CommanderCastsUpdated {
+        game_id: Uuid,
+        player_id: Uuid,
+        commander_casts: i32,
+        version: i64,
+    },
+    /// A player attached or replaced their deck, which also sets their
+    /// commander and partner names
+    PlayerDeckUpdated {
+        game_id: Uuid,
+        player_id: Uuid,
+        deck: PlayerDeck,
+        version: i64,
+    },
+    OpeningHandUpdated {
+        game_id: Uuid,
+        player_id: Uuid,
+        mulligans: i32,
+        hand_size: i32,
+        version: i64,
+    },
+    SeatColorUpdated {
+        game_id: Uuid,
+        player_id: Uuid,
+        color: Option<String>,
+        version: i64,
+    },
+    /// Tally of the vote to end the game; it ends once `votes` reaches `needed`
+    EndGameVotes {
+        game_id: Uuid,
+        votes: Vec<Uuid>, // Players who voted to end
+        needed: usize,    // Majority of players still in the game
+    },
+    GameCounterUpdate {
+        game_id: Uuid,
+        name: String,
+        value: i32,
+        reset_each_turn: bool,
+    },
+    /// Periodic fingerprint of the game state; see `GameState::state_hash`
+    StateHash {
+        game_id: Uuid,
+        hash: String,
+    },
+    /// Reply to a request that carried a `requestId`, sent only to its sender
+    Ack {
+        request_id: String,
+        ok: bool,
+        error: Option<AckError>,
+    },
     Error {
         message: String,
     },
 }
 
+/// Newest WebSocket protocol version; clients that don't say get this one
+pub const PROTOCOL_VERSION: u32 = 4;
+/// Oldest protocol version still served
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+impl WebSocketMessage {
+    /// The message with any game state's join code removed, for the event
+    /// log, which is read by more than the room
+    pub fn without_join_code(self) -> Self {
+        match self {
+            WebSocketMessage::GameStarted { mut game_state } => {
+                game_state.game = game_state.game.without_join_code();
+                WebSocketMessage::GameStarted { game_state }
+            }
+            WebSocketMessage::FullState { mut game_state } => {
+                game_state.game = game_state.game.without_join_code();
+                WebSocketMessage::FullState { game_state }
+            }
+            message => message,
+        }
+    }
+
+    /// First protocol version that includes this message type
+    pub fn min_protocol_version(&self) -> u32 {
+        match self {
+            WebSocketMessage::LifeUpdate { .. }
+            | WebSocketMessage::PlayerJoined { .. }
+            | WebSocketMessage::PlayerLeft { .. }
+            | WebSocketMessage::GameStarted { .. }
+            | WebSocketMessage::GameEnded { .. }
+            | WebSocketMessage::CommanderDamageUpdate { .. }
+            | WebSocketMessage::PartnerToggled { .. }
+            | WebSocketMessage::Error { .. } => 1,
+            WebSocketMessage::FullState { .. }
+            | WebSocketMessage::GameCancelled { .. }
+            | WebSocketMessage::PlayerEliminated { .. }
+            | WebSocketMessage::CommanderDamageBulkUpdate { .. }
+            | WebSocketMessage::CommanderNameUpdated { .. }
+            | WebSocketMessage::CommanderCastsUpdated { .. }
+            | WebSocketMessage::OpeningHandUpdated { .. }
+            | WebSocketMessage::GameCounterUpdate { .. }
+            | WebSocketMessage::Ack { .. } => 2,
+            WebSocketMessage::PlayerDeckUpdated { .. }
+            | WebSocketMessage::SeatColorUpdated { .. }
+            | WebSocketMessage::EndGameVotes { .. } => 3,
+            WebSocketMessage::StateHash { .. } => 4,
+        }
+    }
+
+    /// This message as a client speaking `version` understands it: unchanged,
+    /// rewritten as older message types, or dropped (an empty list)
+    pub fn for_protocol(self, version: u32) -> Vec<WebSocketMessage> {
+        if self.min_protocol_version() <= version {
+            return vec![self];
+        }
+
+        let downgraded = match self {
+            // v1 clients took the initial state as a gameStarted
+            WebSocketMessage::FullState { game_state } => {
+                vec![WebSocketMessage::GameStarted { game_state }]
+            }
+            WebSocketMessage::GameCancelled { game_id } => vec![WebSocketMessage::GameEnded {
+                game_id,
+                winner: None,
+                outcome: Some("cancelled".to_string()),
+            }],
+            WebSocketMessage::CommanderDamageBulkUpdate {
+                game_id,
+                changes,
+                actor_clerk_user_id,
+            } => changes
+                .into_iter()
+                .map(|change| WebSocketMessage::CommanderDamageUpdate {
+                    game_id,
+                    from_player_id: change.from_player_id,
+                    to_player_id: change.to_player_id,
+                    commander_number: change.commander_number,
+                    new_damage: change.new_damage,
+                    damage_amount: change.damage_amount,
+                    actor_clerk_user_id: actor_clerk_user_id.clone(),
+                })
+                .collect(),
+            WebSocketMessage::PlayerDeckUpdated {
+                game_id,
+                player_id,
+                deck,
+                version,
+            } => vec![WebSocketMessage::CommanderNameUpdated {
+                game_id,
+                player_id,
+                commander_name: Some(deck.commander_name),
+                partner_name: deck.partner_name,
+                version,
+            }],
+            _ => Vec::new(),
+        };
+
+        // A downgrade may itself be too new for the client
+        downgraded
+            .into_iter()
+            .flat_map(|message| message.for_protocol(version))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AckError {
+    pub code: String,
+    pub message: String,
+}
+
+/// A broadcast message tagged with its position in the game's event log
+#[derive(Debug, Clone, Serialize)]
+pub struct GameEvent {
+    /// Absent for messages that aren't logged, such as full state resyncs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<i64>,
+    #[serde(flatten)]
+    pub message: WebSocketMessage,
+}
+
+/// A WebSocket request plus an optional client-chosen id to correlate its `Ack`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSocketEnvelope {
+    pub request_id: Option<String>,
+    #[serde(flatten)]
+    pub request: WebSocketRequest,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(
     tag = "action",
@@ -233,12 +1090,34 @@ pub enum WebSocketRequest {
     UpdateLife {
         player_id: Uuid,
         change_amount: i32,
+        source_player_id: Option<Uuid>,
+        reason: Option<String>,
+    },
+    /// UpdateLife for the player seated as the given user
+    UpdateLifeByUser {
+        clerk_user_id: String,
+        change_amount: i32,
+        source_player_id: Option<Uuid>,
+        reason: Option<String>,
+    },
+    SetLife {
+        player_id: Uuid,
+        life: i32,
+        expected_version: Option<i64>,
+        expected_current: Option<i32>,
+        source_player_id: Option<Uuid>,
+        reason: Option<String>,
     },
     // JoinGame no longer needed - auto-join happens on WebSocket connection with JWT
     LeaveGame {
         player_id: Uuid,
+        /// Defaults to conceding during an active game, quitting otherwise
+        #[serde(default)]
+        mode: Option<LeaveMode>,
     },
     GetGameState,
+    ForceResync,
+    StartGame,
     EndGame {
         winner_player_id: Option<Uuid>,
     },
@@ -247,6 +1126,10 @@ pub enum WebSocketRequest {
         to_player_id: Uuid,
         commander_number: i32,
         new_damage: i32,
+        expected_damage: Option<i32>,
+    },
+    BulkCommanderDamage {
+        entries: Vec<UpdateCommanderDamageRequest>,
     },
     UpdateCommanderDamage {
         from_player_id: Uuid,
@@ -258,8 +1141,323 @@ pub enum WebSocketRequest {
         player_id: Uuid,
         enable_partner: bool,
     },
+    SetCommanderName {
+        player_id: Uuid,
+        commander_name: Option<String>,
+        partner_name: Option<String>,
+    },
+    CastCommander {
+        player_id: Uuid,
+    },
+    ResetCommanderCasts {
+        player_id: Uuid,
+    },
+    SetMulligans {
+        player_id: Uuid,
+        count: i32,
+    },
+    SetHandSize {
+        player_id: Uuid,
+        size: i32,
+    },
+    VoteEndGame {
+        player_id: Uuid,
+    },
+    SetSeatColor {
+        player_id: Uuid,
+        color: Option<String>,
+    },
+    SetGameCounter {
+        name: String,
+        value: i32,
+        reset_each_turn: Option<bool>,
+    },
+    IncrementGameCounter {
+        name: String,
+        amount: i32,
+    },
+    NextTurn,
 }
 
 // Constants
 pub const DEFAULT_STARTING_LIFE: i32 = 20;
 pub const MAX_PLAYERS_PER_GAME: usize = 8;
+pub const MAX_COMMANDER_NAME_LENGTH: usize = 100;
+pub const MAX_DECKLIST_URL_LENGTH: usize = 500;
+pub const MAX_COUNTER_NAME_LENGTH: usize = 50;
+pub const MAX_BULK_COMMANDER_DAMAGE_ENTRIES: usize = 64;
+pub const MAX_BATCH_GAME_STATES: usize = 20;
+pub const DEFAULT_HAND_SIZE: i32 = 7;
+pub const DEFAULT_RATING: f64 = 1500.0;
+pub const MAX_HAND_SIZE: i32 = 20;
+pub const MAX_MULLIGANS: i32 = 10;
+/// Users ranked in the cached leaderboard; lower-rated users aren't listed
+pub const LEADERBOARD_SIZE: i64 = 1000;
+/// Colors players can claim for their seat, one per player
+pub const SEAT_COLORS: [&str; MAX_PLAYERS_PER_GAME] = [
+    "white", "blue", "black", "red", "green", "purple", "orange", "pink",
+];
+pub const OUTCOME_NO_CONTEST: &str = "no_contest";
+/// Color stats bucket for seats without a recorded color identity
+pub const UNKNOWN_COLOR_IDENTITY: &str = "unknown";
+/// Damage from a single commander that eliminates a player, unless the game
+/// sets its own threshold
+pub const COMMANDER_DAMAGE_LETHAL: i32 = 21;
+pub const MAX_COMMANDER_LETHAL: i32 = 999;
+pub const JOIN_CODE_LENGTH: usize = 6;
+/// Life at or below which a player is eliminated, whatever the life floor
+pub const DEATH_THRESHOLD: i32 = 0;
+
+/// Why a player was eliminated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EliminationCause {
+    Life,
+    CommanderDamage,
+    Poison,
+    Concede,
+}
+
+impl EliminationCause {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EliminationCause::Life => "life",
+            EliminationCause::CommanderDamage => "commander_damage",
+            EliminationCause::Poison => "poison",
+            EliminationCause::Concede => "concede",
+        }
+    }
+}
+
+/// How a player leaves a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaveMode {
+    /// Concede but stay on the board, keeping seat, life history and
+    /// commander damage; only possible in an active game
+    Eliminate,
+    /// Leave the table entirely
+    Quit,
+}
+
+/// Format presets that supply defaults for a new game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameFormat {
+    Commander,
+    Standard,
+    Brawl,
+    TwoHeadedGiant,
+}
+
+impl GameFormat {
+    pub const ALL: [GameFormat; 4] = [
+        GameFormat::Commander,
+        GameFormat::Standard,
+        GameFormat::Brawl,
+        GameFormat::TwoHeadedGiant,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str() == value)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GameFormat::Commander => "commander",
+            GameFormat::Standard => "standard",
+            GameFormat::Brawl => "brawl",
+            GameFormat::TwoHeadedGiant => "two_headed_giant",
+        }
+    }
+
+    pub fn starting_life(self) -> i32 {
+        match self {
+            GameFormat::Commander => 40,
+            GameFormat::Standard => 20,
+            GameFormat::Brawl => 30,
+            GameFormat::TwoHeadedGiant => 30,
+        }
+    }
+
+    pub fn max_players(self) -> usize {
+        match self {
+            GameFormat::Commander => 4,
+            GameFormat::Standard => 2,
+            GameFormat::Brawl => 4,
+            GameFormat::TwoHeadedGiant => 4,
+        }
+    }
+}
+
+/// Why a life total changed, so changes can be grouped reliably across clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifeChangeReason {
+    Combat,
+    Commander,
+    Spell,
+    Ability,
+    Payment,
+    Other,
+}
+
+impl LifeChangeReason {
+    pub const ALL: [LifeChangeReason; 6] = [
+        LifeChangeReason::Combat,
+        LifeChangeReason::Commander,
+        LifeChangeReason::Spell,
+        LifeChangeReason::Ability,
+        LifeChangeReason::Payment,
+        LifeChangeReason::Other,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == value)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LifeChangeReason::Combat => "combat",
+            LifeChangeReason::Commander => "commander",
+            LifeChangeReason::Spell => "spell",
+            LifeChangeReason::Ability => "ability",
+            LifeChangeReason::Payment => "payment",
+            LifeChangeReason::Other => "other",
+        }
+    }
+}
+
+/// Who can find and join a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameVisibility {
+    /// Listed in available games
+    Public,
+    /// Joinable by anyone with the game id, but not listed
+    Unlisted,
+    /// Not listed, and joining takes the game's join code
+    Private,
+}
+
+impl GameVisibility {
+    pub const ALL: [GameVisibility; 3] = [
+        GameVisibility::Public,
+        GameVisibility::Unlisted,
+        GameVisibility::Private,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|visibility| visibility.as_str() == value)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GameVisibility::Public => "public",
+            GameVisibility::Unlisted => "unlisted",
+            GameVisibility::Private => "private",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn players_logged_before_commander_tax_have_no_casts() {
+        let logged = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "gameId": Uuid::new_v4(),
+            "clerkUserId": "user_1",
+            "currentLife": 40,
+            "position": 1,
+            "commanderName": null,
+            "partnerName": null,
+            "version": 3,
+            "eliminationCause": null,
+            "seatColor": null,
+        });
+
+        let player: Player = serde_json::from_value(logged.clone()).unwrap();
+        assert_eq!(player.commander_casts, 0);
+
+        let mut with_user = logged;
+        with_user["displayName"] = "User 1".into();
+        let player: PlayerWithUser = serde_json::from_value(with_user).unwrap();
+        assert_eq!(player.commander_casts, 0);
+    }
+
+    #[test]
+    fn players_logged_before_opening_hands_default_to_seven_cards() {
+        let logged = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "gameId": Uuid::new_v4(),
+            "clerkUserId": "user_1",
+            "currentLife": 40,
+            "position": 1,
+            "commanderName": null,
+            "partnerName": null,
+            "version": 3,
+            "commanderCasts": 0,
+            "eliminationCause": null,
+            "seatColor": null,
+        });
+
+        let player: Player = serde_json::from_value(logged.clone()).unwrap();
+        assert_eq!(player.mulligans, 0);
+        assert_eq!(player.hand_size, DEFAULT_HAND_SIZE);
+
+        let mut with_user = logged;
+        with_user["displayName"] = "User 1".into();
+        let player: PlayerWithUser = serde_json::from_value(with_user).unwrap();
+        assert_eq!(player.mulligans, 0);
+        assert_eq!(player.hand_size, DEFAULT_HAND_SIZE);
+    }
+
+    #[test]
+    fn game_settings_follow_the_game_fields() {
+        // As logged before autoEliminate and settings existed
+        let logged = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "status": "active",
+            "startingLife": 40,
+            "winnerPlayerId": null,
+            "createdAt": "2026-10-16T10:30:00Z",
+            "finishedAt": null,
+            "strictControl": true,
+            "format": "commander",
+            "maxPlayers": 4,
+            "deletedAt": null,
+            "outcome": null,
+        });
+        let mut game: Game = serde_json::from_value(logged).unwrap();
+        game.auto_eliminate = false;
+        game.strict_control = false;
+
+        let json = serde_json::to_value(&game).unwrap();
+        assert_eq!(json["autoEliminate"], false);
+        assert_eq!(
+            json["settings"],
+            serde_json::json!({
+                "startingLife": 40,
+                "maxPlayers": 4,
+                "commanderLethal": COMMANDER_DAMAGE_LETHAL,
+                "format": "commander",
+                "visibility": "public",
+                "strictControl": false,
+                "autoEliminate": false,
+            })
+        );
+
+        // A stale settings object is rebuilt rather than trusted
+        let mut stale = json;
+        stale["settings"]["startingLife"] = 20.into();
+        let read: Game = serde_json::from_value(stale).unwrap();
+        assert_eq!(read.settings().starting_life, 40);
+    }
+}
@@ -1,57 +1,520 @@
-use crate::models::WebSocketMessage;
+use crate::config::Config;
+use crate::database;
+use crate::errors::Result;
+use crate::models::{
+    BroadcastMetrics, GameEvent, GameState, LEADERBOARD_SIZE, LeaderboardEntry, RoomMetrics,
+    WebSocketMessage,
+};
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use dashmap::mapref::one::RefMut;
 use sqlx::SqlitePool;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, broadcast, watch};
 use uuid::Uuid;
 
-pub type Sender = broadcast::Sender<WebSocketMessage>;
-pub type Receiver = broadcast::Receiver<WebSocketMessage>;
+pub type Sender = broadcast::Sender<GameEvent>;
+pub type Receiver = broadcast::Receiver<GameEvent>;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: SqlitePool,
     pub game_rooms: Arc<DashMap<Uuid, GameRoom>>,
+    pub connection_limits: ConnectionLimits,
+    /// Buffered messages per game room before slow receivers start lagging
+    pub channel_capacity: usize,
+    pub config: Config,
+    /// Assembled game states, dropped whenever the game changes
+    pub game_states: GameStateCache,
+    /// Lag counts across all rooms, kept after rooms are cleaned up
+    pub lag_totals: Arc<LagTotals>,
+    /// Flips to true when the server starts shutting down
+    pub shutdown: Arc<watch::Sender<bool>>,
+    /// Ranked users, rebuilt in the background rather than per request
+    pub leaderboard: LeaderboardCache,
+    /// Game creations per user, to stop scripts flooding the lobby
+    pub game_creation_limiter: RateLimiter,
+}
+
+#[derive(Default)]
+pub struct LagTotals {
+    pub lag_events: AtomicU64,
+    pub skipped_messages: AtomicU64,
 }
 
 #[derive(Clone)]
 pub struct GameRoom {
     pub sender: Sender,
+    /// Held while logging and sending a message so sequence order matches send order
+    pub publish_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Open WebSocket connections per clerk_user_id
+    pub connected_users: HashMap<String, usize>,
+    /// Clerk user ids whose connections should close, e.g. after leaving the game
+    pub disconnects: broadcast::Sender<String>,
+    /// Most connections held at once
+    pub peak_connections: usize,
+    /// Times a receiver lagged, and the messages it skipped as a result
+    pub lag_events: u64,
+    pub skipped_messages: u64,
+    /// Open vote to end the game early
+    pub end_votes: EndVotes,
+}
+
+/// A vote lapses this long after it opened, if it hasn't passed by then
+const END_VOTE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Players who voted to end a game, by player id
+#[derive(Debug, Clone, Default)]
+pub struct EndVotes {
+    voters: HashSet<Uuid>,
+    opened_at: Option<Instant>,
+}
+
+impl EndVotes {
+    /// Forget a vote that has been open too long
+    fn expire(&mut self) {
+        if self
+            .opened_at
+            .is_some_and(|opened_at| opened_at.elapsed() >= END_VOTE_TIMEOUT)
+        {
+            *self = Self::default();
+        }
+    }
+}
+
+impl GameRoom {
+    /// Total open WebSocket connections in this room
+    pub fn connection_count(&self) -> usize {
+        self.connected_users.values().sum()
+    }
+}
+
+/// Read-through cache of assembled `GameState`s. Each invalidation bumps the
+/// game's generation so a read that raced a mutation can't cache what it saw.
+#[derive(Clone, Default)]
+pub struct GameStateCache {
+    entries: Arc<DashMap<Uuid, Arc<GameState>>>,
+    generations: Arc<DashMap<Uuid, u64>>,
+}
+
+impl GameStateCache {
+    pub fn get(&self, game_id: Uuid) -> Option<Arc<GameState>> {
+        self.entries.get(&game_id).map(|entry| entry.clone())
+    }
+
+    /// Current generation, to be captured before loading the state from the database
+    pub fn generation(&self, game_id: Uuid) -> u64 {
+        self.generations
+            .get(&game_id)
+            .map_or(0, |generation| *generation)
+    }
+
+    /// Cache a loaded state unless the game was invalidated since `generation`
+    pub fn insert_if_current(&self, game_id: Uuid, generation: u64, game_state: Arc<GameState>) {
+        // Holding the generation entry keeps `invalidate` from interleaving.
+        // A game that was never invalidated has no entry and is at generation 0.
+        let current = match self.generations.entry(game_id) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(_) => 0,
+        };
+        if current == generation {
+            self.entries.insert(game_id, game_state);
+        }
+    }
+
+    pub fn invalidate(&self, game_id: Uuid) {
+        let mut generation = self.generations.entry(game_id).or_default();
+        *generation += 1;
+        self.entries.remove(&game_id);
+    }
+
+    /// Forget a game entirely, e.g. once its room is closed
+    pub fn remove(&self, game_id: Uuid) {
+        self.generations.remove(&game_id);
+        self.entries.remove(&game_id);
+    }
+}
+
+/// The last computed leaderboard. Building it looks up every listed user in
+/// Clerk, so `leaderboard::spawn` rebuilds it periodically and on request.
+#[derive(Clone, Default)]
+pub struct LeaderboardCache {
+    entries: Arc<RwLock<Option<Arc<Vec<LeaderboardEntry>>>>>,
+    stale: Arc<Notify>,
+}
+
+impl LeaderboardCache {
+    pub fn get(&self) -> Option<Arc<Vec<LeaderboardEntry>>> {
+        self.entries.read().unwrap().clone()
+    }
+
+    fn replace(&self, entries: Arc<Vec<LeaderboardEntry>>) {
+        *self.entries.write().unwrap() = Some(entries);
+    }
+
+    /// Ask for a rebuild, e.g. after ratings change. Requests made while a
+    /// rebuild is running are coalesced into one more.
+    pub fn mark_stale(&self) {
+        self.stale.notify_one();
+    }
+
+    /// Resolves once a rebuild has been asked for
+    pub async fn stale(&self) {
+        self.stale.notified().await;
+    }
+}
+
+/// Buckets tracked before full (idle) ones are dropped
+const MAX_IDLE_RATE_LIMIT_BUCKETS: usize = 10_000;
+
+/// Per-user token buckets. Each holds up to a minute's allowance and refills
+/// continuously, so short bursts pass but a steady flood is throttled.
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    buckets: Arc<DashMap<String, TokenBucket>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Take one token from the user's bucket, allowing `per_minute` a minute.
+    /// When the bucket is empty, returns how long until a token is available.
+    pub fn try_acquire(&self, key: &str, per_minute: u32) -> std::result::Result<(), Duration> {
+        self.try_acquire_at(key, per_minute, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        key: &str,
+        per_minute: u32,
+        now: Instant,
+    ) -> std::result::Result<(), Duration> {
+        let capacity = f64::from(per_minute);
+        let per_second = capacity / 60.0;
+
+        if self.buckets.len() > MAX_IDLE_RATE_LIMIT_BUCKETS {
+            self.buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.refilled_at).as_secs_f64() * per_second
+                    < capacity
+            });
+        }
+
+        let mut bucket = self.buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Caps on concurrent WebSocket connections, to protect the broadcast channel
+#[derive(Clone, Copy, Debug)]
+pub struct ConnectionLimits {
+    pub max_per_game: usize,
+    pub max_per_user: usize,
+}
+
+impl ConnectionLimits {
+    /// Load limits from `WS_MAX_CONNECTIONS_PER_GAME` and `WS_MAX_CONNECTIONS_PER_USER`
+    pub fn from_env() -> Self {
+        fn env_or(name: &str, default: usize) -> usize {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        Self {
+            max_per_game: env_or("WS_MAX_CONNECTIONS_PER_GAME", 32),
+            max_per_user: env_or("WS_MAX_CONNECTIONS_PER_USER", 3),
+        }
+    }
+}
+
+/// Why a WebSocket connection was refused
+#[derive(Debug)]
+pub enum ConnectionRejection {
+    GameFull { limit: usize },
+    UserLimit { limit: usize },
+}
+
+impl std::fmt::Display for ConnectionRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectionRejection::GameFull { limit } => {
+                write!(f, "Too many connections to this game (max {limit})")
+            }
+            ConnectionRejection::UserLimit { limit } => {
+                write!(f, "Too many connections for this user (max {limit})")
+            }
+        }
+    }
+}
+
+/// Releases a registered connection slot when dropped
+pub struct ConnectionGuard {
+    state: AppState,
+    game_id: Uuid,
+    clerk_user_id: String,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.state
+            .unregister_connection(self.game_id, &self.clerk_user_id);
+    }
+}
+
+/// Pending disconnect requests per game room
+const DISCONNECT_CHANNEL_CAPACITY: usize = 16;
+
+/// Most cleanup delays a room waits through for someone to see its final message
+const MAX_ROOM_CLEANUP_DELAYS: u32 = 3;
+
+/// Load the broadcast channel capacity from `WS_CHANNEL_CAPACITY` (default 256)
+pub fn channel_capacity_from_env() -> usize {
+    std::env::var("WS_CHANNEL_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&capacity| capacity > 0)
+        .unwrap_or(256)
 }
 
 impl AppState {
-    pub fn new(db: SqlitePool) -> Self {
+    pub fn new(
+        db: SqlitePool,
+        connection_limits: ConnectionLimits,
+        channel_capacity: usize,
+        config: Config,
+    ) -> Self {
         Self {
             db,
             game_rooms: Arc::new(DashMap::new()),
+            connection_limits,
+            channel_capacity,
+            config,
+            game_states: GameStateCache::default(),
+            lag_totals: Arc::default(),
+            shutdown: Arc::new(watch::channel(false).0),
+            leaderboard: LeaderboardCache::default(),
+            game_creation_limiter: RateLimiter::default(),
+        }
+    }
+
+    /// Full game state, served from the cache when warm
+    pub async fn game_state(&self, game_id: Uuid) -> Result<Arc<GameState>> {
+        if let Some(game_state) = self.game_states.get(game_id) {
+            return Ok(game_state);
+        }
+
+        let generation = self.game_states.generation(game_id);
+        let game_state = Arc::new(database::get_game_state_with_users(&self.db, game_id).await?);
+        self.game_states
+            .insert_if_current(game_id, generation, game_state.clone());
+        Ok(game_state)
+    }
+
+    /// The ranked leaderboard, built now if no refresh has finished yet
+    pub async fn leaderboard(&self) -> Result<Arc<Vec<LeaderboardEntry>>> {
+        match self.leaderboard.get() {
+            Some(entries) => Ok(entries),
+            None => self.refresh_leaderboard().await,
+        }
+    }
+
+    /// Rebuild the leaderboard from current ratings and cache it
+    pub async fn refresh_leaderboard(&self) -> Result<Arc<Vec<LeaderboardEntry>>> {
+        let entries = Arc::new(database::get_leaderboard(&self.db, LEADERBOARD_SIZE).await?);
+        self.leaderboard.replace(entries.clone());
+        Ok(entries)
+    }
+
+    /// Full states for several games: cached ones as they are, the rest loaded
+    /// in one batch. Unknown games are left out.
+    pub async fn game_states_for(
+        &self,
+        game_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Arc<GameState>>> {
+        let mut states = HashMap::new();
+        let mut missing = Vec::new();
+        for &game_id in game_ids {
+            match self.game_states.get(game_id) {
+                Some(game_state) => {
+                    states.insert(game_id, game_state);
+                }
+                None => missing.push((game_id, self.game_states.generation(game_id))),
+            }
         }
+        if missing.is_empty() {
+            return Ok(states);
+        }
+
+        let ids: Vec<Uuid> = missing.iter().map(|&(game_id, _)| game_id).collect();
+        let mut loaded = database::get_game_states_with_users(&self.db, &ids).await?;
+        for (game_id, generation) in missing {
+            if let Some(game_state) = loaded.remove(&game_id) {
+                let game_state = Arc::new(game_state);
+                self.game_states
+                    .insert_if_current(game_id, generation, game_state.clone());
+                states.insert(game_id, game_state);
+            }
+        }
+        Ok(states)
     }
 
     /// Get or create a game room atomically to prevent race conditions
     pub fn get_or_create_game_room(&self, game_id: Uuid) -> Sender {
+        self.room_handles(game_id).0
+    }
+
+    fn room_handles(&self, game_id: Uuid) -> (Sender, Arc<tokio::sync::Mutex<()>>) {
+        let room = self.room(game_id);
+        (room.sender.clone(), room.publish_lock.clone())
+    }
+
+    /// The game's room, created if needed. The returned guard holds the room,
+    /// so a concurrent `cleanup_game_room` can't remove it mid-update.
+    fn room(&self, game_id: Uuid) -> RefMut<'_, Uuid, GameRoom> {
         // Use entry API for atomic get-or-insert
-        let room = self.game_rooms.entry(game_id).or_insert_with(|| {
-            let (sender, _) = broadcast::channel(100);
+        self.game_rooms.entry(game_id).or_insert_with(|| {
+            let (sender, _) = broadcast::channel(self.channel_capacity);
+            let (disconnects, _) = broadcast::channel(DISCONNECT_CHANNEL_CAPACITY);
             tracing::info!("Created new WebSocket room for game {}", game_id);
-            GameRoom { sender }
-        });
-        room.sender.clone()
+            GameRoom {
+                sender,
+                publish_lock: Arc::new(tokio::sync::Mutex::new(())),
+                connected_users: HashMap::new(),
+                disconnects,
+                peak_connections: 0,
+                lag_events: 0,
+                skipped_messages: 0,
+                end_votes: EndVotes::default(),
+            }
+        })
     }
 
-    /// Broadcast a message to all clients in a game room
-    pub fn broadcast_to_game(&self, game_id: Uuid, message: WebSocketMessage) {
-        let sender = self.get_or_create_game_room(game_id);
+    /// Reserve a connection slot for a user in a game room, enforcing the
+    /// per-game and per-user limits atomically
+    pub fn register_connection(
+        &self,
+        game_id: Uuid,
+        clerk_user_id: &str,
+    ) -> std::result::Result<ConnectionGuard, ConnectionRejection> {
+        let mut room = self.room(game_id);
+
+        let limits = self.connection_limits;
+        if room.connection_count() >= limits.max_per_game {
+            return Err(ConnectionRejection::GameFull {
+                limit: limits.max_per_game,
+            });
+        }
+
+        let user_connections = room
+            .connected_users
+            .get(clerk_user_id)
+            .copied()
+            .unwrap_or(0);
+        if user_connections >= limits.max_per_user {
+            return Err(ConnectionRejection::UserLimit {
+                limit: limits.max_per_user,
+            });
+        }
+        room.connected_users
+            .insert(clerk_user_id.to_string(), user_connections + 1);
+        room.peak_connections = room.peak_connections.max(room.connection_count());
+
+        Ok(ConnectionGuard {
+            state: self.clone(),
+            game_id,
+            clerk_user_id: clerk_user_id.to_string(),
+        })
+    }
+
+    fn unregister_connection(&self, game_id: Uuid, clerk_user_id: &str) {
+        // The room may already have been cleaned up after the game ended
+        if let Some(mut room) = self.game_rooms.get_mut(&game_id)
+            && let Some(count) = room.connected_users.get_mut(clerk_user_id)
+        {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                room.connected_users.remove(clerk_user_id);
+            }
+        }
+    }
 
-        match sender.send(message) {
+    /// Log a message to the game's event log and broadcast it to all clients in the room,
+    /// returning how many connections it reached
+    pub async fn broadcast_to_game(&self, game_id: Uuid, message: WebSocketMessage) -> usize {
+        // Every mutation is announced here, so this is where cached state goes
+        // stale. Full state and state hash messages only republish what was
+        // read, and are not events worth replaying.
+        let is_event = !matches!(
+            message,
+            WebSocketMessage::FullState { .. } | WebSocketMessage::StateHash { .. }
+        );
+        self.publish(game_id, message, is_event).await
+    }
+
+    /// Broadcast a full state that replaces what clients had, such as after a
+    /// reset or snapshot restore. Unlike other full states it is logged, so a
+    /// client catching up from an earlier `seq` rebuilds from it rather than
+    /// applying later events to the state it had.
+    pub async fn broadcast_resync(&self, game_id: Uuid, game_state: GameState) -> usize {
+        self.publish(game_id, WebSocketMessage::FullState { game_state }, true)
+            .await
+    }
+
+    async fn publish(&self, game_id: Uuid, message: WebSocketMessage, is_event: bool) -> usize {
+        if is_event {
+            self.game_states.invalidate(game_id);
+        }
+
+        let (sender, publish_lock) = self.room_handles(game_id);
+        let _publishing = publish_lock.lock().await;
+
+        let seq = if is_event {
+            match database::append_game_event(&self.db, game_id, &message).await {
+                Ok(seq) => Some(seq),
+                Err(e) => {
+                    tracing::error!("Failed to log event for game {}: {:?}", game_id, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        match sender.send(GameEvent { seq, message }) {
             Ok(receiver_count) => {
                 tracing::info!(
                     "Message broadcast successful to {} receivers in game {}",
                     receiver_count,
                     game_id
                 );
+                receiver_count
             }
-            Err(e) => {
-                tracing::warn!("No active receivers for game {}: {:?}", game_id, e);
+            // The only way a broadcast send fails, and routine: a room exists
+            // from game creation before anyone connects. The event is still
+            // logged above for replay.
+            Err(broadcast::error::SendError(_)) => {
+                tracing::debug!("No connected receivers for game {}", game_id);
+                0
             }
         }
     }
@@ -62,8 +525,162 @@ impl AppState {
         sender.subscribe()
     }
 
-    /// Clean up a game room when the game ends
+    /// Count a receiver in a game falling `skipped` messages behind
+    pub fn record_lag(&self, game_id: Uuid, skipped: u64) {
+        self.lag_totals.lag_events.fetch_add(1, Ordering::Relaxed);
+        self.lag_totals
+            .skipped_messages
+            .fetch_add(skipped, Ordering::Relaxed);
+
+        let (lag_events, skipped_messages) = match self.game_rooms.get_mut(&game_id) {
+            Some(mut room) => {
+                room.lag_events += 1;
+                room.skipped_messages += skipped;
+                (room.lag_events, room.skipped_messages)
+            }
+            None => (1, skipped),
+        };
+        tracing::warn!(
+            %game_id,
+            skipped,
+            lag_events,
+            skipped_messages,
+            capacity = self.channel_capacity,
+            "WebSocket receiver lagged, sending full state"
+        );
+    }
+
+    /// Number of open rooms and of distinct users connected across them,
+    /// counted in one pass so each room's shard is only locked briefly
+    pub fn room_counts(&self) -> (usize, usize) {
+        self.game_rooms.iter().fold((0, 0), |(rooms, users), room| {
+            (rooms + 1, users + room.connected_users.len())
+        })
+    }
+
+    /// Connection and lag figures for every open room
+    pub fn broadcast_metrics(&self) -> BroadcastMetrics {
+        let mut rooms: Vec<RoomMetrics> = self
+            .game_rooms
+            .iter()
+            .map(|room| RoomMetrics {
+                game_id: *room.key(),
+                connections: room.connection_count(),
+                peak_connections: room.peak_connections,
+                lag_events: room.lag_events,
+                skipped_messages: room.skipped_messages,
+            })
+            .collect();
+        rooms.sort_by_key(|room| std::cmp::Reverse(room.skipped_messages));
+
+        BroadcastMetrics {
+            channel_capacity: self.channel_capacity,
+            rooms,
+            total_lag_events: self.lag_totals.lag_events.load(Ordering::Relaxed),
+            total_skipped_messages: self.lag_totals.skipped_messages.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Record a player's vote to end the game, opening a new vote if none is
+    /// running or the last one lapsed
+    pub fn cast_end_vote(&self, game_id: Uuid, player_id: Uuid) {
+        let mut room = self.room(game_id);
+        let votes = &mut room.end_votes;
+        votes.expire();
+        votes.opened_at.get_or_insert_with(Instant::now);
+        votes.voters.insert(player_id);
+    }
+
+    /// Players currently voting to end the game; empty when no vote is open
+    pub fn end_voters(&self, game_id: Uuid) -> HashSet<Uuid> {
+        match self.game_rooms.get_mut(&game_id) {
+            Some(mut room) => {
+                room.end_votes.expire();
+                room.end_votes.voters.clone()
+            }
+            None => HashSet::new(),
+        }
+    }
+
+    /// Drop a departing player's vote. Returns whether a vote is still open.
+    pub fn withdraw_end_vote(&self, game_id: Uuid, player_id: Uuid) -> bool {
+        match self.game_rooms.get_mut(&game_id) {
+            Some(mut room) => {
+                room.end_votes.expire();
+                room.end_votes.voters.remove(&player_id);
+                !room.end_votes.voters.is_empty()
+            }
+            None => false,
+        }
+    }
+
+    /// Close the vote. Returns false if it was already closed, so only one of
+    /// several votes passing at once goes on to end the game.
+    pub fn close_end_vote(&self, game_id: Uuid) -> bool {
+        match self.game_rooms.get_mut(&game_id) {
+            Some(mut room) => {
+                let was_open = !room.end_votes.voters.is_empty();
+                room.end_votes = EndVotes::default();
+                was_open
+            }
+            None => false,
+        }
+    }
+
+    /// Tell every open connection the server is going away
+    pub fn begin_shutdown(&self) {
+        self.shutdown.send_replace(true);
+    }
+
+    /// Get a receiver that sees `true` once the server is shutting down
+    pub fn shutdown_receiver(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    /// Get a receiver for requests to close a user's connections
+    pub fn get_disconnect_receiver(&self, game_id: Uuid) -> broadcast::Receiver<String> {
+        self.room(game_id).disconnects.subscribe()
+    }
+
+    /// Close every open connection (all tabs) a user has to a game
+    pub fn disconnect_user(&self, game_id: Uuid, clerk_user_id: &str) {
+        if let Some(room) = self.game_rooms.get(&game_id)
+            && room.connected_users.contains_key(clerk_user_id)
+        {
+            tracing::info!("Disconnecting user {} from game {}", clerk_user_id, game_id);
+            let _ = room.disconnects.send(clerk_user_id.to_string());
+        }
+    }
+
+    /// Close a game's room once its final message (game ended or cancelled)
+    /// has had `ROOM_CLEANUP_DELAY_SECS` to arrive. If that message reached
+    /// nobody, keep the room for up to `MAX_ROOM_CLEANUP_DELAYS` delays while
+    /// no one is connected. Does nothing if the room is closed in the meantime.
+    pub fn schedule_room_cleanup(&self, game_id: Uuid, final_message_receivers: usize) {
+        let state = self.clone();
+        let delay = Duration::from_secs(self.config.room_cleanup_delay_secs as u64);
+        tokio::spawn(async move {
+            let mut delivered = final_message_receivers > 0;
+            for _ in 0..MAX_ROOM_CLEANUP_DELAYS {
+                tokio::time::sleep(delay).await;
+                let Some(connections) = state
+                    .game_rooms
+                    .get(&game_id)
+                    .map(|room| room.connection_count())
+                else {
+                    return;
+                };
+                delivered |= connections > 0;
+                if delivered {
+                    break;
+                }
+            }
+            state.cleanup_game_room(game_id);
+        });
+    }
+
     pub fn cleanup_game_room(&self, game_id: Uuid) {
+        self.game_states.remove(game_id);
         if let Some((_, _)) = self.game_rooms.remove(&game_id) {
             // Room will be dropped, closing all receivers
             tracing::info!("Cleaned up WebSocket room for game {}", game_id);
@@ -72,3 +689,109 @@ impl AppState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_buckets_throttle_bursts_and_refill() {
+        let limiter = RateLimiter::default();
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            assert!(limiter.try_acquire_at("user_1", 5, start).is_ok());
+        }
+        let wait = limiter.try_acquire_at("user_1", 5, start).unwrap_err();
+        assert_eq!(wait.as_secs_f64().round(), 12.0);
+
+        // Other users have their own bucket
+        assert!(limiter.try_acquire_at("user_2", 5, start).is_ok());
+
+        let almost = start + Duration::from_secs(11);
+        assert!(limiter.try_acquire_at("user_1", 5, almost).is_err());
+        let refilled = start + Duration::from_secs(13);
+        assert!(limiter.try_acquire_at("user_1", 5, refilled).is_ok());
+        assert!(limiter.try_acquire_at("user_1", 5, refilled).is_err());
+    }
+
+    #[test]
+    fn reading_a_generation_does_not_track_the_game() {
+        let cache = GameStateCache::default();
+        let game_id = Uuid::new_v4();
+
+        assert_eq!(cache.generation(game_id), 0);
+        assert!(cache.generations.is_empty());
+
+        cache.invalidate(game_id);
+        assert_eq!(cache.generation(game_id), 1);
+
+        cache.remove(game_id);
+        assert_eq!(cache.generation(game_id), 0);
+        assert!(cache.generations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn connections_past_the_limits_are_refused_until_a_slot_frees() {
+        let state_with = |max_per_game: usize, max_per_user: usize| async move {
+            AppState::new(
+                crate::test_support::pool().await,
+                ConnectionLimits {
+                    max_per_game,
+                    max_per_user,
+                },
+                16,
+                crate::config::Config::default(),
+            )
+        };
+        let game_id = Uuid::new_v4();
+
+        let state = state_with(1, 3).await;
+        let first = state.register_connection(game_id, "user_1").unwrap();
+        assert!(matches!(
+            state.register_connection(game_id, "user_2"),
+            Err(ConnectionRejection::GameFull { limit: 1 })
+        ));
+        drop(first);
+        let _second = state.register_connection(game_id, "user_2").unwrap();
+
+        let state = state_with(32, 1).await;
+        let first = state.register_connection(game_id, "user_1").unwrap();
+        assert!(matches!(
+            state.register_connection(game_id, "user_1"),
+            Err(ConnectionRejection::UserLimit { limit: 1 })
+        ));
+        // The limit is per user, so others still get in
+        let _other = state.register_connection(game_id, "user_2").unwrap();
+        drop(first);
+        let _again = state.register_connection(game_id, "user_1").unwrap();
+    }
+
+    #[tokio::test]
+    async fn connections_register_in_a_fresh_room_after_cleanup() {
+        let state = crate::test_support::app_state().await;
+        let game_id = Uuid::new_v4();
+
+        let first = state.register_connection(game_id, "user_1").unwrap();
+        state.cleanup_game_room(game_id);
+        drop(first);
+
+        let _second = state.register_connection(game_id, "user_1").unwrap();
+        let room = state.game_rooms.get(&game_id).expect("room is recreated");
+        assert_eq!(room.connected_users.get("user_1"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn disconnect_receivers_survive_cleanup() {
+        let state = crate::test_support::app_state().await;
+        let game_id = Uuid::new_v4();
+
+        state.get_disconnect_receiver(game_id);
+        state.cleanup_game_room(game_id);
+
+        let mut receiver = state.get_disconnect_receiver(game_id);
+        let _connection = state.register_connection(game_id, "user_1").unwrap();
+        state.disconnect_user(game_id, "user_1");
+        assert_eq!(receiver.try_recv().unwrap(), "user_1");
+    }
+}
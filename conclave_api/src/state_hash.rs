@@ -0,0 +1,49 @@
+use crate::models::WebSocketMessage;
+use crate::state::AppState;
+use std::time::Duration;
+use tracing::{debug, error};
+
+/// Load the broadcast interval from `STATE_HASH_INTERVAL_SECS`; unset or 0
+/// (the default) leaves state hashes off
+pub fn interval_from_env() -> Option<Duration> {
+    std::env::var("STATE_HASH_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&secs: &u64| secs > 0)
+        .map(Duration::from_secs)
+}
+
+/// Spawn the state hash loop; it runs for the lifetime of the server. On
+/// every tick each room with someone connected gets a `stateHash` of the
+/// current game state, which clients compare against their own.
+pub fn spawn(state: AppState, interval: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+
+            let game_ids: Vec<_> = state
+                .game_rooms
+                .iter()
+                .filter(|room| room.connection_count() > 0)
+                .map(|room| *room.key())
+                .collect();
+
+            for game_id in game_ids {
+                match state.game_state(game_id).await {
+                    Ok(game_state) => {
+                        let hash = game_state.state_hash();
+                        state
+                            .broadcast_to_game(
+                                game_id,
+                                WebSocketMessage::StateHash { game_id, hash },
+                            )
+                            .await;
+                    }
+                    Err(e) => error!("Failed to hash state of game {}: {:?}", game_id, e),
+                }
+            }
+            debug!("Sent state hashes");
+        }
+    });
+}
@@ -0,0 +1,110 @@
+use crate::{
+    database,
+    errors::Result,
+    models::{OUTCOME_NO_CONTEST, WebSocketMessage},
+    state::AppState,
+};
+use anyhow::bail;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Settings for the background job that ends forgotten games
+#[derive(Clone, Copy, Debug)]
+pub struct SweeperConfig {
+    /// How often to look for stale games
+    pub interval: Duration,
+    /// Inactivity after which an active game is ended as a no contest
+    pub stale_after: chrono::Duration,
+}
+
+impl SweeperConfig {
+    /// Load settings from `STALE_GAME_SWEEP_MINUTES` and `STALE_GAME_HOURS`,
+    /// failing on values that aren't positive integers
+    pub fn from_env() -> anyhow::Result<Self> {
+        fn env_or(name: &str, default: u64) -> anyhow::Result<u64> {
+            positive_or(name, std::env::var(name).ok(), default)
+        }
+
+        Ok(Self {
+            interval: Duration::from_secs(env_or("STALE_GAME_SWEEP_MINUTES", 15)? * 60),
+            stale_after: chrono::Duration::hours(env_or("STALE_GAME_HOURS", 12)? as i64),
+        })
+    }
+}
+
+/// `value` as a positive integer, or `default` when unset. Zero would make
+/// the sweep interval panic, so it's rejected along with anything unparsable.
+fn positive_or(name: &str, value: Option<String>, default: u64) -> anyhow::Result<u64> {
+    let Some(value) = value else {
+        return Ok(default);
+    };
+    match value.trim().parse() {
+        Ok(0) | Err(_) => bail!("{name} must be a positive integer, got '{value}'"),
+        Ok(parsed) => Ok(parsed),
+    }
+}
+
+/// Spawn the sweeper loop; it runs for the lifetime of the server
+pub fn spawn(state: AppState, config: SweeperConfig) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sweep_stale_games(&state, config.stale_after).await {
+                error!("Stale game sweep failed: {:?}", e);
+            }
+            match database::purge_expired_idempotency_keys(&state.db).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} expired idempotency keys", purged),
+                Err(e) => error!("Idempotency key purge failed: {:?}", e),
+            }
+        }
+    });
+}
+
+async fn sweep_stale_games(state: &AppState, stale_after: chrono::Duration) -> Result<()> {
+    let cutoff = chrono::Utc::now() - stale_after;
+    let game_ids = database::get_stale_game_ids(&state.db, cutoff).await?;
+
+    for game_id in game_ids {
+        // Skip games that picked up activity or were ended since the scan
+        if !database::end_stale_game(&state.db, game_id, cutoff).await? {
+            continue;
+        }
+
+        info!("Ended stale game {} as no contest", game_id);
+
+        let receivers = state
+            .broadcast_to_game(
+                game_id,
+                WebSocketMessage::GameEnded {
+                    game_id,
+                    winner: None,
+                    outcome: Some(OUTCOME_NO_CONTEST.to_string()),
+                },
+            )
+            .await;
+        state.schedule_room_cleanup(game_id, receivers);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweep_settings_must_be_positive() {
+        assert_eq!(positive_or("STALE_GAME_HOURS", None, 12).unwrap(), 12);
+        assert_eq!(
+            positive_or("STALE_GAME_HOURS", Some(" 3 ".into()), 12).unwrap(),
+            3
+        );
+
+        for bad in ["0", "-5", "soon", ""] {
+            let e = positive_or("STALE_GAME_SWEEP_MINUTES", Some(bad.into()), 15).unwrap_err();
+            assert!(e.to_string().contains("STALE_GAME_SWEEP_MINUTES"));
+        }
+    }
+}
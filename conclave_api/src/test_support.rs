@@ -0,0 +1,65 @@
+//! Shared setup for tests: fresh in-memory databases, app state, games and
+//! tokens the development-mode Clerk client accepts
+
+use crate::{
+    clerk::{self, ClerkClient},
+    config::Config,
+    database,
+    models::{Game, GameVisibility, NewAuditEntry, NewGame},
+    state::{AppState, ConnectionLimits},
+};
+use sqlx::SqlitePool;
+
+/// A new, empty database with migrations applied
+pub async fn pool() -> SqlitePool {
+    database::connect("sqlite::memory:")
+        .await
+        .expect("in-memory database opens")
+}
+
+/// An active, public game created (and joined) by `creator`
+pub async fn create_game(pool: &SqlitePool, creator: &str) -> Game {
+    let settings = NewGame {
+        starting_life: 40,
+        format: None,
+        strict_control: true,
+        commander_lethal: 21,
+        visibility: GameVisibility::Public,
+        lobby: false,
+        auto_eliminate: true,
+    };
+    let (game, _) = database::create_game(pool, settings, creator, None, 1, |_| {
+        audit(creator, "create_game")
+    })
+    .await
+    .expect("game is created");
+    game
+}
+
+/// An audit entry for a mutation under test, with an empty payload
+pub fn audit(actor: &str, action: &'static str) -> NewAuditEntry {
+    NewAuditEntry::new(actor, action, serde_json::json!({}))
+}
+
+/// App state on its own in-memory database, with default limits
+pub async fn app_state() -> AppState {
+    app_state_with_config(Config::default()).await
+}
+
+pub async fn app_state_with_config(config: Config) -> AppState {
+    AppState::new(
+        pool().await,
+        ConnectionLimits {
+            max_per_game: 32,
+            max_per_user: 3,
+        },
+        256,
+        config,
+    )
+}
+
+/// A bearer token for `user_id`, valid for an hour
+pub fn token(user_id: &str) -> String {
+    ClerkClient::init_dev_mode();
+    clerk::test_token(user_id, "dev-mode", 3600)
+}
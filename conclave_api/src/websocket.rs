@@ -1,20 +1,30 @@
 use crate::{
+    achievements,
     clerk::{self, ClerkUser},
     database,
     errors::{ApiError, Result},
-    models::{WebSocketMessage, WebSocketRequest},
+    models::{
+        AckError, CommanderDamageChange, EliminationCause, GameEvent, GameState, JoinGameRequest,
+        LeaveMode, MAX_BULK_COMMANDER_DAMAGE_ENTRIES, MIN_PROTOCOL_VERSION, NewAuditEntry,
+        PROTOCOL_VERSION, Player, PlayerWithUser, UpdateCommanderDamageRequest, WebSocketEnvelope,
+        WebSocketMessage, WebSocketRequest,
+    },
     state::AppState,
 };
 use axum::{
     extract::{
         Query, State, WebSocketUpgrade,
-        ws::{Message, WebSocket},
+        ws::{CloseFrame, Message, WebSocket, close_code},
     },
     response::IntoResponse,
 };
-use futures::{sink::SinkExt, stream::StreamExt};
-use serde::Deserialize;
-use tracing::{debug, error, info};
+use futures::{
+    sink::{Sink, SinkExt},
+    stream::StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tracing::{Instrument, debug, error, info, info_span, warn};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -23,8 +33,123 @@ pub struct WebSocketQuery {
     pub game_id: Uuid,
     /// JWT token for authentication
     pub token: String,
+    /// Sequence number of the last event seen before a reconnect; missed
+    /// events are replayed instead of sending a full state
+    #[serde(alias = "last_seq")]
+    pub last_seq: Option<i64>,
+    /// Protocol version the client speaks; the latest when omitted
+    #[serde(alias = "protocol_version")]
+    pub protocol_version: Option<u32>,
+    /// Stream the event log of a finished game, then close
+    #[serde(default)]
+    pub replay: bool,
+    /// With `replay`, wait between events as long as they originally took
+    #[serde(default)]
+    pub realtime: bool,
+    /// Join code, when connecting to a private game the user hasn't joined
+    #[serde(alias = "join_code")]
+    pub join_code: Option<String>,
+    /// How server messages are encoded; JSON when omitted
+    #[serde(default)]
+    pub encoding: Encoding,
+}
+
+/// Wire encoding for server messages on one connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    /// Text frames holding JSON
+    #[default]
+    Json,
+    /// Binary frames holding MessagePack, with the same field names as JSON
+    Msgpack,
+}
+
+impl Encoding {
+    /// Serialize `value` into a frame of this encoding
+    fn frame<T: Serialize>(self, value: &T) -> Result<Message> {
+        match self {
+            Encoding::Json => {
+                let text =
+                    serde_json::to_string(value).map_err(|e| ApiError::Internal(e.into()))?;
+                Ok(Message::Text(text.into()))
+            }
+            Encoding::Msgpack => {
+                // Named fields and human readable ids and timestamps, so the
+                // payload matches its JSON form
+                let mut bytes = Vec::new();
+                let mut serializer = rmp_serde::Serializer::new(&mut bytes)
+                    .with_struct_map()
+                    .with_human_readable();
+                value
+                    .serialize(&mut serializer)
+                    .map_err(|e| ApiError::Internal(e.into()))?;
+                Ok(Message::Binary(bytes.into()))
+            }
+        }
+    }
+}
+
+/// Messages were dropped for a slow receiver; resync it with the full state
+/// rather than leave the client desynced
+async fn lag_resync(state: &AppState, game_id: Uuid, skipped: u64) -> Option<GameEvent> {
+    state.record_lag(game_id, skipped);
+    match state.game_state(game_id).await {
+        Ok(game_state) => Some(GameEvent {
+            seq: None,
+            message: WebSocketMessage::FullState {
+                game_state: GameState::clone(&game_state),
+            },
+        }),
+        Err(e) => {
+            error!("Failed to load game state for resync: {:?}", e);
+            None
+        }
+    }
+}
+
+// Close codes beyond the standard ones (1001 going away, 1008 policy,
+// 1011 server error, 1013 try again later). See WEBSOCKET_PROTOCOL.md.
+/// The game has ended or been cancelled; reconnecting won't help
+const CLOSE_GAME_NOT_ACTIVE: u16 = 4001;
+/// The user was removed from the game, e.g. by leaving it
+const CLOSE_REMOVED: u16 = 4002;
+
+/// Close code for a connection refused because of `error`
+fn close_code_for(error: &ApiError) -> u16 {
+    match error {
+        ApiError::GameNotActive | ApiError::GameFinished(_) => CLOSE_GAME_NOT_ACTIVE,
+        ApiError::Database(_) | ApiError::Internal(_) => close_code::ERROR,
+        _ => close_code::POLICY,
+    }
+}
+
+/// Send an error message, then close with `code` and the same reason
+async fn reject<S>(sink: &mut S, encoding: Encoding, code: u16, reason: String)
+where
+    S: Sink<Message> + Unpin,
+{
+    let error_msg = WebSocketMessage::Error {
+        message: reason.clone(),
+    };
+    if let Ok(frame) = encoding.frame(&error_msg) {
+        let _ = sink.send(frame).await;
+    }
+    let _ = sink
+        .send(Message::Close(Some(CloseFrame {
+            code,
+            reason: reason.into(),
+        })))
+        .await;
 }
 
+/// Most events replayed on reconnect; clients further behind get a full state
+const MAX_REPLAY_EVENTS: i64 = 500;
+
+/// Longest pause between events in a realtime replay, so idle stretches of a
+/// game don't stall the stream
+const MAX_REPLAY_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     Query(params): Query<WebSocketQuery>,
@@ -32,6 +157,21 @@ pub async fn websocket_handler(
 ) -> impl IntoResponse {
     info!("WebSocket connection attempt - Game: {}", params.game_id);
 
+    let protocol_version = params.protocol_version.unwrap_or(PROTOCOL_VERSION);
+    let encoding = params.encoding;
+    if !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&protocol_version) {
+        info!(
+            "Refusing WebSocket for game {}: unsupported protocol version {}",
+            params.game_id, protocol_version
+        );
+        return ws.on_upgrade(move |mut socket| async move {
+            let reason = format!(
+                "Unsupported protocol version {protocol_version}; supported versions are {MIN_PROTOCOL_VERSION} to {PROTOCOL_VERSION}"
+            );
+            reject(&mut socket, encoding, close_code::PROTOCOL, reason).await;
+        });
+    }
+
     // Validate JWT token and get user info
     let user = match clerk::validate_and_get_user(&params.token).await {
         Ok(user) => user,
@@ -39,28 +179,68 @@ pub async fn websocket_handler(
             error!("WebSocket auth failed: {:?}", e);
             // Return an error response before upgrading
             return ws.on_upgrade(move |mut socket| async move {
-                let error_msg = WebSocketMessage::Error {
-                    message: "Authentication failed".to_string(),
-                };
-                if let Ok(msg) = serde_json::to_string(&error_msg) {
-                    let _ = socket.send(Message::Text(msg.into())).await;
-                }
-                let _ = socket.close().await;
+                reject(
+                    &mut socket,
+                    encoding,
+                    close_code::POLICY,
+                    "Authentication failed".to_string(),
+                )
+                .await;
             });
         }
     };
 
     info!(
-        "WebSocket authenticated - Game: {}, User: {} ({})",
+        "WebSocket authenticated - Game: {}, User: {} ({}), protocol v{}",
         params.game_id,
         user.id,
-        user.display_name()
+        user.display_name(),
+        protocol_version
     );
 
-    ws.on_upgrade(move |socket| handle_socket(socket, params.game_id, user, state))
+    // The upgraded socket outlives the request span, so it gets its own
+    let span = info_span!("ws", game_id = %params.game_id, user_id = %user.id);
+
+    if params.replay {
+        return ws.on_upgrade(move |socket| {
+            handle_replay_socket(
+                socket,
+                params.game_id,
+                params.realtime,
+                protocol_version,
+                encoding,
+                state,
+            )
+            .instrument(span)
+        });
+    }
+
+    ws.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            params.game_id,
+            params.last_seq,
+            protocol_version,
+            encoding,
+            params.join_code,
+            user,
+            state,
+        )
+        .instrument(span)
+    })
 }
 
-async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state: AppState) {
+#[allow(clippy::too_many_arguments)]
+async fn handle_socket(
+    socket: WebSocket,
+    game_id: Uuid,
+    last_seq: Option<i64>,
+    protocol_version: u32,
+    encoding: Encoding,
+    join_code: Option<String>,
+    user: ClerkUser,
+    state: AppState,
+) {
     let (mut sender, mut receiver) = socket.split();
     let clerk_user_id = user.id.clone();
 
@@ -68,25 +248,34 @@ async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state:
     let verification_result = verify_game(&state, game_id).await;
     if let Err(e) = verification_result {
         error!("WebSocket verification failed: {:?}", e);
-        let error_msg = WebSocketMessage::Error {
-            message: e.to_string(),
-        };
-        if let Ok(msg) = serde_json::to_string(&error_msg) {
-            let _ = sender.send(Message::Text(msg.into())).await;
-        }
+        reject(&mut sender, encoding, close_code_for(&e), e.to_string()).await;
         return;
     }
 
+    // Enforce connection limits; the slot is released when the guard drops
+    let _connection_guard = match state.register_connection(game_id, &clerk_user_id) {
+        Ok(guard) => guard,
+        Err(rejection) => {
+            info!(
+                "Refusing WebSocket for user {} in game {}: {}",
+                clerk_user_id, game_id, rejection
+            );
+            reject(
+                &mut sender,
+                encoding,
+                close_code::AGAIN,
+                rejection.to_string(),
+            )
+            .await;
+            return;
+        }
+    };
+
     // Add user to the game if they are not part of it already
-    let add_user_result = add_user_to_game(&state, game_id, &clerk_user_id, &user).await;
+    let add_user_result = add_user_to_game(&state, game_id, &clerk_user_id, &user, join_code).await;
     if let Err(e) = add_user_result {
         error!("Failed to add user to game: {:?}", e);
-        let error_msg = WebSocketMessage::Error {
-            message: e.to_string(),
-        };
-        if let Ok(msg) = serde_json::to_string(&error_msg) {
-            let _ = sender.send(Message::Text(msg.into())).await;
-        }
+        reject(&mut sender, encoding, close_code_for(&e), e.to_string()).await;
         return;
     }
 
@@ -99,23 +288,117 @@ async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state:
 
     // Get receiver for game room messages - this will create the room if it doesn't exist
     let mut game_receiver = state.get_game_receiver(game_id);
+    let mut disconnect_receiver = state.get_disconnect_receiver(game_id);
+    let mut shutdown_receiver = state.shutdown_receiver();
+
+    // Catch a reconnecting client up on what it missed, or send the full state.
+    // We subscribed first, so anything broadcast meanwhile is queued; events
+    // already replayed are skipped when they come through the live stream.
+    let replayed = match last_seq {
+        Some(last_seq) => {
+            match replay_missed_events(
+                &mut sender,
+                &state,
+                game_id,
+                last_seq,
+                protocol_version,
+                encoding,
+            )
+            .await
+            {
+                Ok(replayed) => replayed,
+                Err(e) => {
+                    error!("Failed to replay missed events: {:?}", e);
+                    return;
+                }
+            }
+        }
+        None => None,
+    };
+    let replayed_through = match replayed {
+        Some(seq) => Some(seq),
+        None => {
+            if let Err(e) =
+                send_initial_game_state(&mut sender, &state, game_id, protocol_version, encoding)
+                    .await
+            {
+                error!("Failed to send initial game state: {:?}", e);
+                return;
+            }
+            None
+        }
+    };
 
-    // Send initial game state
-    if let Err(e) = send_initial_game_state(&mut sender, &state, game_id).await {
-        error!("Failed to send initial game state: {:?}", e);
-        return;
-    }
+    // Private channel for replies meant only for this connection (acks, errors)
+    let (reply_sender, mut reply_receiver) = mpsc::channel::<WebSocketMessage>(32);
+
+    // Handle incoming and outgoing messages. Resolves to true if the server
+    // closed the connection, e.g. after the user left the game.
+    let sender_task = {
+        let state = state.clone();
+        let clerk_user_id = clerk_user_id.clone();
+        tokio::spawn(async move {
+            loop {
+                // Biased so events broadcast before a disconnect (such as the
+                // user's own playerLeft) still go out first
+                let event = tokio::select! {
+                    biased;
+                    reply = reply_receiver.recv() => match reply {
+                        Some(message) => GameEvent { seq: None, message },
+                        None => break,
+                    },
+                    received = game_receiver.recv() => match received {
+                        Ok(event) => {
+                            if let (Some(seq), Some(through)) = (event.seq, replayed_through)
+                                && seq <= through
+                            {
+                                continue;
+                            }
+                            event
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            match lag_resync(&state, game_id, skipped).await {
+                                Some(event) => event,
+                                None => continue,
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                    // Wrapped so the non-Send borrow it resolves to is dropped inside
+                    _ = async { shutdown_receiver.wait_for(|&down| down).await.is_ok() } => {
+                        let _ = sender
+                            .send(Message::Close(Some(CloseFrame {
+                                code: close_code::AWAY,
+                                reason: "Server shutting down".into(),
+                            })))
+                            .await;
+                        return true;
+                    },
+                    disconnect = disconnect_receiver.recv() => match disconnect {
+                        Ok(disconnected_user_id) if disconnected_user_id == clerk_user_id => {
+                            let _ = sender
+                                .send(Message::Close(Some(CloseFrame {
+                                    code: CLOSE_REMOVED,
+                                    reason: "No longer in this game".into(),
+                                })))
+                                .await;
+                            return true;
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
 
-    // Handle incoming and outgoing messages
-    let sender_task = tokio::spawn(async move {
-        while let Ok(message) = game_receiver.recv().await {
-            if let Ok(msg_text) = serde_json::to_string(&message) {
-                if sender.send(Message::Text(msg_text.into())).await.is_err() {
+                if send_event(&mut sender, event, protocol_version, encoding)
+                    .await
+                    .is_err()
+                {
                     break;
                 }
             }
-        }
-    });
+            false
+        })
+    };
 
     let receiver_task = {
         let state = state.clone();
@@ -124,7 +407,34 @@ async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state:
             while let Some(msg) = receiver.next().await {
                 match msg {
                     Ok(Message::Text(text)) => {
-                        if let Err(e) = handle_websocket_message(&text, game_id, &state).await {
+                        let request = serde_json::from_str(&text)
+                            .map_err(|e| ApiError::BadRequest(format!("Malformed JSON: {e}")));
+                        if let Err(e) = handle_websocket_message(
+                            request,
+                            game_id,
+                            &clerk_user_id,
+                            &state,
+                            &reply_sender,
+                        )
+                        .await
+                        {
+                            error!("Error handling websocket message: {:?}", e);
+                        }
+                    }
+                    // Requests may be sent as MessagePack whatever the encoding
+                    Ok(Message::Binary(bytes)) => {
+                        let request = rmp_serde::from_slice(&bytes).map_err(|e| {
+                            ApiError::BadRequest(format!("Malformed MessagePack: {e}"))
+                        });
+                        if let Err(e) = handle_websocket_message(
+                            request,
+                            game_id,
+                            &clerk_user_id,
+                            &state,
+                            &reply_sender,
+                        )
+                        .await
+                        {
                             error!("Error handling websocket message: {:?}", e);
                         }
                     }
@@ -145,9 +455,16 @@ async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state:
         })
     };
 
+    let receiver_abort = receiver_task.abort_handle();
+
     // Wait for either task to complete
     tokio::select! {
-        _ = sender_task => {},
+        disconnected = sender_task => {
+            // Stop taking requests once the server has closed the connection
+            if matches!(disconnected, Ok(true)) {
+                receiver_abort.abort();
+            }
+        },
         _ = receiver_task => {},
     }
 
@@ -158,11 +475,101 @@ async fn handle_socket(socket: WebSocket, game_id: Uuid, user: ClerkUser, state:
     );
 }
 
+/// Read-only connection that plays back a finished game's event log, in order,
+/// and closes once it runs out. Nothing is joined and requests are ignored.
+async fn handle_replay_socket(
+    socket: WebSocket,
+    game_id: Uuid,
+    realtime: bool,
+    protocol_version: u32,
+    encoding: Encoding,
+    state: AppState,
+) {
+    let (mut sender, _receiver) = socket.split();
+    let replayed = replay_game(
+        &mut sender,
+        &state,
+        game_id,
+        realtime,
+        protocol_version,
+        encoding,
+    )
+    .await;
+    let (code, reason) = match replayed {
+        Ok(()) => (close_code::NORMAL, "Replay complete".to_string()),
+        Err(e) => {
+            error!("Replay of game {} failed: {:?}", game_id, e);
+            reject(&mut sender, encoding, close_code_for(&e), e.to_string()).await;
+            return;
+        }
+    };
+
+    let _ = sender
+        .send(Message::Close(Some(CloseFrame {
+            code,
+            reason: reason.into(),
+        })))
+        .await;
+}
+
+async fn replay_game(
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    game_id: Uuid,
+    realtime: bool,
+    protocol_version: u32,
+    encoding: Encoding,
+) -> Result<()> {
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.is_open() {
+        return Err(ApiError::BadRequest(
+            "Game is still active; connect without replay to follow it live".to_string(),
+        ));
+    }
+
+    info!(
+        "Replaying game {} (realtime: {}, protocol v{})",
+        game_id, realtime, protocol_version
+    );
+
+    let mut last_seq = 0;
+    let mut last_created_at: Option<chrono::DateTime<chrono::Utc>> = None;
+    loop {
+        let events =
+            database::get_game_events(&state.db, game_id, last_seq, MAX_REPLAY_EVENTS).await?;
+        let Some(last) = events.last() else {
+            break;
+        };
+        last_seq = last.seq;
+
+        for record in events {
+            if realtime
+                && let Some(previous) = last_created_at
+                && let Ok(gap) = (record.created_at - previous).to_std()
+            {
+                tokio::time::sleep(gap.min(MAX_REPLAY_DELAY)).await;
+            }
+            last_created_at = Some(record.created_at);
+
+            let message: WebSocketMessage =
+                serde_json::from_value(record.message).map_err(|e| ApiError::Internal(e.into()))?;
+            let event = GameEvent {
+                seq: Some(record.seq),
+                message,
+            };
+            send_event(sender, event, protocol_version, encoding).await?;
+        }
+    }
+
+    info!("Replayed game {} through seq {}", game_id, last_seq);
+    Ok(())
+}
+
 async fn verify_game(state: &AppState, game_id: Uuid) -> Result<()> {
-    // Verify game exists
+    // Verify game exists and hasn't ended; lobby games can be joined
     let game = database::get_game_by_id(&state.db, game_id).await?;
 
-    if game.status != "active" {
+    if !game.is_open() {
         return Err(ApiError::GameNotActive);
     }
 
@@ -174,23 +581,101 @@ async fn add_user_to_game(
     game_id: Uuid,
     clerk_user_id: &str,
     user: &ClerkUser,
+    join_code: Option<String>,
 ) -> Result<()> {
-    // Verify user is a player in this game
-    let players = database::get_players_in_game(&state.db, game_id).await?;
-    let player = players.iter().find(|p| p.clerk_user_id == clerk_user_id);
-    if player.is_none() {
-        handle_join_game(clerk_user_id, game_id, state, user).await?;
+    // Only users not yet in the game join, and only a join is announced
+    match database::get_player_by_user(&state.db, game_id, clerk_user_id).await {
+        Ok(_) => Ok(()),
+        Err(ApiError::PlayerNotFound) => {
+            handle_join_game(clerk_user_id, game_id, state, user, join_code).await
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Send an event in the form the client's protocol version understands, which
+/// may be several messages or none at all
+async fn send_event(
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    event: GameEvent,
+    protocol_version: u32,
+    encoding: Encoding,
+) -> Result<()> {
+    for message in event.message.for_protocol(protocol_version) {
+        let frame = encoding.frame(&GameEvent {
+            seq: event.seq,
+            message,
+        })?;
+        sender
+            .send(frame)
+            .await
+            .map_err(|e| ApiError::WebSocket(e.to_string()))?;
     }
     Ok(())
 }
 
+/// Send every logged event after `last_seq`, in order. Returns the sequence
+/// number replayed through, or `None` if the client is too far behind (or
+/// ahead, e.g. after a server reset) and needs a full state instead.
+async fn replay_missed_events(
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    game_id: Uuid,
+    last_seq: i64,
+    protocol_version: u32,
+    encoding: Encoding,
+) -> Result<Option<i64>> {
+    let latest_seq = database::get_latest_game_event_seq(&state.db, game_id).await?;
+    if last_seq < 0 || last_seq > latest_seq || latest_seq - last_seq > MAX_REPLAY_EVENTS {
+        info!(
+            "Cannot replay game {} from seq {} (latest {}), sending full state",
+            game_id, last_seq, latest_seq
+        );
+        return Ok(None);
+    }
+
+    let events = database::get_game_events(&state.db, game_id, last_seq, MAX_REPLAY_EVENTS).await?;
+
+    let mut replayed_through = last_seq;
+    for record in events {
+        // An entry this server can't read, e.g. from a newer version, leaves
+        // a gap the client can't skip over
+        let message: WebSocketMessage = match serde_json::from_value(record.message) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!(
+                    "Cannot replay event {} of game {} ({}), sending full state",
+                    record.seq, game_id, e
+                );
+                return Ok(None);
+            }
+        };
+        let event = GameEvent {
+            seq: Some(record.seq),
+            message,
+        };
+        send_event(sender, event, protocol_version, encoding).await?;
+        replayed_through = record.seq;
+    }
+
+    info!(
+        "Replayed events {}..={} for game {}",
+        last_seq + 1,
+        replayed_through,
+        game_id
+    );
+    Ok(Some(replayed_through))
+}
+
 async fn send_initial_game_state(
     sender: &mut futures::stream::SplitSink<WebSocket, Message>,
     state: &AppState,
     game_id: Uuid,
+    protocol_version: u32,
+    encoding: Encoding,
 ) -> Result<()> {
     // Use enriched game state with user display info
-    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    let game_state = (*state.game_state(game_id).await?).clone();
 
     info!(
         "Sending initial game state for game {} with {} players",
@@ -198,16 +683,11 @@ async fn send_initial_game_state(
         game_state.players.len(),
     );
 
-    let message = WebSocketMessage::GameStarted {
-        game_state: game_state.clone(),
+    let event = GameEvent {
+        seq: None,
+        message: WebSocketMessage::FullState { game_state },
     };
-
-    let msg_text = serde_json::to_string(&message).map_err(|e| ApiError::Internal(e.into()))?;
-
-    sender
-        .send(Message::Text(msg_text.into()))
-        .await
-        .map_err(|e| ApiError::WebSocket(e.to_string()))?;
+    send_event(sender, event, protocol_version, encoding).await?;
 
     info!(
         "Complete initial game state sent successfully for game {}",
@@ -217,67 +697,231 @@ async fn send_initial_game_state(
     Ok(())
 }
 
-async fn handle_websocket_message(text: &str, game_id: Uuid, state: &AppState) -> Result<()> {
-    debug!("WebSocket message received for game {}: {}", game_id, text);
-
-    let request: WebSocketRequest =
-        serde_json::from_str(text).map_err(|_| ApiError::BadRequest("Invalid JSON".to_string()))?;
-
+/// Handle a request frame, already decoded from JSON or MessagePack
+async fn handle_websocket_message(
+    request: Result<serde_json::Value>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+    reply_sender: &mpsc::Sender<WebSocketMessage>,
+) -> Result<()> {
     debug!(
-        "Parsed WebSocket request for game {}: {:?}",
+        "WebSocket message received for game {}: {:?}",
         game_id, request
     );
 
+    let parsed = request.map_err(|e| (None, e)).and_then(parse_envelope);
+    let (request_id, result) = match parsed {
+        Ok(envelope) => {
+            debug!(
+                "Parsed WebSocket request for game {}: {:?}",
+                game_id, envelope
+            );
+            let result = handle_websocket_request(
+                envelope.request,
+                game_id,
+                actor_clerk_user_id,
+                state,
+                reply_sender,
+            )
+            .await;
+            (envelope.request_id, result)
+        }
+        Err((request_id, e)) => (request_id, Err(e)),
+    };
+
+    if let Some(request_id) = request_id {
+        let ack = WebSocketMessage::Ack {
+            request_id,
+            ok: result.is_ok(),
+            error: result.as_ref().err().map(|e| AckError {
+                code: e.code().to_string(),
+                message: e.client_message(),
+            }),
+        };
+        let _ = reply_sender.send(ack).await;
+    } else if let Err(e) = &result {
+        let _ = reply_sender
+            .send(WebSocketMessage::Error {
+                message: e.client_message(),
+            })
+            .await;
+    }
+
+    result
+}
+
+/// Parse a request, explaining what was wrong with it if that fails. The
+/// request id is recovered from invalid requests when possible so they can
+/// still be acked.
+fn parse_envelope(
+    value: serde_json::Value,
+) -> std::result::Result<WebSocketEnvelope, (Option<String>, ApiError)> {
+    serde_json::from_value(value.clone()).map_err(|e| {
+        let request_id = value
+            .get("requestId")
+            .and_then(|id| id.as_str())
+            .map(str::to_string);
+        let reason = match value.get("action") {
+            None => "Missing action".to_string(),
+            Some(serde_json::Value::String(action)) => {
+                if e.to_string().starts_with("unknown variant") {
+                    format!("Unknown action '{action}'")
+                } else {
+                    format!("Invalid payload for action '{action}': {e}")
+                }
+            }
+            Some(_) => "Action must be a string".to_string(),
+        };
+        (request_id, ApiError::BadRequest(reason))
+    })
+}
+
+async fn handle_websocket_request(
+    request: WebSocketRequest,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+    reply_sender: &mpsc::Sender<WebSocketMessage>,
+) -> Result<()> {
     match request {
         WebSocketRequest::UpdateLife {
             player_id,
             change_amount,
+            source_player_id,
+            reason,
+        } => {
+            debug!(
+                "WebSocket UpdateLife: player_id={}, change_amount={}, source_player_id={:?}, reason={:?}, game_id={}",
+                player_id, change_amount, source_player_id, reason, game_id
+            );
+            handle_life_update(
+                player_id,
+                change_amount,
+                source_player_id,
+                reason.as_deref(),
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::UpdateLifeByUser {
+            clerk_user_id,
+            change_amount,
+            source_player_id,
+            reason,
+        } => {
+            debug!(
+                "WebSocket UpdateLifeByUser: clerk_user_id={}, change_amount={}, game_id={}",
+                clerk_user_id, change_amount, game_id
+            );
+            let player = database::get_player_by_user(&state.db, game_id, &clerk_user_id).await?;
+            handle_life_update(
+                player.id,
+                change_amount,
+                source_player_id,
+                reason.as_deref(),
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::SetLife {
+            player_id,
+            life,
+            expected_version,
+            expected_current,
+            source_player_id,
+            reason,
         } => {
             debug!(
-                "WebSocket UpdateLife: player_id={}, change_amount={}, game_id={}",
-                player_id, change_amount, game_id
+                "WebSocket SetLife: player_id={}, life={}, expected_version={:?}, expected_current={:?}, source_player_id={:?}, reason={:?}, game_id={}",
+                player_id,
+                life,
+                expected_version,
+                expected_current,
+                source_player_id,
+                reason,
+                game_id
             );
-            handle_life_update(player_id, change_amount, game_id, state).await
+            handle_set_life(
+                player_id,
+                life,
+                expected_version,
+                expected_current,
+                source_player_id,
+                reason.as_deref(),
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
         }
         // JoinGame is now handled automatically on WebSocket connection with JWT
-        WebSocketRequest::LeaveGame { player_id } => {
+        WebSocketRequest::LeaveGame { player_id, mode } => {
             debug!(
-                "WebSocket LeaveGame: player_id={}, game_id={}",
-                player_id, game_id
+                "WebSocket LeaveGame: player_id={}, game_id={}, mode={:?}",
+                player_id, game_id, mode
             );
-            handle_leave_game(player_id, game_id, state).await
+            handle_leave_game(player_id, game_id, mode, actor_clerk_user_id, state).await
         }
         WebSocketRequest::GetGameState => {
             debug!("WebSocket GetGameState: game_id={}", game_id);
-            handle_get_game_state(game_id, state).await
+            handle_get_game_state(game_id, state, reply_sender).await
+        }
+        WebSocketRequest::ForceResync => {
+            debug!("WebSocket ForceResync: game_id={}", game_id);
+            handle_force_resync(game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::StartGame => {
+            debug!("WebSocket StartGame: game_id={}", game_id);
+            handle_start_game(game_id, actor_clerk_user_id, state).await
         }
         WebSocketRequest::EndGame { winner_player_id } => {
             debug!(
                 "WebSocket EndGame: game_id={}, winner={:?}",
                 game_id, winner_player_id
             );
-            handle_end_game(game_id, winner_player_id, state).await
+            handle_end_game(game_id, winner_player_id, actor_clerk_user_id, state).await
         }
         WebSocketRequest::SetCommanderDamage {
             from_player_id,
             to_player_id,
             commander_number,
             new_damage,
+            expected_damage,
         } => {
             debug!(
-                "WebSocket SetCommanderDamage: from_player_id={}, to_player_id={}, commander_number={}, new_damage={}, game_id={}",
-                from_player_id, to_player_id, commander_number, new_damage, game_id
+                "WebSocket SetCommanderDamage: from_player_id={}, to_player_id={}, commander_number={}, new_damage={}, expected_damage={:?}, game_id={}",
+                from_player_id,
+                to_player_id,
+                commander_number,
+                new_damage,
+                expected_damage,
+                game_id
             );
             handle_set_commander_damage(
                 from_player_id,
                 to_player_id,
                 commander_number,
                 new_damage,
+                expected_damage,
                 game_id,
+                actor_clerk_user_id,
                 state,
             )
             .await
         }
+        WebSocketRequest::BulkCommanderDamage { entries } => {
+            debug!(
+                "WebSocket BulkCommanderDamage: {} entries, game_id={}",
+                entries.len(),
+                game_id
+            );
+            handle_bulk_commander_damage(entries, game_id, actor_clerk_user_id, state).await
+        }
         WebSocketRequest::UpdateCommanderDamage {
             from_player_id,
             to_player_id,
@@ -294,6 +938,7 @@ async fn handle_websocket_message(text: &str, game_id: Uuid, state: &AppState) -
                 commander_number,
                 damage_amount,
                 game_id,
+                actor_clerk_user_id,
                 state,
             )
             .await
@@ -306,7 +951,119 @@ async fn handle_websocket_message(text: &str, game_id: Uuid, state: &AppState) -
                 "WebSocket TogglePartner: player_id={}, enable_partner={}, game_id={}",
                 player_id, enable_partner, game_id
             );
-            handle_toggle_partner(player_id, enable_partner, game_id, state).await
+            handle_toggle_partner(
+                player_id,
+                enable_partner,
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::SetCommanderName {
+            player_id,
+            commander_name,
+            partner_name,
+        } => {
+            debug!(
+                "WebSocket SetCommanderName: player_id={}, commander_name={:?}, partner_name={:?}, game_id={}",
+                player_id, commander_name, partner_name, game_id
+            );
+            handle_set_commander_name(
+                player_id,
+                commander_name,
+                partner_name,
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::CastCommander { player_id } => {
+            debug!(
+                "WebSocket CastCommander: player_id={}, game_id={}",
+                player_id, game_id
+            );
+            handle_commander_casts(player_id, false, game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::ResetCommanderCasts { player_id } => {
+            debug!(
+                "WebSocket ResetCommanderCasts: player_id={}, game_id={}",
+                player_id, game_id
+            );
+            handle_commander_casts(player_id, true, game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::SetMulligans { player_id, count } => {
+            debug!(
+                "WebSocket SetMulligans: player_id={}, count={}, game_id={}",
+                player_id, count, game_id
+            );
+            handle_opening_hand(
+                player_id,
+                OpeningHandChange::Mulligans(count),
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::VoteEndGame { player_id } => {
+            debug!(
+                "WebSocket VoteEndGame: player_id={}, game_id={}",
+                player_id, game_id
+            );
+            handle_vote_end_game(player_id, game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::SetSeatColor { player_id, color } => {
+            debug!(
+                "WebSocket SetSeatColor: player_id={}, color={:?}, game_id={}",
+                player_id, color, game_id
+            );
+            handle_set_seat_color(player_id, color, game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::SetHandSize { player_id, size } => {
+            debug!(
+                "WebSocket SetHandSize: player_id={}, size={}, game_id={}",
+                player_id, size, game_id
+            );
+            handle_opening_hand(
+                player_id,
+                OpeningHandChange::HandSize(size),
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::SetGameCounter {
+            name,
+            value,
+            reset_each_turn,
+        } => {
+            debug!(
+                "WebSocket SetGameCounter: name={}, value={}, reset_each_turn={:?}, game_id={}",
+                name, value, reset_each_turn, game_id
+            );
+            handle_set_game_counter(
+                &name,
+                value,
+                reset_each_turn,
+                game_id,
+                actor_clerk_user_id,
+                state,
+            )
+            .await
+        }
+        WebSocketRequest::IncrementGameCounter { name, amount } => {
+            debug!(
+                "WebSocket IncrementGameCounter: name={}, amount={}, game_id={}",
+                name, amount, game_id
+            );
+            handle_increment_game_counter(&name, amount, game_id, actor_clerk_user_id, state).await
+        }
+        WebSocketRequest::NextTurn => {
+            debug!("WebSocket NextTurn: game_id={}", game_id);
+            handle_next_turn(game_id, actor_clerk_user_id, state).await
         }
     }
 }
@@ -314,7 +1071,10 @@ async fn handle_websocket_message(text: &str, game_id: Uuid, state: &AppState) -
 async fn handle_life_update(
     player_id: Uuid,
     change_amount: i32,
+    source_player_id: Option<Uuid>,
+    reason: Option<&str>,
     game_id: Uuid,
+    actor_clerk_user_id: &str,
     state: &AppState,
 ) -> Result<()> {
     info!(
@@ -322,43 +1082,210 @@ async fn handle_life_update(
         game_id, player_id, change_amount
     );
 
-    // Update player life
-    let (updated_player, _life_change) =
-        database::update_player_life(&state.db, player_id, change_amount).await?;
-
-    info!(
-        "✅ Player life updated: new life = {}",
-        updated_player.current_life
-    );
+    if change_amount == 0 {
+        return Err(ApiError::BadRequest(
+            "Life change must be non-zero".to_string(),
+        ));
+    }
+    let max_change = state.config.max_life_change;
+    if change_amount.abs() > max_change {
+        return Err(ApiError::BadRequest(format!(
+            "Life change too large (max ±{max_change})"
+        )));
+    }
 
-    // Broadcast the update
-    let message = WebSocketMessage::LifeUpdate {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+    database::ensure_can_control_player(&state.db, &game, player_id, actor_clerk_user_id).await?;
+
+    // Update player life
+    let applied = database::update_player_life(
+        &state.db,
         game_id,
         player_id,
-        new_life: updated_player.current_life,
         change_amount,
-    };
+        source_player_id,
+        reason,
+        actor_clerk_user_id,
+        state.config.life_floor,
+        |player, life_change| {
+            NewAuditEntry::new(
+                actor_clerk_user_id,
+                "life_update",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "changeAmount": change_amount,
+                    "newLife": player.current_life,
+                    "sourcePlayerId": source_player_id,
+                    "reason": life_change.reason,
+                }),
+            )
+        },
+    )
+    .await?;
+    let updated_player = applied.player;
 
     info!(
-        "Broadcasting life update message to all clients in game {}: {:?}",
-        game_id, message
+        "✅ Player life updated: new life = {}",
+        updated_player.current_life
     );
 
-    state.broadcast_to_game(game_id, message);
+    // Nothing to announce when the life floor absorbed the whole change
+    if let Some(life_change) = applied.life_change {
+        broadcast_game_started_if_first_change(state, game_id).await?;
+
+        // Broadcast the update
+        let message = WebSocketMessage::LifeUpdate {
+            game_id,
+            player_id,
+            new_life: updated_player.current_life,
+            change_amount: life_change.change_amount,
+            version: updated_player.version,
+            source_player_id: life_change.source_player_id,
+            reason: life_change.reason,
+            actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
+        };
+
+        info!(
+            "Broadcasting life update message to all clients in game {}: {:?}",
+            game_id, message
+        );
+
+        state.broadcast_to_game(game_id, message).await;
+    }
+
+    if applied.lethal {
+        eliminate_player(
+            state,
+            game_id,
+            player_id,
+            EliminationCause::Life,
+            actor_clerk_user_id,
+        )
+        .await?;
+    }
 
     info!("Life update broadcast completed for game {}", game_id);
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn handle_set_life(
+    player_id: Uuid,
+    life: i32,
+    expected_version: Option<i64>,
+    expected_current: Option<i32>,
+    source_player_id: Option<Uuid>,
+    reason: Option<&str>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    info!(
+        "Processing set life for game {}, player {}, life {}",
+        game_id, player_id, life
+    );
+
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+    database::ensure_can_control_player(&state.db, &game, player_id, actor_clerk_user_id).await?;
+
+    let applied = database::set_player_life(
+        &state.db,
+        game_id,
+        player_id,
+        life,
+        expected_version,
+        expected_current,
+        source_player_id,
+        reason,
+        actor_clerk_user_id,
+        state.config.life_floor,
+        |player, life_change| {
+            NewAuditEntry::new(
+                actor_clerk_user_id,
+                "set_life",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "life": life,
+                    "changeAmount": life_change.change_amount,
+                    "version": player.version,
+                    "sourcePlayerId": source_player_id,
+                    "reason": life_change.reason,
+                }),
+            )
+        },
+    )
+    .await?;
+    let updated_player = applied.player;
+
+    // Nothing to announce when the total didn't move
+    if let Some(life_change) = applied.life_change {
+        broadcast_game_started_if_first_change(state, game_id).await?;
+
+        // Broadcast the update
+        let message = WebSocketMessage::LifeUpdate {
+            game_id,
+            player_id,
+            new_life: updated_player.current_life,
+            change_amount: life_change.change_amount,
+            version: updated_player.version,
+            source_player_id: life_change.source_player_id,
+            reason: life_change.reason,
+            actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
+        };
+
+        state.broadcast_to_game(game_id, message).await;
+    }
+
+    if applied.lethal {
+        eliminate_player(
+            state,
+            game_id,
+            player_id,
+            EliminationCause::Life,
+            actor_clerk_user_id,
+        )
+        .await?;
+    }
+
+    debug!("Set life broadcast completed for game {}", game_id);
+    Ok(())
+}
+
 async fn handle_join_game(
     clerk_user_id: &str,
     game_id: Uuid,
     state: &AppState,
     user: &ClerkUser,
+    join_code: Option<String>,
 ) -> Result<()> {
     // Add user to game if not already present
-    let result = database::join_game(&state.db, game_id, clerk_user_id).await;
+    let result = database::join_game(
+        &state.db,
+        game_id,
+        clerk_user_id,
+        JoinGameRequest {
+            join_code,
+            ..Default::default()
+        },
+        state.config.max_open_games_per_user,
+        |player| {
+            NewAuditEntry::new(
+                clerk_user_id,
+                "join",
+                serde_json::json!({ "playerId": player.id, "position": player.position }),
+            )
+        },
+    )
+    .await;
 
     match result {
         Ok(player) => {
@@ -369,21 +1296,21 @@ async fn handle_join_game(
                 game_id
             );
 
-            // Create enriched player with user info
-            let enriched_player = crate::models::PlayerWithUser::from_player(
-                player,
-                user.display_name(),
-                user.username.clone(),
-                user.image_url.clone(),
+            broadcast_player_joined(state, game_id, player, user).await;
+            Ok(())
+        }
+        // Another connection for the same user (a second tab, or a reconnect
+        // racing the first) seated them since the membership check; that's a
+        // reconnect, not a new player, so there's nothing to announce
+        Err(ApiError::Conflict(_))
+            if database::get_player_by_user(&state.db, game_id, clerk_user_id)
+                .await
+                .is_ok() =>
+        {
+            debug!(
+                "Player {} already in game {}, treating as a reconnect",
+                clerk_user_id, game_id
             );
-
-            // Broadcast player joined message with enriched data
-            let message = WebSocketMessage::PlayerJoined {
-                game_id,
-                player: enriched_player,
-            };
-
-            state.broadcast_to_game(game_id, message);
             Ok(())
         }
         Err(e) => {
@@ -393,37 +1320,128 @@ async fn handle_join_game(
     }
 }
 
-async fn handle_leave_game(player_id: Uuid, game_id: Uuid, state: &AppState) -> Result<()> {
+async fn handle_leave_game(
+    player_id: Uuid,
+    game_id: Uuid,
+    mode: Option<LeaveMode>,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Without a mode, a player leaving mid-game concedes so the board stays
+    // intact; only lobby games are left outright
+    let mode = match mode {
+        Some(mode) => mode,
+        None if database::get_game_by_id(&state.db, game_id).await?.status == "active" => {
+            LeaveMode::Eliminate
+        }
+        None => LeaveMode::Quit,
+    };
+    if mode == LeaveMode::Eliminate {
+        return concede_player(state, game_id, player_id, actor_clerk_user_id).await;
+    }
+
     info!("Player {} leaving game {}", player_id, game_id);
 
     // Get player info to extract clerk_user_id
     let players = database::get_players_in_game(&state.db, game_id).await?;
-    let player = players
-        .iter()
-        .find(|p| p.id == player_id)
-        .ok_or(ApiError::PlayerNotFound)?;
+    let Some(player) = players.iter().find(|p| p.id == player_id) else {
+        // Already gone, e.g. a retried leave
+        debug!("Player {} already not in game {}", player_id, game_id);
+        return Ok(());
+    };
 
     let clerk_user_id = &player.clerk_user_id;
 
-    // Remove player from game
-    database::leave_game(&state.db, game_id, clerk_user_id).await?;
-
-    // Broadcast player left message
-    let message = WebSocketMessage::PlayerLeft { game_id, player_id };
+    // Remove player from game, unless a concurrent leave got there first
+    if database::leave_game(&state.db, game_id, clerk_user_id, |player_id| {
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "leave",
+            serde_json::json!({ "playerId": player_id }),
+        )
+    })
+    .await?
+    .is_none()
+    {
+        return Ok(());
+    }
 
-    state.broadcast_to_game(game_id, message);
+    // Broadcast player left message and disconnect the player
+    broadcast_player_left(state, game_id, player_id, clerk_user_id).await;
 
     info!("📤 Player left broadcast completed for game {}", game_id);
     Ok(())
 }
 
-async fn handle_get_game_state(game_id: Uuid, state: &AppState) -> Result<()> {
+/// Send the current state to the requesting connection only; other clients
+/// in the room are unaffected.
+async fn handle_get_game_state(
+    game_id: Uuid,
+    state: &AppState,
+    reply_sender: &mpsc::Sender<WebSocketMessage>,
+) -> Result<()> {
     // Use enriched game state with user display info
-    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    let game_state = (*state.game_state(game_id).await?).clone();
+
+    let _ = reply_sender
+        .send(WebSocketMessage::FullState { game_state })
+        .await;
+
+    Ok(())
+}
+
+/// Push the current state to every client in the room, for when the host
+/// wants everyone rebuilt from the same snapshot.
+async fn handle_force_resync(
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(actor_clerk_user_id) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can force a resync".to_string(),
+        ));
+    }
+
+    let game_state = (*state.game_state(game_id).await?).clone();
 
-    let message = WebSocketMessage::GameStarted { game_state };
+    let message = WebSocketMessage::FullState { game_state };
+
+    state.broadcast_to_game(game_id, message).await;
+
+    Ok(())
+}
+
+async fn handle_start_game(
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    info!("Starting game {} via WebSocket request", game_id);
+
+    database::get_game_by_id(&state.db, game_id).await?;
+
+    let host = database::get_game_host(&state.db, game_id).await?;
+    if host.as_deref() != Some(actor_clerk_user_id) {
+        return Err(ApiError::Forbidden(
+            "Only the game host can start the game".to_string(),
+        ));
+    }
+
+    database::start_game(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(actor_clerk_user_id, "start_game", serde_json::json!({})),
+    )
+    .await?;
 
-    state.broadcast_to_game(game_id, message);
+    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    state
+        .broadcast_to_game(game_id, WebSocketMessage::GameStarted { game_state })
+        .await;
 
     Ok(())
 }
@@ -431,6 +1449,7 @@ async fn handle_get_game_state(game_id: Uuid, state: &AppState) -> Result<()> {
 async fn handle_end_game(
     game_id: Uuid,
     winner_player_id: Option<Uuid>,
+    actor_clerk_user_id: &str,
     state: &AppState,
 ) -> Result<()> {
     info!(
@@ -439,7 +1458,26 @@ async fn handle_end_game(
     );
 
     // End the game in the database with the specified winner
-    let _ = database::end_game(&state.db, game_id, winner_player_id).await?;
+    let _ = database::end_game(
+        &state.db,
+        game_id,
+        winner_player_id,
+        state.config.elo_k_factor.into(),
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "end_game",
+            serde_json::json!({ "winnerPlayerId": winner_player_id }),
+        ),
+    )
+    .await?;
+    // The game has already ended; missing badges shouldn't turn that into an error
+    if let Err(e) = achievements::award_for_game(&state.db, game_id).await {
+        error!("Failed to award achievements for game {}: {:?}", game_id, e);
+    }
+    // Only games with a winner move ratings
+    if winner_player_id.is_some() {
+        state.leaderboard.mark_stale();
+    }
 
     // Get the winner player if specified
     let enriched_winner = if let Some(winner_id) = winner_player_id {
@@ -447,7 +1485,7 @@ async fn handle_end_game(
         players
             .into_iter()
             .find(|p| p.id == winner_id)
-            .map(|w| database::enrich_player_with_user(w))
+            .map(database::enrich_player_with_user)
     } else {
         None
     };
@@ -463,27 +1501,135 @@ async fn handle_end_game(
     let message = WebSocketMessage::GameEnded {
         game_id,
         winner: enriched_winner,
+        outcome: None,
     };
-    state.broadcast_to_game(game_id, message);
+    let receivers = state.broadcast_to_game(game_id, message).await;
 
     // Clean up WebSocket room after a delay to allow final messages
-    let state_clone = state.clone();
-    tokio::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-        state_clone.cleanup_game_room(game_id);
-    });
+    state.schedule_room_cleanup(game_id, receivers);
 
     info!("Game {} ended via WebSocket request", game_id);
     Ok(())
 }
 
+async fn handle_vote_end_game(
+    player_id: Uuid,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let players = database::get_players_in_game(&state.db, game_id).await?;
+    let player = players
+        .iter()
+        .find(|p| p.id == player_id)
+        .ok_or(ApiError::PlayerNotFound)?;
+    if player.clerk_user_id != actor_clerk_user_id {
+        return Err(ApiError::Forbidden(
+            "You can only vote for your own seat".to_string(),
+        ));
+    }
+    if player.elimination_cause.is_some() {
+        return Err(ApiError::BadRequest(
+            "Eliminated players can't vote".to_string(),
+        ));
+    }
+
+    state.cast_end_vote(game_id, player_id);
+
+    database::record_audit_entry(
+        &state.db,
+        game_id,
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "vote_end_game",
+            serde_json::json!({ "playerId": player_id }),
+        ),
+    )
+    .await?;
+
+    tally_end_votes(state, game_id, &players, actor_clerk_user_id).await
+}
+
+/// Announce the open vote to end the game, and end it (with no winner) once
+/// a majority of the players still in the game have voted
+async fn tally_end_votes(
+    state: &AppState,
+    game_id: Uuid,
+    players: &[Player],
+    actor_clerk_user_id: &str,
+) -> Result<()> {
+    let voters = state.end_voters(game_id);
+    let remaining: Vec<Uuid> = players
+        .iter()
+        .filter(|p| p.elimination_cause.is_none())
+        .map(|p| p.id)
+        .collect();
+    let votes: Vec<Uuid> = remaining
+        .iter()
+        .copied()
+        .filter(|id| voters.contains(id))
+        .collect();
+    let needed = remaining.len() / 2 + 1;
+    let passed = votes.len() >= needed;
+
+    info!(
+        "End vote in game {}: {} of {} needed",
+        game_id,
+        votes.len(),
+        needed
+    );
+    state
+        .broadcast_to_game(
+            game_id,
+            WebSocketMessage::EndGameVotes {
+                game_id,
+                votes,
+                needed,
+            },
+        )
+        .await;
+
+    if passed && state.close_end_vote(game_id) {
+        handle_end_game(game_id, None, actor_clerk_user_id, state).await?;
+    }
+    Ok(())
+}
+
+/// Re-tally an open end vote after the roster changed, since fewer players
+/// may now be enough to pass it
+async fn recount_end_votes(
+    state: &AppState,
+    game_id: Uuid,
+    departed_player_id: Uuid,
+    actor_clerk_user_id: &str,
+) {
+    if !state.withdraw_end_vote(game_id, departed_player_id) {
+        return;
+    }
+    let result = match database::get_players_in_game(&state.db, game_id).await {
+        Ok(players) => tally_end_votes(state, game_id, &players, actor_clerk_user_id).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        error!("Failed to recount end votes for game {}: {:?}", game_id, e);
+    }
+}
+
 // Commander Damage handlers
+#[allow(clippy::too_many_arguments)]
 async fn handle_set_commander_damage(
     from_player_id: Uuid,
     to_player_id: Uuid,
     commander_number: i32,
     new_damage: i32,
+    expected_damage: Option<i32>,
     game_id: Uuid,
+    actor_clerk_user_id: &str,
     state: &AppState,
 ) -> Result<()> {
     debug!(
@@ -497,19 +1643,6 @@ async fn handle_set_commander_damage(
         return Err(ApiError::GameNotActive);
     }
 
-    // Update commander damage
-    let updated_damage = database::update_commander_damage(
-        &state.db,
-        game_id,
-        from_player_id,
-        to_player_id,
-        commander_number,
-        new_damage,
-    )
-    .await?;
-
-    info!("Commander damage updated: {} damage", updated_damage.damage);
-
     // Calculate damage amount for broadcast (difference from previous)
     let previous_damage = database::get_commander_damage_for_game(&state.db, game_id)
         .await?
@@ -524,6 +1657,32 @@ async fn handle_set_commander_damage(
 
     let damage_amount = new_damage - previous_damage;
 
+    // Update commander damage
+    let updated_damage = database::update_commander_damage(
+        &state.db,
+        game_id,
+        from_player_id,
+        to_player_id,
+        commander_number,
+        new_damage,
+        expected_damage,
+        actor_clerk_user_id,
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "commander_damage",
+            serde_json::json!({
+                "fromPlayerId": from_player_id,
+                "toPlayerId": to_player_id,
+                "commanderNumber": commander_number,
+                "damageAmount": damage_amount,
+                "newDamage": new_damage,
+            }),
+        ),
+    )
+    .await?;
+
+    info!("Commander damage updated: {} damage", updated_damage.damage);
+
     // Broadcast the update
     let message = WebSocketMessage::CommanderDamageUpdate {
         game_id,
@@ -532,6 +1691,7 @@ async fn handle_set_commander_damage(
         commander_number,
         new_damage,
         damage_amount,
+        actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
     };
 
     info!(
@@ -539,7 +1699,18 @@ async fn handle_set_commander_damage(
         game_id, message
     );
 
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+
+    if new_damage >= game.commander_lethal {
+        eliminate_player(
+            state,
+            game_id,
+            to_player_id,
+            EliminationCause::CommanderDamage,
+            actor_clerk_user_id,
+        )
+        .await?;
+    }
 
     debug!(
         "Commander damage update broadcast completed for game {}",
@@ -554,6 +1725,7 @@ async fn handle_update_commander_damage(
     commander_number: i32,
     damage_amount: i32,
     game_id: Uuid,
+    actor_clerk_user_id: &str,
     state: &AppState,
 ) -> Result<()> {
     debug!(
@@ -561,6 +1733,18 @@ async fn handle_update_commander_damage(
         game_id, from_player_id, to_player_id, commander_number, damage_amount
     );
 
+    if damage_amount == 0 {
+        return Err(ApiError::BadRequest(
+            "Commander damage change must be non-zero".to_string(),
+        ));
+    }
+    let max_change = state.config.max_commander_damage_change;
+    if damage_amount.abs() > max_change {
+        return Err(ApiError::BadRequest(format!(
+            "Commander damage change too large (max ±{max_change})"
+        )));
+    }
+
     // Verify game is active
     let game = database::get_game_by_id(&state.db, game_id).await?;
     if game.status != "active" {
@@ -579,7 +1763,7 @@ async fn handle_update_commander_damage(
         .map(|cd| cd.damage)
         .unwrap_or(0);
 
-    let new_damage = current_damage + damage_amount;
+    let new_damage = database::checked_total("Commander damage", current_damage, damage_amount)?;
 
     // Update commander damage
     let _updated_damage = database::update_commander_damage(
@@ -589,6 +1773,19 @@ async fn handle_update_commander_damage(
         to_player_id,
         commander_number,
         new_damage,
+        None,
+        actor_clerk_user_id,
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "commander_damage",
+            serde_json::json!({
+                "fromPlayerId": from_player_id,
+                "toPlayerId": to_player_id,
+                "commanderNumber": commander_number,
+                "damageAmount": damage_amount,
+                "newDamage": new_damage,
+            }),
+        ),
     )
     .await?;
 
@@ -605,6 +1802,7 @@ async fn handle_update_commander_damage(
         commander_number,
         new_damage,
         damage_amount,
+        actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
     };
 
     info!(
@@ -612,7 +1810,18 @@ async fn handle_update_commander_damage(
         game_id, message
     );
 
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+
+    if new_damage >= game.commander_lethal {
+        eliminate_player(
+            state,
+            game_id,
+            to_player_id,
+            EliminationCause::CommanderDamage,
+            actor_clerk_user_id,
+        )
+        .await?;
+    }
 
     debug!(
         "Commander damage update broadcast completed for game {}",
@@ -621,10 +1830,96 @@ async fn handle_update_commander_damage(
     Ok(())
 }
 
+async fn handle_bulk_commander_damage(
+    entries: Vec<UpdateCommanderDamageRequest>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    if entries.is_empty() || entries.len() > MAX_BULK_COMMANDER_DAMAGE_ENTRIES {
+        return Err(ApiError::BadRequest(format!(
+            "Between 1 and {MAX_BULK_COMMANDER_DAMAGE_ENTRIES} entries are required"
+        )));
+    }
+
+    if entries.iter().any(|entry| entry.damage_amount == 0) {
+        return Err(ApiError::BadRequest(
+            "Commander damage change must be non-zero".to_string(),
+        ));
+    }
+
+    let max_change = state.config.max_commander_damage_change;
+    if entries
+        .iter()
+        .any(|entry| entry.damage_amount.abs() > max_change)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Commander damage change too large (max ±{max_change})"
+        )));
+    }
+
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let updated = database::apply_commander_damage_batch(
+        &state.db,
+        game_id,
+        &entries,
+        actor_clerk_user_id,
+        |updated| {
+            NewAuditEntry::new(
+                actor_clerk_user_id,
+                "bulk_commander_damage",
+                serde_json::json!({
+                    "changes": CommanderDamageChange::from_batch(&entries, updated),
+                }),
+            )
+        },
+    )
+    .await?;
+    let changes = CommanderDamageChange::from_batch(&entries, &updated);
+
+    // Broadcast all changes as one message
+    state
+        .broadcast_to_game(
+            game_id,
+            WebSocketMessage::CommanderDamageBulkUpdate {
+                game_id,
+                changes,
+                actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
+            },
+        )
+        .await;
+
+    for damage in updated
+        .iter()
+        .filter(|damage| damage.damage >= game.commander_lethal)
+    {
+        eliminate_player(
+            state,
+            game_id,
+            damage.to_player_id,
+            EliminationCause::CommanderDamage,
+            actor_clerk_user_id,
+        )
+        .await?;
+    }
+
+    debug!(
+        "Bulk commander damage broadcast completed for game {}",
+        game_id
+    );
+    Ok(())
+}
+
 async fn handle_toggle_partner(
     player_id: Uuid,
     enable_partner: bool,
     game_id: Uuid,
+    actor_clerk_user_id: &str,
     state: &AppState,
 ) -> Result<()> {
     debug!(
@@ -632,14 +1927,25 @@ async fn handle_toggle_partner(
         game_id, player_id, enable_partner
     );
 
-    // Verify game is active
+    // Verify game is active, or still in the lobby
     let game = database::get_game_by_id(&state.db, game_id).await?;
-    if game.status != "active" {
+    if !game.is_open() {
         return Err(ApiError::GameNotActive);
     }
 
     // Toggle partner status
-    database::toggle_partner(&state.db, game_id, player_id, enable_partner).await?;
+    let restored = database::toggle_partner(
+        &state.db,
+        game_id,
+        player_id,
+        enable_partner,
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "toggle_partner",
+            serde_json::json!({ "playerId": player_id, "enablePartner": enable_partner }),
+        ),
+    )
+    .await?;
 
     info!(
         "Partner {} for player {} in game {}",
@@ -664,27 +1970,641 @@ async fn handle_toggle_partner(
         game_id, message
     );
 
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+
+    // Re-enabling a partner brings back damage its commander had already dealt
+    if !restored.is_empty() {
+        state
+            .broadcast_to_game(
+                game_id,
+                WebSocketMessage::CommanderDamageBulkUpdate {
+                    game_id,
+                    changes: restored,
+                    actor_clerk_user_id: Some(actor_clerk_user_id.to_string()),
+                },
+            )
+            .await;
+    }
 
     debug!("Partner toggle broadcast completed for game {}", game_id);
     Ok(())
 }
 
+async fn handle_set_commander_name(
+    player_id: Uuid,
+    commander_name: Option<String>,
+    partner_name: Option<String>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    debug!(
+        "Processing set commander name for game {}, player {}",
+        game_id, player_id
+    );
+
+    // Verify game is active, or still in the lobby
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if !game.is_open() {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let player = database::set_commander_name(
+        &state.db,
+        game_id,
+        player_id,
+        commander_name,
+        partner_name,
+        |player| {
+            NewAuditEntry::new(
+                actor_clerk_user_id,
+                "set_commander_name",
+                serde_json::json!({
+                    "playerId": player_id,
+                    "commanderName": player.commander_name,
+                    "partnerName": player.partner_name,
+                }),
+            )
+        },
+    )
+    .await?;
+
+    // Broadcast the update
+    let message = WebSocketMessage::CommanderNameUpdated {
+        game_id,
+        player_id,
+        commander_name: player.commander_name,
+        partner_name: player.partner_name,
+        version: player.version,
+    };
+
+    state.broadcast_to_game(game_id, message).await;
+
+    debug!("Commander name broadcast completed for game {}", game_id);
+    Ok(())
+}
+
+async fn handle_set_seat_color(
+    player_id: Uuid,
+    color: Option<String>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active, or still in the lobby
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if !game.is_open() {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let player = database::set_seat_color(&state.db, game_id, player_id, color, |player| {
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "set_seat_color",
+            serde_json::json!({
+                "playerId": player_id,
+                "seatColor": player.seat_color,
+            }),
+        )
+    })
+    .await?;
+
+    let message = WebSocketMessage::SeatColorUpdated {
+        game_id,
+        player_id,
+        color: player.seat_color,
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+    Ok(())
+}
+
+/// Record a commander cast, or zero the count when `reset` is set
+async fn handle_commander_casts(
+    player_id: Uuid,
+    reset: bool,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let audit = |player: &Player| {
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            if reset {
+                "reset_commander_casts"
+            } else {
+                "cast_commander"
+            },
+            serde_json::json!({
+                "playerId": player_id,
+                "commanderCasts": player.commander_casts,
+            }),
+        )
+    };
+    let player = if reset {
+        database::reset_commander_casts(&state.db, game_id, player_id, audit).await?
+    } else {
+        database::increment_commander_casts(&state.db, game_id, player_id, audit).await?
+    };
+
+    let message = WebSocketMessage::CommanderCastsUpdated {
+        game_id,
+        player_id,
+        commander_casts: player.commander_casts,
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+
+    debug!("Commander casts broadcast completed for game {}", game_id);
+    Ok(())
+}
+
+enum OpeningHandChange {
+    Mulligans(i32),
+    HandSize(i32),
+}
+
+/// Record a player's mulligan count or opening hand size
+async fn handle_opening_hand(
+    player_id: Uuid,
+    change: OpeningHandChange,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let player = match change {
+        OpeningHandChange::Mulligans(count) => {
+            let audit = NewAuditEntry::new(
+                actor_clerk_user_id,
+                "set_mulligans",
+                serde_json::json!({ "playerId": player_id, "mulligans": count }),
+            );
+            database::set_player_mulligans(&state.db, game_id, player_id, count, audit).await?
+        }
+        OpeningHandChange::HandSize(size) => {
+            let audit = NewAuditEntry::new(
+                actor_clerk_user_id,
+                "set_hand_size",
+                serde_json::json!({ "playerId": player_id, "handSize": size }),
+            );
+            database::set_player_hand_size(&state.db, game_id, player_id, size, audit).await?
+        }
+    };
+
+    let message = WebSocketMessage::OpeningHandUpdated {
+        game_id,
+        player_id,
+        mulligans: player.mulligans,
+        hand_size: player.hand_size,
+        version: player.version,
+    };
+    state.broadcast_to_game(game_id, message).await;
+
+    debug!("Opening hand broadcast completed for game {}", game_id);
+    Ok(())
+}
+
+// Game counter handlers
+async fn handle_set_game_counter(
+    name: &str,
+    value: i32,
+    reset_each_turn: Option<bool>,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let counter = database::set_game_counter(
+        &state.db,
+        game_id,
+        name,
+        value,
+        reset_each_turn,
+        |counter| {
+            NewAuditEntry::new(
+                actor_clerk_user_id,
+                "set_game_counter",
+                serde_json::json!({
+                    "name": counter.name,
+                    "value": counter.value,
+                    "resetEachTurn": counter.reset_each_turn,
+                }),
+            )
+        },
+    )
+    .await?;
+
+    broadcast_game_counter(state, counter).await;
+    Ok(())
+}
+
+async fn handle_increment_game_counter(
+    name: &str,
+    amount: i32,
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    let counter = database::increment_game_counter(&state.db, game_id, name, amount, |counter| {
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "increment_game_counter",
+            serde_json::json!({
+                "name": counter.name,
+                "amount": amount,
+                "value": counter.value,
+            }),
+        )
+    })
+    .await?;
+
+    broadcast_game_counter(state, counter).await;
+    Ok(())
+}
+
+async fn handle_next_turn(
+    game_id: Uuid,
+    actor_clerk_user_id: &str,
+    state: &AppState,
+) -> Result<()> {
+    // Verify game is active
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+
+    // Per-turn counters (e.g. storm) go back to zero
+    let reset_counters = database::reset_turn_counters(&state.db, game_id, |counters| {
+        NewAuditEntry::new(
+            actor_clerk_user_id,
+            "next_turn",
+            serde_json::json!({
+                "resetCounters": counters.iter().map(|c| &c.name).collect::<Vec<_>>(),
+            }),
+        )
+    })
+    .await?;
+
+    for counter in reset_counters {
+        broadcast_game_counter(state, counter).await;
+    }
+
+    info!("Advanced turn in game {}", game_id);
+    Ok(())
+}
+
+async fn broadcast_game_counter(state: &AppState, counter: crate::models::GameCounter) {
+    let game_id = counter.game_id;
+    let message = WebSocketMessage::GameCounterUpdate {
+        game_id,
+        name: counter.name,
+        value: counter.value,
+        reset_each_turn: counter.reset_each_turn,
+    };
+    state.broadcast_to_game(game_id, message).await;
+}
+
+/// Announce a new player, enriched with the joining user's profile. `user`
+/// comes from authentication, which already falls back from token claims to a
+/// Clerk lookup to minimal info, so no further lookup is needed.
 pub async fn broadcast_player_joined(
     state: &AppState,
     game_id: Uuid,
     player: crate::models::Player,
-) {
-    // Enrich player with user info before broadcasting
-    let enriched_player = database::enrich_player_with_user(player).await;
+    user: &ClerkUser,
+) -> PlayerWithUser {
+    let enriched_player = PlayerWithUser::from_player(
+        player,
+        user.display_name(),
+        user.username.clone(),
+        user.image_url.clone(),
+    );
     let message = WebSocketMessage::PlayerJoined {
         game_id,
-        player: enriched_player,
+        player: enriched_player.clone(),
     };
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+    enriched_player
 }
 
-pub async fn broadcast_player_left(state: &AppState, game_id: Uuid, player_id: Uuid) {
+/// Announce a departure, then close the departed user's open connections so
+/// they stop receiving the game's events
+pub async fn broadcast_player_left(
+    state: &AppState,
+    game_id: Uuid,
+    player_id: Uuid,
+    clerk_user_id: &str,
+) {
     let message = WebSocketMessage::PlayerLeft { game_id, player_id };
-    state.broadcast_to_game(game_id, message);
+    state.broadcast_to_game(game_id, message).await;
+    state.disconnect_user(game_id, clerk_user_id);
+    recount_end_votes(state, game_id, player_id, clerk_user_id).await;
+}
+
+/// Announce the game start the first time anyone's life changes, unless the
+/// host already started it from the lobby
+pub async fn broadcast_game_started_if_first_change(state: &AppState, game_id: Uuid) -> Result<()> {
+    if database::count_life_changes(&state.db, game_id).await? != 1 {
+        return Ok(());
+    }
+
+    let game_state = database::get_game_state_with_users(&state.db, game_id).await?;
+    // Games started from the lobby were announced when the host started them
+    if game_state.game.started_at.is_some() {
+        return Ok(());
+    }
+    state
+        .broadcast_to_game(game_id, WebSocketMessage::GameStarted { game_state })
+        .await;
+    Ok(())
+}
+
+/// Knock a player out of an active game by concession. They keep their seat,
+/// life history and commander damage; conceding again is a no-op.
+pub async fn concede_player(
+    state: &AppState,
+    game_id: Uuid,
+    player_id: Uuid,
+    actor_clerk_user_id: &str,
+) -> Result<()> {
+    let game = database::get_game_by_id(&state.db, game_id).await?;
+    if game.status != "active" {
+        return Err(ApiError::GameNotActive);
+    }
+    database::ensure_can_control_player(&state.db, &game, player_id, actor_clerk_user_id).await?;
+
+    eliminate_player(
+        state,
+        game_id,
+        player_id,
+        EliminationCause::Concede,
+        actor_clerk_user_id,
+    )
+    .await
+}
+
+/// Eliminate a player and announce it, unless they were already out or the
+/// game leaves life and commander damage eliminations to its players
+pub async fn eliminate_player(
+    state: &AppState,
+    game_id: Uuid,
+    player_id: Uuid,
+    cause: EliminationCause,
+    actor_clerk_user_id: &str,
+) -> Result<()> {
+    let automatic = matches!(
+        cause,
+        EliminationCause::Life | EliminationCause::CommanderDamage
+    );
+    if automatic
+        && !database::get_game_by_id(&state.db, game_id)
+            .await?
+            .auto_eliminate
+    {
+        debug!(
+            "Not eliminating player {} in game {} ({}): auto-elimination is off",
+            player_id,
+            game_id,
+            cause.as_str()
+        );
+        return Ok(());
+    }
+
+    let audit = NewAuditEntry::new(
+        actor_clerk_user_id,
+        "player_eliminated",
+        serde_json::json!({
+            "playerId": player_id,
+            "cause": cause,
+        }),
+    );
+    let Some(player) =
+        database::eliminate_player(&state.db, game_id, player_id, cause, audit).await?
+    else {
+        return Ok(());
+    };
+
+    info!(
+        "Player {} eliminated in game {} ({})",
+        player_id,
+        game_id,
+        cause.as_str()
+    );
+    state
+        .broadcast_to_game(
+            game_id,
+            WebSocketMessage::PlayerEliminated {
+                game_id,
+                player_id,
+                cause,
+                version: player.version,
+            },
+        )
+        .await;
+    recount_end_votes(state, game_id, player_id, actor_clerk_user_id).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, models::LifeChangeReason, state::ConnectionLimits, test_support};
+
+    #[tokio::test]
+    async fn lagging_receivers_get_a_full_state() {
+        let pool = test_support::pool().await;
+        let game = test_support::create_game(&pool, "host").await;
+        let limits = ConnectionLimits {
+            max_per_game: 32,
+            max_per_user: 3,
+        };
+        let state = AppState::new(pool, limits, 2, Config::default());
+        let mut receiver = state.get_game_receiver(game.id);
+
+        for has_partner in [true, false, true, false, true] {
+            let message = WebSocketMessage::PartnerToggled {
+                game_id: game.id,
+                player_id: Uuid::new_v4(),
+                has_partner,
+            };
+            state.broadcast_to_game(game.id, message).await;
+        }
+
+        let Err(RecvError::Lagged(skipped)) = receiver.recv().await else {
+            panic!("a receiver two messages deep lags behind five");
+        };
+        let event = lag_resync(&state, game.id, skipped).await.unwrap();
+        match event.message {
+            WebSocketMessage::FullState { game_state } => {
+                assert_eq!(game_state.game.id, game.id)
+            }
+            other => panic!("unexpected resync: {other:?}"),
+        }
+        assert_eq!(state.broadcast_metrics().total_lag_events, 1);
+    }
+
+    #[tokio::test]
+    async fn zero_deltas_change_and_broadcast_nothing() {
+        let state = test_support::app_state().await;
+        let game = test_support::create_game(&state.db, "host").await;
+        let host = database::get_player_by_user(&state.db, game.id, "host")
+            .await
+            .unwrap();
+        let guest =
+            database::join_game(&state.db, game.id, "user_1", Default::default(), 1, |_| {
+                test_support::audit("user_1", "join")
+            })
+            .await
+            .unwrap();
+        let mut receiver = state.get_game_receiver(game.id);
+        let zero_damage = UpdateCommanderDamageRequest {
+            from_player_id: host.id,
+            to_player_id: guest.id,
+            commander_number: 1,
+            damage_amount: 0,
+        };
+
+        let rejected = |result: Result<()>| matches!(result, Err(ApiError::BadRequest(_)));
+        assert!(rejected(
+            handle_life_update(guest.id, 0, None, None, game.id, "host", &state).await
+        ));
+        assert!(rejected(
+            handle_update_commander_damage(host.id, guest.id, 1, 0, game.id, "host", &state).await
+        ));
+        assert!(rejected(
+            handle_bulk_commander_damage(vec![zero_damage], game.id, "host", &state).await
+        ));
+
+        let game_state = database::get_game_state(&state.db, game.id).await.unwrap();
+        assert!(game_state.recent_changes.is_empty());
+        assert!(
+            game_state
+                .commander_damage
+                .iter()
+                .all(|row| row.damage == 0)
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn changes_the_floor_absorbs_write_and_broadcast_nothing() {
+        let state = test_support::app_state_with_config(Config {
+            life_floor: Some(5),
+            ..Config::default()
+        })
+        .await;
+        let game = test_support::create_game(&state.db, "host").await;
+        let guest =
+            database::join_game(&state.db, game.id, "user_1", Default::default(), 1, |_| {
+                test_support::audit("user_1", "join")
+            })
+            .await
+            .unwrap();
+        sqlx::query("UPDATE players SET current_life = 5 WHERE id = ?")
+            .bind(guest.id.to_string())
+            .execute(&state.db)
+            .await
+            .unwrap();
+        let before = database::get_player(&state.db, game.id, guest.id)
+            .await
+            .unwrap();
+        let mut receiver = state.get_game_receiver(game.id);
+
+        handle_life_update(guest.id, -3, None, None, game.id, "host", &state)
+            .await
+            .unwrap();
+        handle_set_life(guest.id, 3, None, None, None, None, game.id, "host", &state)
+            .await
+            .unwrap();
+
+        let after = database::get_player(&state.db, game.id, guest.id)
+            .await
+            .unwrap();
+        assert_eq!(after.current_life, 5);
+        assert_eq!(after.version, before.version);
+        let game_state = database::get_game_state(&state.db, game.id).await.unwrap();
+        assert!(game_state.recent_changes.is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn msgpack_messages_match_their_json_form() {
+        let message = WebSocketMessage::LifeUpdate {
+            game_id: Uuid::new_v4(),
+            player_id: Uuid::new_v4(),
+            new_life: 33,
+            change_amount: -7,
+            version: 4,
+            source_player_id: Some(Uuid::new_v4()),
+            reason: Some(LifeChangeReason::Combat),
+            actor_clerk_user_id: Some("user_1".to_string()),
+        };
+
+        let Message::Binary(bytes) = Encoding::Msgpack.frame(&message).unwrap() else {
+            panic!("msgpack frames are binary");
+        };
+        let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, serde_json::to_value(&message).unwrap());
+
+        let round_tripped: WebSocketMessage = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(round_tripped).unwrap(),
+            serde_json::to_value(&message).unwrap()
+        );
+    }
+
+    #[test]
+    fn msgpack_requests_parse_like_json() {
+        let player_id = Uuid::new_v4();
+        let bytes = rmp_serde::to_vec_named(&serde_json::json!({
+            "action": "setLife",
+            "requestId": "r1",
+            "playerId": player_id,
+            "life": 12,
+            "expectedVersion": 3,
+        }))
+        .unwrap();
+
+        let value: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        let envelope = parse_envelope(value).unwrap();
+        assert_eq!(envelope.request_id.as_deref(), Some("r1"));
+        match envelope.request {
+            WebSocketRequest::SetLife {
+                player_id: parsed,
+                life,
+                expected_version,
+                expected_current,
+                ..
+            } => {
+                assert_eq!(parsed, player_id);
+                assert_eq!(life, 12);
+                assert_eq!(expected_version, Some(3));
+                assert_eq!(expected_current, None);
+            }
+            other => panic!("unexpected request: {other:?}"),
+        }
+    }
 }